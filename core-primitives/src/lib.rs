@@ -16,7 +16,7 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use account::EthereumSignature;
+use account::{EthereumMultiSignature, EthereumSignature};
 use sp_runtime::traits::BlakeTwo256;
 pub use sp_runtime::OpaqueExtrinsic as UncheckedExtrinsic;
 use sp_runtime::{
@@ -25,11 +25,17 @@ use sp_runtime::{
 };
 
 pub type Block = generic::Block<Header, UncheckedExtrinsic>;
-/// Alias to 512-bit hash when used in the context of a transaction signature on the chain.
-pub type Signature = EthereumSignature;
+/// Signature of a transaction dispatching a `Call`, over either its raw payload or its EIP-712
+/// typed data.
+///
+/// It replaces `EthereumSignature` from spec version 157 and transaction version 3, whose
+/// extrinsics start their signature with the `EthereumMultiSignature` variant. Clients must use
+/// moonbeam-types-bundle 1.2.0 or later and send their existing signatures as `{ Raw: signature }`,
+/// which is verified exactly as before.
+pub type Signature<Call> = EthereumMultiSignature<Call>;
 /// Some way of identifying an account on the chain. We intentionally make it equivalent
 /// to the public key of our transaction signing scheme.
-pub type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::AccountId;
+pub type AccountId = <<EthereumSignature as Verify>::Signer as IdentifyAccount>::AccountId;
 /// Balance of an account.
 pub type Balance = u128;
 /// An index to a block.
@@ -585,7 +585,7 @@ export const moonbeamDefinitions = {
     },
     ,
     {
-      minmax: [155, undefined],
+      minmax: [155, 156],
       types: {
         AccountId: "EthereumAccountId",
         AccountId32: "H256",
@@ -718,6 +718,146 @@ export const moonbeamDefinitions = {
         },
       },
     },
+    {
+      minmax: [157, undefined],
+      types: {
+        AccountId: "EthereumAccountId",
+        AccountId32: "H256",
+        AccountInfo: "AccountInfoWithTripleRefCount",
+        Address: "AccountId",
+        AuthorId: "AccountId32",
+        Balance: "u128",
+        LookupSource: "AccountId",
+        Account: {
+          nonce: "U256",
+          balance: "u128",
+        },
+        ExtrinsicSignature: "EthereumMultiSignature",
+        RoundIndex: "u32",
+        Candidate: {
+          id: "AccountId",
+          fee: "Perbill",
+          bond: "Balance",
+          nominators: "Vec<Bond>",
+          total: "Balance",
+          state: "CollatorStatus",
+        },
+        Nominator: {
+          nominations: "Vec<Bond>",
+          total: "Balance",
+        },
+        Bond: {
+          owner: "AccountId",
+          amount: "Balance",
+        },
+        CollatorStatus: {
+          _enum: ["Active", "Idle", { Leaving: "RoundIndex" }],
+        },
+        TxPoolResultContent: {
+          pending: "HashMap<H160, HashMap<U256, PoolTransaction>>",
+          queued: "HashMap<H160, HashMap<U256, PoolTransaction>>",
+        },
+        TxPoolResultInspect: {
+          pending: "HashMap<H160, HashMap<U256, Summary>>",
+          queued: "HashMap<H160, HashMap<U256, Summary>>",
+        },
+        TxPoolResultStatus: {
+          pending: "U256",
+          queued: "U256",
+        },
+        Summary: "Bytes",
+        PoolTransaction: {
+          hash: "H256",
+          nonce: "U256",
+          block_hash: "Option<H256>",
+          block_number: "Option<U256>",
+          from: "H160",
+          to: "Option<H160>",
+          value: "U256",
+          gas_price: "U256",
+          gas: "U256",
+          input: "Bytes",
+        },
+        // Staking inflation
+        Range: "RangeBalance",
+        RangeBalance: {
+          min: "Balance",
+          ideal: "Balance",
+          max: "Balance",
+        },
+        RangePerbill: {
+          min: "Perbill",
+          ideal: "Perbill",
+          max: "Perbill",
+        },
+        InflationInfo: {
+          expect: "RangeBalance",
+          annual: "RangePerbill",
+          round: "RangePerbill",
+        },
+        OrderedSet: "Vec<Bond>",
+        Collator: {
+          id: "AccountId",
+          bond: "Balance",
+          nominators: "Vec<Bond>",
+          total: "Balance",
+          state: "CollatorStatus",
+        },
+        Collator2: {
+          id: "AccountId",
+          bond: "Balance",
+          nominators: "Vec<AccountId>",
+          top_nominators: "Vec<Bond>",
+          bottom_nominators: "Vec<Bond>",
+          total_counted: "Balance",
+          total_backing: "Balance",
+          state: "CollatorStatus",
+        },
+        NominatorAdded: {
+          _enum: ["AddedToBottom", { AddedToTop: "Balance" }],
+        },
+        CollatorSnapshot: {
+          bond: "Balance",
+          nominators: "Vec<Bond>",
+          total: "Balance",
+        },
+        SystemInherentData: {
+          validation_data: "PersistedValidationData",
+          relay_chain_state: "StorageProof",
+          downward_messages: "Vec<InboundDownwardMessage>",
+          horizontal_messages: "BTreeMap<ParaId, Vec<InboundHrmpMessage>>",
+        },
+        RelayChainAccountId: "AccountId32",
+        RoundInfo: {
+          current: "RoundIndex",
+          first: "BlockNumber",
+          length: "u32",
+        },
+        RewardInfo: {
+          total_reward: "Balance",
+          claimed_reward: "Balance",
+        },
+        RegistrationInfo: {
+          account: "AccountId",
+          deposit: "Balance",
+        },
+        ParachainBondConfig: {
+          account: "AccountId",
+          percent: "Percent",
+        },
+        EthereumSignature: {
+          r: "H256",
+          s: "H256",
+          v: "U8",
+        },
+        EthereumMultiSignature: {
+          _enum: {
+            Raw: "EthereumSignature",
+            Eip712: "EthereumSignature",
+          },
+        },
+      },
+    },
   ],
 } as OverrideBundleDefinition;
 
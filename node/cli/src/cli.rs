@@ -61,6 +61,12 @@ pub enum Subcommand {
 	#[structopt(name = "benchmark", about = "Benchmark runtime pallets.")]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
 
+	/// Try runtime upgrades against a snapshot of the chain state.
+	///
+	/// Only `on-runtime-upgrade` is supported, executing blocks needs a newer Substrate.
+	#[structopt(name = "try-runtime")]
+	TryRuntime(try_runtime_cli::TryRuntimeCmd),
+
 	/// Key management cli utilities
 	Key(KeyCmd),
 }
@@ -165,6 +171,11 @@ pub struct RunCmd {
 	#[structopt(long, default_value = "10000")]
 	pub max_past_logs: u32,
 
+	/// Percentage by which the priority of a transaction, the gas price for Ethereum transactions,
+	/// must exceed that of the pending transaction it replaces.
+	#[structopt(long, default_value = "10")]
+	pub txpool_price_bump: u8,
+
 	/// Force using Moonbase native runtime.
 	#[structopt(long = "force-moonbase")]
 	pub force_moonbase: bool,
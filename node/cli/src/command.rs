@@ -31,7 +31,7 @@ use sc_cli::{
 use sc_service::config::{BasePath, PrometheusConfig};
 use service::{chain_spec, frontier_database_dir, IdentifyVariant};
 use sp_core::hexdisplay::HexDisplay;
-use sp_runtime::traits::Block as _;
+use sp_runtime::{traits::Block as _, Percent};
 use std::{io::Write, net::SocketAddr};
 
 fn load_spec(
@@ -443,6 +443,65 @@ pub fn run() -> Result<()> {
 					.into())
 			}
 		}
+		Some(Subcommand::TryRuntime(cmd)) => {
+			if cfg!(feature = "try-runtime") {
+				let runner = cli.create_runner(cmd)?;
+				let chain_spec = &runner.config().chain_spec;
+
+				// The command runs without a client, so it needs its own task manager.
+				let registry = &runner
+					.config()
+					.prometheus_config
+					.as_ref()
+					.map(|cfg| &cfg.registry);
+				let task_manager =
+					sc_service::TaskManager::new(runner.config().task_executor.clone(), *registry)
+						.map_err(|e| sc_cli::Error::Service(sc_service::Error::Prometheus(e)))?;
+
+				if chain_spec.is_moonbeam() {
+					runner.async_run(|config| {
+						Ok((
+							cmd.run::<service::moonbeam_runtime::Block, service::MoonbeamExecutor>(
+								config,
+							),
+							task_manager,
+						))
+					})
+				} else if chain_spec.is_moonriver() {
+					runner.async_run(|config| {
+						Ok((
+							cmd.run::<service::moonriver_runtime::Block, service::MoonriverExecutor>(
+								config,
+							),
+							task_manager,
+						))
+					})
+				} else if chain_spec.is_moonshadow() {
+					runner.async_run(|config| {
+						Ok((
+							cmd.run::<
+								service::moonshadow_runtime::Block,
+								service::MoonshadowExecutor,
+							>(config),
+							task_manager,
+						))
+					})
+				} else {
+					runner.async_run(|config| {
+						Ok((
+							cmd.run::<service::moonbase_runtime::Block, service::MoonbaseExecutor>(
+								config,
+							),
+							task_manager,
+						))
+					})
+				}
+			} else {
+				Err("Try-runtime wasn't enabled when building the node. \
+				You can enable it with `--features try-runtime`."
+					.into())
+			}
+		}
 		Some(Subcommand::Key(cmd)) => Ok(cmd.run(&cli)?),
 		None => {
 			let runner = cli.create_runner(&(*cli.run).normalize())?;
@@ -458,6 +517,7 @@ pub fn run() -> Result<()> {
 					ethapi_trace_cache_duration: cli.run.ethapi_trace_cache_duration,
 					max_past_logs: cli.run.max_past_logs,
 				};
+				let transaction_price_bump = Percent::from_percent(cli.run.txpool_price_bump);
 
 				// If dev service was requested, start up manual or instant seal.
 				// Otherwise continue with the normal parachain node.
@@ -478,8 +538,14 @@ pub fn run() -> Result<()> {
 						"Alice",
 					));
 
-					return service::new_dev(config, author_id, cli.run.sealing, rpc_config)
-						.map_err(Into::into);
+					return service::new_dev(
+						config,
+						author_id,
+						cli.run.sealing,
+						rpc_config,
+						transaction_price_bump,
+					)
+					.map_err(Into::into);
 				}
 
 				let polkadot_cli = RelayChainCli::new(
@@ -527,7 +593,13 @@ pub fn run() -> Result<()> {
 					service::start_node::<
 						service::moonbeam_runtime::RuntimeApi,
 						service::MoonbeamExecutor,
-					>(config, polkadot_config, id, rpc_config)
+					>(
+						config,
+						polkadot_config,
+						id,
+						rpc_config,
+						transaction_price_bump,
+					)
 					.await
 					.map(|r| r.0)
 					.map_err(Into::into)
@@ -535,7 +607,13 @@ pub fn run() -> Result<()> {
 					service::start_node::<
 						service::moonriver_runtime::RuntimeApi,
 						service::MoonriverExecutor,
-					>(config, polkadot_config, id, rpc_config)
+					>(
+						config,
+						polkadot_config,
+						id,
+						rpc_config,
+						transaction_price_bump,
+					)
 					.await
 					.map(|r| r.0)
 					.map_err(Into::into)
@@ -543,7 +621,13 @@ pub fn run() -> Result<()> {
 					service::start_node::<
 						service::moonshadow_runtime::RuntimeApi,
 						service::MoonshadowExecutor,
-					>(config, polkadot_config, id, rpc_config)
+					>(
+						config,
+						polkadot_config,
+						id,
+						rpc_config,
+						transaction_price_bump,
+					)
 					.await
 					.map(|r| r.0)
 					.map_err(Into::into)
@@ -551,7 +635,13 @@ pub fn run() -> Result<()> {
 					service::start_node::<
 						service::moonbase_runtime::RuntimeApi,
 						service::MoonbaseExecutor,
-					>(config, polkadot_config, id, rpc_config)
+					>(
+						config,
+						polkadot_config,
+						id,
+						rpc_config,
+						transaction_price_bump,
+					)
 					.await
 					.map(|r| r.0)
 					.map_err(Into::into)
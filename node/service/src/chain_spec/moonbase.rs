@@ -29,7 +29,7 @@ use moonbase_runtime::{
 	CouncilCollectiveConfig, CrowdloanRewardsConfig, DemocracyConfig, EVMConfig,
 	EthereumChainIdConfig, EthereumConfig, GenesisConfig, InflationInfo, ParachainInfoConfig,
 	ParachainStakingConfig, Precompiles, Range, SchedulerConfig, SudoConfig, SystemConfig,
-	TechComitteeCollectiveConfig, WASM_BINARY,
+	TechComitteeCollectiveConfig, VestingConfig, WASM_BINARY,
 };
 use nimbus_primitives::NimbusId;
 use sc_service::ChainType;
@@ -71,6 +71,8 @@ pub fn development_chain_spec(mnemonic: Option<String>, num_accounts: Option<u32
 				vec![],
 				accounts.clone(),
 				3_000_000 * UNIT,
+				// Vesting
+				vec![],
 				Default::default(), // para_id
 				1281,               //ChainId
 			)
@@ -133,6 +135,8 @@ pub fn get_chain_spec(para_id: ParaId) -> ChainSpec {
 					AccountId::from_str("3Cd0A705a2DC65e5b1E1205896BaA2be8A07c6e0").unwrap(),
 				],
 				3_000_000 * UNIT,
+				// Vesting
+				vec![],
 				para_id,
 				1280, //ChainId
 			)
@@ -181,6 +185,7 @@ pub fn testnet_genesis(
 	nominations: Vec<(AccountId, AccountId, Balance)>,
 	endowed_accounts: Vec<AccountId>,
 	crowdloan_fund_pot: Balance,
+	vesting: Vec<(AccountId, u32, u32, Balance)>,
 	para_id: ParaId,
 	chain_id: u64,
 ) -> GenesisConfig {
@@ -260,6 +265,8 @@ pub fn testnet_genesis(
 				.collect(),
 		},
 		treasury: Default::default(),
+		// Grants are vested with `vested_transfer` once the chain is live.
+		vesting: VestingConfig { vesting },
 	}
 }
 
@@ -27,9 +27,9 @@ use evm::GenesisAccount;
 use moonbeam_runtime::{
 	currency::GLMR, AccountId, AuthorFilterConfig, AuthorMappingConfig, Balance, BalancesConfig,
 	CouncilCollectiveConfig, CrowdloanRewardsConfig, DemocracyConfig, EVMConfig,
-	EthereumChainIdConfig, EthereumConfig, GenesisConfig, InflationInfo, ParachainInfoConfig,
-	ParachainStakingConfig, Precompiles, Range, SchedulerConfig, SudoConfig, SystemConfig,
-	TechComitteeCollectiveConfig, WASM_BINARY,
+	EthereumChainIdConfig, EthereumConfig, GenesisConfig, InflationInfo, MaintenanceModeConfig,
+	ParachainInfoConfig, ParachainStakingConfig, Precompiles, Range, SchedulerConfig, SudoConfig,
+	SystemConfig, TechComitteeCollectiveConfig, VestingConfig, WASM_BINARY,
 };
 use nimbus_primitives::NimbusId;
 use sc_service::ChainType;
@@ -68,6 +68,8 @@ pub fn development_chain_spec(mnemonic: Option<String>, num_accounts: Option<u32
 				vec![],
 				accounts.clone(),
 				3_000_000 * GLMR,
+				// Vesting
+				vec![],
 				Default::default(), // para_id
 				1281,               //ChainId
 			)
@@ -124,6 +126,8 @@ pub fn get_chain_spec(para_id: ParaId) -> ChainSpec {
 					AccountId::from_str("3Cd0A705a2DC65e5b1E1205896BaA2be8A07c6e0").unwrap(),
 				],
 				3_000_000 * GLMR,
+				// Vesting
+				vec![],
 				para_id,
 				1280, //ChainId
 			)
@@ -171,6 +175,7 @@ pub fn testnet_genesis(
 	nominations: Vec<(AccountId, AccountId, Balance)>,
 	endowed_accounts: Vec<AccountId>,
 	crowdloan_fund_pot: Balance,
+	vesting: Vec<(AccountId, u32, u32, Balance)>,
 	para_id: ParaId,
 	chain_id: u64,
 ) -> GenesisConfig {
@@ -250,6 +255,13 @@ pub fn testnet_genesis(
 				.collect(),
 		},
 		treasury: Default::default(),
+		// Moonbeam launches in maintenance mode, governance enables the filtered calls once the
+		// chain is ready.
+		maintenance_mode: MaintenanceModeConfig {
+			start_in_maintenance_mode: true,
+		},
+		// Grants are vested with `vested_transfer` once the chain is live.
+		vesting: VestingConfig { vesting },
 	}
 }
 
@@ -63,6 +63,8 @@ pub fn staking_spec(para_id: ParaId) -> ChainSpec {
 					AccountId::from_str("f24FF3a9CF04c71Dbc94D0b566f7A27B94566cac").unwrap(),
 				],
 				3_000_000 * GLMR,
+				// Vesting
+				vec![],
 				para_id,
 				// Chain ID
 				1280,
@@ -38,6 +38,7 @@ use std::{
 };
 mod inherents;
 mod rpc;
+mod transaction_pool;
 use cumulus_client_network::build_block_announce_validator;
 use cumulus_client_service::{
 	prepare_node_config, start_collator, start_full_node, StartCollatorParams, StartFullNodeParams,
@@ -64,6 +65,13 @@ use sc_telemetry::{Telemetry, TelemetryWorker, TelemetryWorkerHandle};
 type FullClient<RuntimeApi, Executor> = TFullClient<Block, RuntimeApi, Executor>;
 type FullBackend = TFullBackend<Block>;
 type MaybeSelectChain = Option<sc_consensus::LongestChain<FullBackend, Block>>;
+/// The transaction pool of a full node, enforcing a price bump to replace pending transactions.
+type FullPool<RuntimeApi, Executor> = sc_transaction_pool::BasicPool<
+	transaction_pool::ReplacementChainApi<
+		sc_transaction_pool::FullChainApi<FullClient<RuntimeApi, Executor>, Block>,
+	>,
+	Block,
+>;
 
 native_executor_instance!(
 	pub MoonbeamExecutor,
@@ -170,7 +178,7 @@ pub fn open_frontier_backend(config: &Configuration) -> Result<Arc<fc_db::Backen
 	)?))
 }
 
-use sp_runtime::traits::BlakeTwo256;
+use sp_runtime::{traits::BlakeTwo256, Percent};
 use sp_trie::PrefixedMemoryDB;
 
 /// Builds a new object suitable for chain operations.
@@ -197,6 +205,7 @@ pub fn new_chain_ops(
 		} = new_partial::<moonbase_runtime::RuntimeApi, MoonbaseExecutor>(
 			config,
 			config.chain_spec.is_dev(),
+			transaction_pool::TRANSACTION_PRICE_BUMP,
 		)?;
 		Ok((
 			Arc::new(Client::Moonbase(client)),
@@ -214,6 +223,7 @@ pub fn new_chain_ops(
 		} = new_partial::<moonriver_runtime::RuntimeApi, MoonriverExecutor>(
 			config,
 			config.chain_spec.is_dev(),
+			transaction_pool::TRANSACTION_PRICE_BUMP,
 		)?;
 		Ok((
 			Arc::new(Client::Moonriver(client)),
@@ -231,6 +241,7 @@ pub fn new_chain_ops(
 		} = new_partial::<moonshadow_runtime::RuntimeApi, MoonshadowExecutor>(
 			config,
 			config.chain_spec.is_dev(),
+			transaction_pool::TRANSACTION_PRICE_BUMP,
 		)?;
 		Ok((
 			Arc::new(Client::Moonshadow(client)),
@@ -248,6 +259,7 @@ pub fn new_chain_ops(
 		} = new_partial::<moonbeam_runtime::RuntimeApi, MoonbeamExecutor>(
 			config,
 			config.chain_spec.is_dev(),
+			transaction_pool::TRANSACTION_PRICE_BUMP,
 		)?;
 		Ok((
 			Arc::new(Client::Moonbeam(client)),
@@ -261,18 +273,20 @@ pub fn new_chain_ops(
 /// Builds the PartialComponents for a parachain or development service
 ///
 /// Use this function if you don't actually need the full service, but just the partial in order to
-/// be able to perform chain operations.
+/// be able to perform chain operations. `transaction_price_bump` is the price bump of the
+/// transactions replacing pending ones in the pool, see `transaction_pool`.
 #[allow(clippy::type_complexity)]
 pub fn new_partial<RuntimeApi, Executor>(
 	config: &Configuration,
 	dev_service: bool,
+	transaction_price_bump: Percent,
 ) -> Result<
 	PartialComponents<
 		TFullClient<Block, RuntimeApi, Executor>,
 		FullBackend,
 		MaybeSelectChain,
 		sp_consensus::DefaultImportQueue<Block, TFullClient<Block, RuntimeApi, Executor>>,
-		sc_transaction_pool::FullPool<Block, FullClient<RuntimeApi, Executor>>,
+		FullPool<RuntimeApi, Executor>,
 		(
 			FrontierBlockImport<
 				Block,
@@ -327,13 +341,26 @@ where
 		None
 	};
 
-	let transaction_pool = sc_transaction_pool::BasicPool::new_full(
+	// Unlike `BasicPool::new_full`, this doesn't let offchain workers submit transactions to the
+	// pool, which none of our pallets do.
+	let pool_api = Arc::new(transaction_pool::ReplacementChainApi::new(
+		sc_transaction_pool::FullChainApi::new(
+			client.clone(),
+			config.prometheus_registry(),
+			&task_manager.spawn_essential_handle(),
+		),
+		transaction_price_bump,
+	));
+	let transaction_pool = Arc::new(sc_transaction_pool::BasicPool::with_revalidation_type(
 		config.transaction_pool.clone(),
 		config.role.is_authority().into(),
+		pool_api.clone(),
 		config.prometheus_registry(),
+		sc_transaction_pool::RevalidationType::Full,
 		task_manager.spawn_essential_handle(),
-		client.clone(),
-	);
+		client.info().best_number,
+	));
+	pool_api.set_pool(transaction_pool.pool());
 
 	let pending_transactions: PendingTransactions = Some(Arc::new(Mutex::new(HashMap::new())));
 
@@ -393,6 +420,9 @@ where
 /// Although the implementation should be the same in each runtime, this might change at some point.
 /// `TransactionConverters` is just a `fp_rpc::ConvertTransaction` implementor that proxies calls to
 /// each runtime implementation.
+///
+/// Only legacy transactions can be converted. Typed EIP-2718 transactions, e.g. with EIP-2930
+/// access lists, need the `TransactionV2` of a newer pallet-ethereum.
 pub enum TransactionConverters {
 	Moonbeam(moonbeam_runtime::TransactionConverter),
 	Moonbase(moonbase_runtime::TransactionConverter),
@@ -425,6 +455,7 @@ async fn start_node_impl<RuntimeApi, Executor>(
 	polkadot_config: Configuration,
 	id: polkadot_primitives::v0::Id,
 	rpc_config: RpcConfig,
+	transaction_price_bump: Percent,
 ) -> sc_service::error::Result<(TaskManager, Arc<TFullClient<Block, RuntimeApi, Executor>>)>
 where
 	RuntimeApi:
@@ -439,7 +470,7 @@ where
 
 	let parachain_config = prepare_node_config(parachain_config);
 
-	let params = new_partial(&parachain_config, false)?;
+	let params = new_partial(&parachain_config, false, transaction_price_bump)?;
 	let (
 		block_import,
 		pending_transactions,
@@ -650,6 +681,7 @@ pub async fn start_node<RuntimeApi, Executor>(
 	polkadot_config: Configuration,
 	id: polkadot_primitives::v0::Id,
 	rpc_config: RpcConfig,
+	transaction_price_bump: Percent,
 ) -> sc_service::error::Result<(TaskManager, Arc<TFullClient<Block, RuntimeApi, Executor>>)>
 where
 	RuntimeApi:
@@ -658,7 +690,14 @@ where
 		RuntimeApiCollection<StateBackend = sc_client_api::StateBackendFor<FullBackend, Block>>,
 	Executor: NativeExecutionDispatch + 'static,
 {
-	start_node_impl(parachain_config, polkadot_config, id, rpc_config).await
+	start_node_impl(
+		parachain_config,
+		polkadot_config,
+		id,
+		rpc_config,
+		transaction_price_bump,
+	)
+	.await
 }
 
 /// Builds a new development service. This service uses manual seal, and mocks
@@ -668,6 +707,7 @@ pub fn new_dev(
 	_author_id: Option<nimbus_primitives::NimbusId>,
 	sealing: cli_opt::Sealing,
 	rpc_config: RpcConfig,
+	transaction_price_bump: Percent,
 ) -> Result<TaskManager, ServiceError> {
 	use async_io::Timer;
 	use futures::Stream;
@@ -691,7 +731,11 @@ pub fn new_dev(
 				_telemetry_worker_handle,
 				frontier_backend,
 			),
-	} = new_partial::<moonbase_runtime::RuntimeApi, MoonbaseExecutor>(&config, true)?;
+	} = new_partial::<moonbase_runtime::RuntimeApi, MoonbaseExecutor>(
+		&config,
+		true,
+		transaction_price_bump,
+	)?;
 
 	let (network, system_rpc_tx, network_starter) =
 		sc_service::build_network(sc_service::BuildNetworkParams {
@@ -0,0 +1,322 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Replacement of the pending transactions of the transaction pool.
+//!
+//! The pool replaces a pending transaction by another one providing the same tag as soon as the
+//! new one has a higher priority. For Ethereum transactions the tag is the (sender, nonce) pair
+//! and the priority is the gas price, so like in geth, `ReplacementChainApi` rejects the new
+//! transaction unless its priority is higher by at least the price bump.
+//!
+//! The pending transactions are found by the tags they provide, whether they are ready or in the
+//! future queue of the pool.
+
+use futures::{future::BoxFuture, FutureExt};
+use parking_lot::RwLock;
+use sc_transaction_graph::{
+	error::Error as PoolError, BlockHash, ChainApi, ExtrinsicFor, ExtrinsicHash, NumberFor, Pool,
+};
+use sp_runtime::{
+	generic::BlockId,
+	transaction_validity::{
+		TransactionPriority, TransactionSource, TransactionTag, TransactionValidity,
+	},
+	PerThing, Percent,
+};
+use std::{
+	collections::HashMap,
+	sync::{Arc, Weak},
+};
+
+/// By default, a pending transaction is replaced by one with the same sender and nonce only if it
+/// pays a gas price at least 10% higher, like in geth.
+pub const TRANSACTION_PRICE_BUMP: Percent = Percent::from_percent(10);
+
+/// The transactions that were accepted by `ReplacementChainApi`, with their priority, by the tags
+/// they provide. Some of them may have left the pool since.
+type Providers<Hash> = HashMap<TransactionTag, Vec<(Hash, TransactionPriority)>>;
+
+/// A `ChainApi` enforcing the price bump of the transactions replacing pending ones.
+pub struct ReplacementChainApi<Api: ChainApi> {
+	inner: Api,
+	price_bump: Percent,
+	pool: RwLock<Weak<Pool<Self>>>,
+	providers: Arc<RwLock<Providers<ExtrinsicHash<Api>>>>,
+}
+
+impl<Api: ChainApi> ReplacementChainApi<Api> {
+	pub fn new(inner: Api, price_bump: Percent) -> Self {
+		Self {
+			inner,
+			price_bump,
+			pool: RwLock::new(Weak::new()),
+			providers: Default::default(),
+		}
+	}
+
+	/// Sets the pool whose pending transactions are checked. Until then, no transaction is
+	/// considered a replacement.
+	pub fn set_pool(&self, pool: &Arc<Pool<Self>>) {
+		*self.pool.write() = Arc::downgrade(pool);
+	}
+}
+
+impl<Api> ChainApi for ReplacementChainApi<Api>
+where
+	Api: ChainApi + 'static,
+	Api::ValidationFuture: 'static,
+{
+	type Block = Api::Block;
+	type Error = Api::Error;
+	type ValidationFuture = BoxFuture<'static, Result<TransactionValidity, Self::Error>>;
+	type BodyFuture = Api::BodyFuture;
+
+	fn validate_transaction(
+		&self,
+		at: &BlockId<Self::Block>,
+		source: TransactionSource,
+		uxt: ExtrinsicFor<Self>,
+	) -> Self::ValidationFuture {
+		let (hash, _) = self.inner.hash_and_length(&uxt);
+		let pool = self.pool.read().clone();
+		let providers = self.providers.clone();
+		let price_bump = self.price_bump;
+
+		self.inner
+			.validate_transaction(at, source, uxt)
+			.map(move |validity| {
+				let valid = match validity? {
+					Ok(valid) => valid,
+					invalid => return Ok(invalid),
+				};
+				let pool = match pool.upgrade() {
+					Some(pool) => pool,
+					None => return Ok(Ok(valid)),
+				};
+				let is_pending = |pending: &ExtrinsicHash<Api>| {
+					pool.validated_pool().check_is_known(pending, true).is_err()
+				};
+				let mut providers = providers.write();
+
+				// The transaction itself is pending when the pool revalidates it.
+				let mut replaced: Option<TransactionPriority> = None;
+				for tag in &valid.provides {
+					if let Some(tag_providers) = providers.get_mut(tag) {
+						tag_providers.retain(|(pending, _)| is_pending(pending));
+						for (_, priority) in
+							tag_providers.iter().filter(|(pending, _)| *pending != hash)
+						{
+							replaced = replaced.max(Some(*priority));
+						}
+					}
+				}
+				if let Some(old) = replaced {
+					let min_priority = old.saturating_add(price_bump.mul_ceil(old));
+					if valid.priority < min_priority {
+						return Err(PoolError::TooLowPriority {
+							old,
+							new: valid.priority,
+						}
+						.into());
+					}
+				}
+
+				for tag in &valid.provides {
+					let tag_providers = providers.entry(tag.clone()).or_default();
+					tag_providers.retain(|(pending, _)| *pending != hash);
+					tag_providers.push((hash, valid.priority));
+				}
+				// The tags of the transactions that left the pool are only dropped when another
+				// transaction provides them, so they are pruned once they outnumber the pool.
+				let status = pool.validated_pool().status();
+				if providers.len() > 2 * (status.ready + status.future) {
+					providers.retain(|_, tag_providers| {
+						tag_providers
+							.retain(|(pending, _)| *pending == hash || is_pending(pending));
+						!tag_providers.is_empty()
+					});
+				}
+
+				Ok(Ok(valid))
+			})
+			.boxed()
+	}
+
+	fn block_id_to_number(
+		&self,
+		at: &BlockId<Self::Block>,
+	) -> Result<Option<NumberFor<Self>>, Self::Error> {
+		self.inner.block_id_to_number(at)
+	}
+
+	fn block_id_to_hash(
+		&self,
+		at: &BlockId<Self::Block>,
+	) -> Result<Option<BlockHash<Self>>, Self::Error> {
+		self.inner.block_id_to_hash(at)
+	}
+
+	fn hash_and_length(&self, uxt: &ExtrinsicFor<Self>) -> (ExtrinsicHash<Self>, usize) {
+		self.inner.hash_and_length(uxt)
+	}
+
+	fn block_body(&self, at: &BlockId<Self::Block>) -> Self::BodyFuture {
+		self.inner.block_body(at)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::{executor::block_on, future};
+	use parity_scale_codec::Encode;
+	use sc_transaction_graph::Options;
+	use sp_runtime::{
+		testing::{Block as TestBlock, ExtrinsicWrapper},
+		traits::{BlakeTwo256, Hash},
+		transaction_validity::ValidTransaction,
+	};
+
+	/// A transaction of a single sender, made of its nonce and its priority.
+	type Extrinsic = ExtrinsicWrapper<(u64, u64)>;
+	type Block = TestBlock<Extrinsic>;
+
+	/// Accepts every transaction, providing its nonce and requiring the previous one.
+	struct TestApi;
+
+	impl ChainApi for TestApi {
+		type Block = Block;
+		type Error = PoolError;
+		type ValidationFuture = future::Ready<Result<TransactionValidity, PoolError>>;
+		type BodyFuture = future::Ready<Result<Option<Vec<Extrinsic>>, PoolError>>;
+
+		fn validate_transaction(
+			&self,
+			_at: &BlockId<Block>,
+			_source: TransactionSource,
+			uxt: Extrinsic,
+		) -> Self::ValidationFuture {
+			let (nonce, priority) = *uxt;
+			let requires = match nonce {
+				0 => vec![],
+				_ => vec![(nonce - 1).encode()],
+			};
+			future::ready(Ok(Ok(ValidTransaction {
+				priority,
+				requires,
+				provides: vec![nonce.encode()],
+				longevity: 64,
+				propagate: true,
+			})))
+		}
+
+		fn block_id_to_number(&self, at: &BlockId<Block>) -> Result<Option<u64>, PoolError> {
+			Ok(match at {
+				BlockId::Number(number) => Some(*number),
+				BlockId::Hash(_) => None,
+			})
+		}
+
+		fn block_id_to_hash(
+			&self,
+			_at: &BlockId<Block>,
+		) -> Result<Option<BlockHash<Self>>, PoolError> {
+			Ok(Some(Default::default()))
+		}
+
+		fn hash_and_length(&self, uxt: &Extrinsic) -> (ExtrinsicHash<Self>, usize) {
+			let encoded = uxt.encode();
+			(BlakeTwo256::hash(&encoded), encoded.len())
+		}
+
+		fn block_body(&self, _at: &BlockId<Block>) -> Self::BodyFuture {
+			future::ready(Ok(None))
+		}
+	}
+
+	fn pool() -> Arc<Pool<ReplacementChainApi<TestApi>>> {
+		let api = Arc::new(ReplacementChainApi::new(TestApi, TRANSACTION_PRICE_BUMP));
+		let pool = Arc::new(Pool::new(Options::default(), true.into(), api.clone()));
+		api.set_pool(&pool);
+		pool
+	}
+
+	fn submit(
+		pool: &Pool<ReplacementChainApi<TestApi>>,
+		nonce: u64,
+		priority: u64,
+	) -> Result<(), PoolError> {
+		block_on(pool.submit_one(
+			&BlockId::Number(0),
+			TransactionSource::External,
+			ExtrinsicWrapper::from((nonce, priority)),
+		))
+		.map(drop)
+	}
+
+	#[test]
+	fn replacement_below_the_price_bump_is_rejected() {
+		let pool = pool();
+		submit(&pool, 0, 1_000).unwrap();
+
+		assert!(matches!(
+			submit(&pool, 0, 1_099),
+			Err(PoolError::TooLowPriority {
+				old: 1_000,
+				new: 1_099
+			})
+		));
+		assert_eq!(pool.validated_pool().status().ready, 1);
+	}
+
+	#[test]
+	fn replacement_at_the_price_bump_is_accepted() {
+		let pool = pool();
+		submit(&pool, 0, 1_000).unwrap();
+
+		submit(&pool, 0, 1_100).unwrap();
+		assert_eq!(pool.validated_pool().status().ready, 1);
+		// The replacement must itself be bumped.
+		assert!(submit(&pool, 0, 1_200).is_err());
+		submit(&pool, 0, 1_210).unwrap();
+		assert_eq!(pool.validated_pool().status().ready, 1);
+	}
+
+	#[test]
+	fn replacement_of_a_future_transaction_is_checked() {
+		let pool = pool();
+		submit(&pool, 1, 1_000).unwrap();
+		assert_eq!(pool.validated_pool().status().future, 1);
+
+		assert!(matches!(
+			submit(&pool, 1, 1_099),
+			Err(PoolError::TooLowPriority {
+				old: 1_000,
+				new: 1_099
+			})
+		));
+		submit(&pool, 1, 1_100).unwrap();
+	}
+
+	#[test]
+	fn transactions_with_other_tags_are_accepted() {
+		let pool = pool();
+		submit(&pool, 0, 1_000).unwrap();
+
+		submit(&pool, 1, 1).unwrap();
+		assert_eq!(pool.validated_pool().status().ready, 2);
+	}
+}
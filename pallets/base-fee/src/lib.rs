@@ -0,0 +1,125 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Minimum EVM gas price following block fullness.
+//!
+//! Like the base fee of EIP-1559, the gas price goes up when blocks are fuller than
+//! `TargetBlockFullness` and down when they are emptier, by at most `Elasticity` per block. It
+//! never goes below `MinGasPrice`. The pallet is the `FeeCalculator` of `pallet_evm`, so the
+//! price is what `eth_gasPrice` returns and the floor of the Ethereum transactions.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::pallet;
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[pallet]
+pub mod pallet {
+	use frame_support::{pallet_prelude::*, weights::DispatchClass};
+	use frame_system::pallet_prelude::*;
+	use sp_core::U256;
+	use sp_runtime::{PerThing, Perquintill};
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	/// Configuration trait of this pallet.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Gas price under which the base fee never goes.
+		type MinGasPrice: Get<U256>;
+		/// Share of the normal dispatch class weight at which the base fee stays the same.
+		type TargetBlockFullness: Get<Perquintill>;
+		/// Largest change of the base fee from one block to the next, reached by empty and full
+		/// blocks.
+		type Elasticity: Get<Perquintill>;
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: T::BlockNumber) -> Weight {
+			// Reads the block weight and the base fee, writes the base fee in `on_finalize`
+			T::DbWeight::get().reads_writes(2, 1)
+		}
+
+		fn on_finalize(_n: T::BlockNumber) {
+			let block_weights = T::BlockWeights::get();
+			let max_weight = block_weights
+				.get(DispatchClass::Normal)
+				.max_total
+				.unwrap_or(block_weights.max_block);
+			let used_weight = *frame_system::Pallet::<T>::block_weight().get(DispatchClass::Normal);
+			let fullness = Perquintill::from_rational_approximation(used_weight, max_weight);
+
+			BaseFeePerGas::<T>::put(Self::next_base_fee(BaseFeePerGas::<T>::get(), fullness));
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {}
+
+	impl<T: Config> Pallet<T> {
+		/// The base fee following a block with the given `fullness`.
+		pub fn next_base_fee(base_fee: U256, fullness: Perquintill) -> U256 {
+			let target = T::TargetBlockFullness::get();
+			let elasticity = T::Elasticity::get();
+
+			let next = if fullness > target {
+				// Proportion of the weight above the target that was used
+				let excess = Perquintill::from_rational_approximation(
+					(fullness - target).deconstruct(),
+					(Perquintill::one() - target).deconstruct(),
+				);
+				base_fee.saturating_add(Self::scale(base_fee, elasticity * excess))
+			} else {
+				// Proportion of the weight below the target that wasn't used
+				let shortfall = Perquintill::from_rational_approximation(
+					(target - fullness).deconstruct(),
+					target.deconstruct(),
+				);
+				base_fee.saturating_sub(Self::scale(base_fee, elasticity * shortfall))
+			};
+
+			next.max(T::MinGasPrice::get())
+		}
+
+		fn scale(value: U256, ratio: Perquintill) -> U256 {
+			value.saturating_mul(ratio.deconstruct().into()) / U256::from(Perquintill::ACCURACY)
+		}
+	}
+
+	impl<T: Config> pallet_evm::FeeCalculator for Pallet<T> {
+		fn min_gas_price() -> U256 {
+			BaseFeePerGas::<T>::get()
+		}
+	}
+
+	#[pallet::type_value]
+	pub fn DefaultBaseFeePerGas<T: Config>() -> U256 {
+		T::MinGasPrice::get()
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn base_fee_per_gas)]
+	/// Minimum gas price of the EVM transactions.
+	pub type BaseFeePerGas<T: Config> = StorageValue<_, U256, ValueQuery, DefaultBaseFeePerGas<T>>;
+}
@@ -0,0 +1,105 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A minimal runtime including the base-fee pallet
+use crate as pallet_base_fee;
+use frame_support::{construct_runtime, parameter_types};
+use frame_system::limits::BlockWeights;
+use sp_core::{H256, U256};
+use sp_io;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	Perquintill,
+};
+
+pub type AccountId = u64;
+pub type BlockNumber = u64;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		BaseFee: pallet_base_fee::{Pallet, Storage},
+	}
+);
+
+pub const MAX_BLOCK_WEIGHT: u64 = 1_000_000;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub TestBlockWeights: BlockWeights = BlockWeights::simple_max(MAX_BLOCK_WEIGHT);
+}
+impl frame_system::Config for Test {
+	type BaseCallFilter = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type BlockWeights = TestBlockWeights;
+	type BlockLength = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub MinGasPrice: U256 = 1_000_000_000.into();
+	pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
+	pub Elasticity: Perquintill = Perquintill::from_rational_approximation(1u64, 8u64);
+}
+impl pallet_base_fee::Config for Test {
+	type MinGasPrice = MinGasPrice;
+	type TargetBlockFullness = TargetBlockFullness;
+	type Elasticity = Elasticity;
+}
+
+pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::default()
+		.build_storage::<Test>()
+		.expect("Frame system builds valid default genesis config");
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Finalize a block whose normal dispatch class used `weight`.
+pub(crate) fn finalize_block_with_weight(weight: u64) {
+	System::set_block_consumed_resources(weight, 0);
+	<BaseFee as frame_support::traits::OnFinalize<BlockNumber>>::on_finalize(System::block_number());
+	System::set_block_number(System::block_number() + 1);
+}
@@ -0,0 +1,92 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Unit testing
+use crate::mock::{
+	finalize_block_with_weight, new_test_ext, BaseFee, MinGasPrice, MAX_BLOCK_WEIGHT,
+};
+use pallet_evm::FeeCalculator;
+use sp_core::U256;
+
+fn gwei(value: u64) -> U256 {
+	U256::from(value) * 1_000_000_000
+}
+
+#[test]
+fn base_fee_starts_at_min_gas_price() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(BaseFee::base_fee_per_gas(), MinGasPrice::get());
+		assert_eq!(BaseFee::min_gas_price(), MinGasPrice::get());
+	});
+}
+
+#[test]
+fn base_fee_is_stable_at_target_fullness() {
+	new_test_ext().execute_with(|| {
+		crate::BaseFeePerGas::<crate::mock::Test>::put(gwei(8));
+		finalize_block_with_weight(MAX_BLOCK_WEIGHT / 4);
+		assert_eq!(BaseFee::base_fee_per_gas(), gwei(8));
+	});
+}
+
+#[test]
+fn full_block_increases_base_fee_by_elasticity() {
+	new_test_ext().execute_with(|| {
+		crate::BaseFeePerGas::<crate::mock::Test>::put(gwei(8));
+		finalize_block_with_weight(MAX_BLOCK_WEIGHT);
+		assert_eq!(BaseFee::base_fee_per_gas(), gwei(9));
+		assert_eq!(BaseFee::min_gas_price(), gwei(9));
+	});
+}
+
+#[test]
+fn empty_block_decreases_base_fee_by_elasticity() {
+	new_test_ext().execute_with(|| {
+		crate::BaseFeePerGas::<crate::mock::Test>::put(gwei(8));
+		finalize_block_with_weight(0);
+		assert_eq!(BaseFee::base_fee_per_gas(), gwei(7));
+	});
+}
+
+#[test]
+fn base_fee_change_is_proportional_to_distance_from_target() {
+	new_test_ext().execute_with(|| {
+		crate::BaseFeePerGas::<crate::mock::Test>::put(gwei(16));
+		// Halfway between the target and a full block
+		finalize_block_with_weight(MAX_BLOCK_WEIGHT * 5 / 8);
+		assert_eq!(BaseFee::base_fee_per_gas(), gwei(17));
+	});
+}
+
+#[test]
+fn base_fee_never_goes_below_min_gas_price() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..10 {
+			finalize_block_with_weight(0);
+		}
+		assert_eq!(BaseFee::base_fee_per_gas(), MinGasPrice::get());
+	});
+}
+
+#[test]
+fn congestion_raises_base_fee_above_min_gas_price() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..10 {
+			finalize_block_with_weight(MAX_BLOCK_WEIGHT);
+		}
+		assert!(BaseFee::base_fee_per_gas() > gwei(3));
+	});
+}
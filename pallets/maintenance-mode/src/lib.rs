@@ -0,0 +1,246 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Governance controlled call filter of the runtime.
+//!
+//! The chain is either in normal operation or in maintenance mode, each mode having its own call
+//! filter. Governance can switch between the two modes, and can additionally pause all the calls
+//! of a pallet, whatever the mode. The pallet is meant to be the `BaseCallFilter` of the runtime,
+//! so that calls can be disabled and enabled again without a runtime upgrade.
+//!
+//! The calls of this pallet are never filtered, and the pallets making up `MaintenanceOrigin` can't
+//! be paused, so that governance can always leave the maintenance mode.
+//!
+//! Chains that upgrade to a runtime including this pallet start in maintenance mode, as the
+//! storage of its mode is only initialized at genesis otherwise.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::pallet;
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Code of the `InvalidTransaction::Custom` error of transactions whose call is filtered by the
+/// maintenance mode or by a pause.
+pub const CALL_PAUSED: u8 = 0xa0;
+
+#[pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use frame_support::traits::{
+		Contains, Filter, GetCallMetadata, GetPalletVersion, PalletInfoAccess,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_std::vec::Vec;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	/// Configuration trait of this pallet.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Overarching event type
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Filter of the calls allowed during normal operation.
+		type NormalCallFilter: Filter<<Self as frame_system::Config>::Call>;
+		/// Filter of the calls allowed in maintenance mode.
+		type MaintenanceCallFilter: Filter<<Self as frame_system::Config>::Call>;
+		/// Origin allowed to switch the mode and to pause pallets.
+		type MaintenanceOrigin: EnsureOrigin<Self::Origin>;
+		/// Names of the pallets that can't be paused, as the calls of `MaintenanceOrigin` are
+		/// dispatched through them.
+		type UnpausablePallets: Contains<Vec<u8>>;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The chain is already in maintenance mode.
+		AlreadyInMaintenanceMode,
+		/// The chain is not in maintenance mode.
+		NotInMaintenanceMode,
+		/// The pallet is already paused.
+		AlreadyPaused,
+		/// The pallet is not paused.
+		NotPaused,
+		/// The calls of this pallet can't be paused.
+		CannotPauseMaintenanceMode,
+		/// The calls of this pallet are needed by `MaintenanceOrigin` and can't be paused.
+		CannotPauseMaintenanceOrigin,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The chain entered maintenance mode.
+		EnteredMaintenanceMode,
+		/// The chain returned to normal operation.
+		NormalOperationResumed,
+		/// The calls of the pallet with this name have been paused.
+		PalletPaused(Vec<u8>),
+		/// The calls of the pallet with this name have been unpaused.
+		PalletUnpaused(Vec<u8>),
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Enter maintenance mode when the pallet is added to a live chain. Its storage version
+		/// is only missing until the end of the first upgrade including it, or on chains whose
+		/// genesis didn't include it.
+		fn on_runtime_upgrade() -> Weight {
+			if <Self as GetPalletVersion>::storage_version().is_some() {
+				return T::DbWeight::get().reads(1);
+			}
+
+			MaintenanceMode::<T>::put(true);
+
+			T::DbWeight::get().reads_writes(1, 1)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Enter maintenance mode. Only the calls allowed by `MaintenanceCallFilter` can be
+		/// dispatched until normal operation is resumed.
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn enter_maintenance_mode(origin: OriginFor<T>) -> DispatchResult {
+			T::MaintenanceOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				!MaintenanceMode::<T>::get(),
+				Error::<T>::AlreadyInMaintenanceMode
+			);
+
+			MaintenanceMode::<T>::put(true);
+
+			Self::deposit_event(Event::EnteredMaintenanceMode);
+
+			Ok(())
+		}
+
+		/// Leave maintenance mode. The calls allowed by `NormalCallFilter` can be dispatched
+		/// again.
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn resume_normal_operation(origin: OriginFor<T>) -> DispatchResult {
+			T::MaintenanceOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				MaintenanceMode::<T>::get(),
+				Error::<T>::NotInMaintenanceMode
+			);
+
+			MaintenanceMode::<T>::put(false);
+
+			Self::deposit_event(Event::NormalOperationResumed);
+
+			Ok(())
+		}
+
+		/// Pause all the calls of the pallet named `pallet_name` in `construct_runtime`.
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn pause_pallet(origin: OriginFor<T>, pallet_name: Vec<u8>) -> DispatchResult {
+			T::MaintenanceOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				pallet_name != <Self as PalletInfoAccess>::name().as_bytes(),
+				Error::<T>::CannotPauseMaintenanceMode
+			);
+			ensure!(
+				!T::UnpausablePallets::contains(&pallet_name),
+				Error::<T>::CannotPauseMaintenanceOrigin
+			);
+			ensure!(
+				!Self::is_pallet_paused(&pallet_name),
+				Error::<T>::AlreadyPaused
+			);
+
+			PausedPallets::<T>::insert(&pallet_name, ());
+
+			Self::deposit_event(Event::PalletPaused(pallet_name));
+
+			Ok(())
+		}
+
+		/// Unpause the calls of the pallet named `pallet_name`.
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn unpause_pallet(origin: OriginFor<T>, pallet_name: Vec<u8>) -> DispatchResult {
+			T::MaintenanceOrigin::ensure_origin(origin)?;
+
+			ensure!(Self::is_pallet_paused(&pallet_name), Error::<T>::NotPaused);
+
+			PausedPallets::<T>::remove(&pallet_name);
+
+			Self::deposit_event(Event::PalletUnpaused(pallet_name));
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Whether the calls of the pallet named `pallet_name` are paused.
+		pub fn is_pallet_paused(pallet_name: &[u8]) -> bool {
+			PausedPallets::<T>::contains_key(pallet_name)
+		}
+	}
+
+	impl<T: Config> Filter<<T as frame_system::Config>::Call> for Pallet<T>
+	where
+		<T as frame_system::Config>::Call: GetCallMetadata,
+	{
+		fn filter(call: &<T as frame_system::Config>::Call) -> bool {
+			let pallet_name = call.get_call_metadata().pallet_name;
+			if pallet_name == <Self as PalletInfoAccess>::name() {
+				return true;
+			}
+			if Self::is_pallet_paused(pallet_name.as_bytes()) {
+				return false;
+			}
+
+			if MaintenanceMode::<T>::get() {
+				T::MaintenanceCallFilter::filter(call)
+			} else {
+				T::NormalCallFilter::filter(call)
+			}
+		}
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn maintenance_mode)]
+	/// Whether the chain is in maintenance mode.
+	pub type MaintenanceMode<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	#[pallet::storage]
+	/// Names of the paused pallets.
+	pub type PausedPallets<T: Config> = StorageMap<_, Blake2_128Concat, Vec<u8>, (), OptionQuery>;
+
+	#[pallet::genesis_config]
+	#[derive(Default)]
+	pub struct GenesisConfig {
+		/// Whether the chain starts in maintenance mode.
+		pub start_in_maintenance_mode: bool,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			MaintenanceMode::<T>::put(self.start_in_maintenance_mode);
+		}
+	}
+}
@@ -0,0 +1,199 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Unit testing
+use crate::mock::{
+	last_event, new_test_ext, Call, Event as MetaEvent, MaintenanceMode, Origin, Test,
+};
+use crate::{Error, Event};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Filter, OnRuntimeUpgrade},
+};
+use sp_runtime::DispatchError;
+
+fn remark() -> Call {
+	Call::System(frame_system::Call::remark(vec![]))
+}
+
+#[test]
+fn can_start_in_maintenance_mode() {
+	new_test_ext(true).execute_with(|| {
+		assert!(MaintenanceMode::maintenance_mode());
+	});
+	new_test_ext(false).execute_with(|| {
+		assert!(!MaintenanceMode::maintenance_mode());
+	});
+}
+
+#[test]
+fn root_can_enter_and_leave_maintenance_mode() {
+	new_test_ext(false).execute_with(|| {
+		assert_ok!(MaintenanceMode::enter_maintenance_mode(Origin::root()));
+		assert!(MaintenanceMode::maintenance_mode());
+		assert_eq!(
+			last_event(),
+			MetaEvent::MaintenanceMode(Event::EnteredMaintenanceMode)
+		);
+
+		assert_ok!(MaintenanceMode::resume_normal_operation(Origin::root()));
+		assert!(!MaintenanceMode::maintenance_mode());
+		assert_eq!(
+			last_event(),
+			MetaEvent::MaintenanceMode(Event::NormalOperationResumed)
+		);
+	})
+}
+
+#[test]
+fn signed_origin_cannot_enter_maintenance_mode() {
+	new_test_ext(false).execute_with(|| {
+		assert_noop!(
+			MaintenanceMode::enter_maintenance_mode(Origin::signed(1)),
+			DispatchError::BadOrigin
+		);
+	})
+}
+
+#[test]
+fn cannot_enter_maintenance_mode_twice() {
+	new_test_ext(true).execute_with(|| {
+		assert_noop!(
+			MaintenanceMode::enter_maintenance_mode(Origin::root()),
+			Error::<Test>::AlreadyInMaintenanceMode
+		);
+	})
+}
+
+#[test]
+fn cannot_resume_normal_operation_twice() {
+	new_test_ext(false).execute_with(|| {
+		assert_noop!(
+			MaintenanceMode::resume_normal_operation(Origin::root()),
+			Error::<Test>::NotInMaintenanceMode
+		);
+	})
+}
+
+#[test]
+fn maintenance_mode_uses_maintenance_filter() {
+	new_test_ext(false).execute_with(|| {
+		assert!(MaintenanceMode::filter(&remark()));
+
+		assert_ok!(MaintenanceMode::enter_maintenance_mode(Origin::root()));
+		assert!(!MaintenanceMode::filter(&remark()));
+	})
+}
+
+#[test]
+fn maintenance_mode_calls_are_never_filtered() {
+	new_test_ext(true).execute_with(|| {
+		assert!(MaintenanceMode::filter(&Call::MaintenanceMode(
+			crate::Call::resume_normal_operation()
+		)));
+	})
+}
+
+#[test]
+fn paused_pallet_calls_are_filtered() {
+	new_test_ext(false).execute_with(|| {
+		assert_ok!(MaintenanceMode::pause_pallet(
+			Origin::root(),
+			b"System".to_vec()
+		));
+		assert!(MaintenanceMode::is_pallet_paused(b"System"));
+		assert_eq!(
+			last_event(),
+			MetaEvent::MaintenanceMode(Event::PalletPaused(b"System".to_vec()))
+		);
+		assert!(!MaintenanceMode::filter(&remark()));
+
+		assert_ok!(MaintenanceMode::unpause_pallet(
+			Origin::root(),
+			b"System".to_vec()
+		));
+		assert!(!MaintenanceMode::is_pallet_paused(b"System"));
+		assert_eq!(
+			last_event(),
+			MetaEvent::MaintenanceMode(Event::PalletUnpaused(b"System".to_vec()))
+		);
+		assert!(MaintenanceMode::filter(&remark()));
+	})
+}
+
+#[test]
+fn cannot_pause_pallet_twice() {
+	new_test_ext(false).execute_with(|| {
+		assert_ok!(MaintenanceMode::pause_pallet(
+			Origin::root(),
+			b"System".to_vec()
+		));
+		assert_noop!(
+			MaintenanceMode::pause_pallet(Origin::root(), b"System".to_vec()),
+			Error::<Test>::AlreadyPaused
+		);
+	})
+}
+
+#[test]
+fn cannot_unpause_running_pallet() {
+	new_test_ext(false).execute_with(|| {
+		assert_noop!(
+			MaintenanceMode::unpause_pallet(Origin::root(), b"System".to_vec()),
+			Error::<Test>::NotPaused
+		);
+	})
+}
+
+#[test]
+fn cannot_pause_maintenance_mode_pallet() {
+	new_test_ext(false).execute_with(|| {
+		assert_noop!(
+			MaintenanceMode::pause_pallet(Origin::root(), b"MaintenanceMode".to_vec()),
+			Error::<Test>::CannotPauseMaintenanceMode
+		);
+	})
+}
+
+#[test]
+fn cannot_pause_maintenance_origin_pallets() {
+	new_test_ext(false).execute_with(|| {
+		assert_noop!(
+			MaintenanceMode::pause_pallet(Origin::root(), b"Governance".to_vec()),
+			Error::<Test>::CannotPauseMaintenanceOrigin
+		);
+	})
+}
+
+#[test]
+fn enters_maintenance_mode_when_added_by_an_upgrade() {
+	new_test_ext(false).execute_with(|| {
+		// The storage version isn't set, as if the pallet wasn't in the genesis of the chain.
+		MaintenanceMode::on_runtime_upgrade();
+		assert!(MaintenanceMode::maintenance_mode());
+	})
+}
+
+#[test]
+fn keeps_the_mode_on_later_upgrades() {
+	new_test_ext(false).execute_with(|| {
+		MaintenanceMode::on_runtime_upgrade();
+		assert_ok!(MaintenanceMode::resume_normal_operation(Origin::root()));
+
+		MaintenanceMode::on_runtime_upgrade();
+		assert!(!MaintenanceMode::maintenance_mode());
+	})
+}
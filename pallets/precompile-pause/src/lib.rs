@@ -0,0 +1,110 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Keeps a list of paused precompile addresses.
+//!
+//! If a vulnerability is found in a precompile, governance can pause it here without waiting for
+//! a runtime upgrade. The runtime PrecompileSet checks this list before routing a call and fails
+//! calls to paused addresses. Reading the list is charged to every precompile call.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::pallet;
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_core::H160;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	/// Configuration trait of this pallet.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Overarching event type
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Origin allowed to pause and unpause precompiles.
+		type PauseOrigin: EnsureOrigin<Self::Origin>;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The precompile is already paused.
+		AlreadyPaused,
+		/// The precompile is not paused.
+		NotPaused,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The precompile at this address has been paused.
+		PrecompilePaused(H160),
+		/// The precompile at this address has been unpaused.
+		PrecompileUnpaused(H160),
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Pause the precompile at `address`. Calls to it fail until it is unpaused.
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn pause_precompile(origin: OriginFor<T>, address: H160) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+
+			ensure!(!Self::is_paused(&address), Error::<T>::AlreadyPaused);
+
+			PausedPrecompiles::<T>::insert(&address, ());
+
+			Self::deposit_event(Event::PrecompilePaused(address));
+
+			Ok(())
+		}
+
+		/// Unpause the precompile at `address`.
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn unpause_precompile(origin: OriginFor<T>, address: H160) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+
+			ensure!(Self::is_paused(&address), Error::<T>::NotPaused);
+
+			PausedPrecompiles::<T>::remove(&address);
+
+			Self::deposit_event(Event::PrecompileUnpaused(address));
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Whether the precompile at `address` is paused.
+		pub fn is_paused(address: &H160) -> bool {
+			PausedPrecompiles::<T>::contains_key(address)
+		}
+	}
+
+	#[pallet::storage]
+	/// Addresses of the paused precompiles.
+	pub type PausedPrecompiles<T: Config> = StorageMap<_, Twox64Concat, H160, (), OptionQuery>;
+}
@@ -0,0 +1,85 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Unit testing
+use crate::mock::{last_event, new_test_ext, Event as MetaEvent, Origin, PrecompilePause, Test};
+use crate::{Error, Event};
+use frame_support::{assert_noop, assert_ok};
+use sp_core::H160;
+use sp_runtime::DispatchError;
+
+fn dispatch_precompile() -> H160 {
+	H160::from_low_u64_be(1025)
+}
+
+#[test]
+fn root_can_pause_and_unpause_precompile() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PrecompilePause::pause_precompile(
+			Origin::root(),
+			dispatch_precompile()
+		));
+		assert!(PrecompilePause::is_paused(&dispatch_precompile()));
+		assert_eq!(
+			last_event(),
+			MetaEvent::PrecompilePause(Event::PrecompilePaused(dispatch_precompile()))
+		);
+
+		assert_ok!(PrecompilePause::unpause_precompile(
+			Origin::root(),
+			dispatch_precompile()
+		));
+		assert!(!PrecompilePause::is_paused(&dispatch_precompile()));
+		assert_eq!(
+			last_event(),
+			MetaEvent::PrecompilePause(Event::PrecompileUnpaused(dispatch_precompile()))
+		);
+	})
+}
+
+#[test]
+fn signed_origin_cannot_pause_precompile() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			PrecompilePause::pause_precompile(Origin::signed(1), dispatch_precompile()),
+			DispatchError::BadOrigin
+		);
+	})
+}
+
+#[test]
+fn cannot_pause_precompile_twice() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PrecompilePause::pause_precompile(
+			Origin::root(),
+			dispatch_precompile()
+		));
+		assert_noop!(
+			PrecompilePause::pause_precompile(Origin::root(), dispatch_precompile()),
+			Error::<Test>::AlreadyPaused
+		);
+	})
+}
+
+#[test]
+fn cannot_unpause_running_precompile() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			PrecompilePause::unpause_precompile(Origin::root(), dispatch_precompile()),
+			Error::<Test>::NotPaused
+		);
+	})
+}
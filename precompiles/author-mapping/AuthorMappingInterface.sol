@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.0;
+
+/// The interface through which solidity contracts will interact with Author Mapping
+/// We follow this same interface including four-byte function selectors, in the precompile that
+/// wraps the pallet
+///
+/// A call that fails in the pallet fails without revert data, so Solidity callers can't read the
+/// reason. The RPC reports the pallet error, e.g. `AuthorMapping::AlreadyAssociated`, in the error of `eth_call`
+/// and `eth_estimateGas`.
+interface AuthorMapping {
+    // First some simple accessors
+
+    /// Get the account currently associated with the given author id (NimbusId).
+    /// Returns the zero address if the author id is not associated.
+    function account_id_of(bytes32 nimbus_id) external view returns (address);
+
+    // Now the dispatchables
+
+    /// Register your author id (NimbusId) onchain so blocks you author are associated with
+    /// your account. A security deposit is reserved.
+    function add_association(bytes32 nimbus_id) external;
+
+    /// Change your author id.
+    function update_association(bytes32 old_nimbus_id, bytes32 new_nimbus_id)
+        external;
+
+    /// Clear your author id. The security deposit is returned.
+    function clear_association(bytes32 nimbus_id) external;
+}
+
+// These are the selectors generated by remix following this advice
+// https://ethereum.stackexchange.com/a/73405/9963
+// {
+// 	"bed68935": "account_id_of(bytes32)",
+// 	"aa5ac585": "add_association(bytes32)",
+// 	"7354c91d": "clear_association(bytes32)",
+// 	"d9cef879": "update_association(bytes32,bytes32)"
+// }
@@ -0,0 +1,239 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Precompile to call pallet-author-mapping runtime methods via the EVM
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Decode;
+use evm::{executor::PrecompileOutput, Context, ExitError, ExitSucceed};
+use frame_support::dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo};
+use frame_support::traits::Get;
+use pallet_evm::AddressMapping;
+use pallet_evm::GasWeightMapping;
+use pallet_evm::Precompile;
+use sp_core::H160;
+use sp_std::marker::PhantomData;
+
+/// A precompile to wrap the functionality from pallet_author_mapping.
+///
+/// It lets collators that manage their keys with Ethereum tooling register, rotate and clear the
+/// association between their author id (NimbusId) and their account.
+pub struct AuthorMappingWrapper<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> Precompile for AuthorMappingWrapper<Runtime>
+where
+	Runtime: pallet_author_mapping::Config + pallet_evm::Config,
+	Runtime::AccountId: Into<H160>,
+	Runtime::Call: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	<Runtime::Call as Dispatchable>::Origin: From<Option<Runtime::AccountId>>,
+	Runtime::Call: From<pallet_author_mapping::Call<Runtime>>,
+{
+	fn execute(
+		input: &[u8], //Reminder this is big-endian
+		target_gas: Option<u64>,
+		context: &Context,
+	) -> Result<PrecompileOutput, ExitError> {
+		log::trace!(target: "author-mapping-precompile", "In author mapping wrapper");
+
+		const SELECTOR_SIZE_BYTES: usize = 4;
+
+		if input.len() < SELECTOR_SIZE_BYTES {
+			return Err(ExitError::Other("input length less than 4 bytes".into()));
+		}
+
+		let input_args = &input[SELECTOR_SIZE_BYTES..];
+
+		// Parse the function selector
+		// These are the four-byte function selectors calculated from the
+		// AuthorMappingInterface.sol according to the solidity specification
+		// https://docs.soliditylang.org/en/v0.8.0/abi-spec.html#function-selector
+		let inner_call = match input[0..SELECTOR_SIZE_BYTES] {
+			// Check for accessor methods first. These return results immediately
+			[0xbe, 0xd6, 0x89, 0x35] => {
+				return Self::account_id_of(input_args);
+			}
+
+			// If not an accessor, check for dispatchables. These calls ready for dispatch below.
+			[0xaa, 0x5a, 0xc5, 0x85] => Self::add_association(input_args)?,
+			[0xd9, 0xce, 0xf8, 0x79] => Self::update_association(input_args)?,
+			[0x73, 0x54, 0xc9, 0x1d] => Self::clear_association(input_args)?,
+			_ => {
+				log::trace!(
+					target: "author-mapping-precompile",
+					"Failed to match function selector in author mapping precompile"
+				);
+				return Err(ExitError::Other(
+					"No author mapping wrapper method at given selector".into(),
+				));
+			}
+		};
+
+		let outer_call: Runtime::Call = inner_call.into();
+		let info = outer_call.get_dispatch_info();
+
+		// Make sure enough gas
+		if let Some(gas_limit) = target_gas {
+			let required_gas = Runtime::GasWeightMapping::weight_to_gas(info.weight);
+			if required_gas > gas_limit {
+				return Err(ExitError::OutOfGas);
+			}
+		}
+		log::trace!(target: "author-mapping-precompile", "Made it past gas check");
+
+		// Dispatch that call
+		let origin = Runtime::AddressMapping::into_account_id(context.caller);
+
+		log::trace!(
+			target: "author-mapping-precompile",
+			"Gonna call with origin {:?}",
+			origin
+		);
+
+		match outer_call.dispatch(Some(origin).into()) {
+			Ok(post_info) => {
+				let gas_used = Runtime::GasWeightMapping::weight_to_gas(
+					post_info.actual_weight.unwrap_or(info.weight),
+				);
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Stopped,
+					cost: gas_used,
+					output: Default::default(),
+					logs: Default::default(),
+				})
+			}
+			Err(e) => {
+				log::trace!(
+					target: "author-mapping-precompile",
+					"Author mapping call via evm failed {:?}",
+					e
+				);
+				Err(precompile_utils::dispatch_error::<
+					Runtime,
+					pallet_author_mapping::Pallet<Runtime>,
+				>("Author mapping call via EVM failed", e.error))
+			}
+		}
+	}
+}
+
+impl<Runtime> AuthorMappingWrapper<Runtime>
+where
+	Runtime: pallet_author_mapping::Config + pallet_evm::Config,
+	Runtime::AccountId: Into<H160>,
+	Runtime::Call: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	<Runtime::Call as Dispatchable>::Origin: From<Option<Runtime::AccountId>>,
+	Runtime::Call: From<pallet_author_mapping::Call<Runtime>>,
+{
+	// The accessors are first. They directly return their result.
+
+	fn account_id_of(input: &[u8]) -> Result<PrecompileOutput, ExitError> {
+		let author_id = parse_author_id::<Runtime>(input)?;
+
+		log::trace!(
+			target: "author-mapping-precompile",
+			"Looking up the account of author id {:?}",
+			author_id
+		);
+
+		// fetch data from pallet. Unknown author ids resolve to the zero address.
+		let account: H160 = pallet_author_mapping::Pallet::<Runtime>::account_id_of(&author_id)
+			.map(Into::into)
+			.unwrap_or_default();
+
+		log::trace!(target: "author-mapping-precompile", "Result from pallet is {:?}", account);
+
+		let mut output = [0u8; 32];
+		output[12..32].copy_from_slice(account.as_bytes());
+
+		let gas_consumed = <Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
+			<Runtime as frame_system::Config>::DbWeight::get().read,
+		);
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: gas_consumed,
+			output: output.to_vec(),
+			logs: Default::default(),
+		})
+	}
+
+	// The dispatchable wrappers are next. They return a substrate inner Call ready for dispatch.
+
+	fn add_association(input: &[u8]) -> Result<pallet_author_mapping::Call<Runtime>, ExitError> {
+		let author_id = parse_author_id::<Runtime>(input)?;
+
+		log::trace!(target: "author-mapping-precompile", "Associating author id {:?}", author_id);
+
+		Ok(pallet_author_mapping::Call::<Runtime>::add_association(
+			author_id,
+		))
+	}
+
+	fn update_association(input: &[u8]) -> Result<pallet_author_mapping::Call<Runtime>, ExitError> {
+		if input.len() != 64 {
+			return Err(ExitError::Other(
+				"Incorrect input length for update_association".into(),
+			));
+		}
+		let old_author_id = parse_author_id::<Runtime>(&input[..32])?;
+		let new_author_id = parse_author_id::<Runtime>(&input[32..])?;
+
+		log::trace!(
+			target: "author-mapping-precompile",
+			"Updating author id {:?} to {:?}",
+			old_author_id,
+			new_author_id
+		);
+
+		Ok(pallet_author_mapping::Call::<Runtime>::update_association(
+			old_author_id,
+			new_author_id,
+		))
+	}
+
+	fn clear_association(input: &[u8]) -> Result<pallet_author_mapping::Call<Runtime>, ExitError> {
+		let author_id = parse_author_id::<Runtime>(input)?;
+
+		log::trace!(target: "author-mapping-precompile", "Clearing author id {:?}", author_id);
+
+		Ok(pallet_author_mapping::Call::<Runtime>::clear_association(
+			author_id,
+		))
+	}
+}
+
+/// Parses an author id from a 256 bit (32 byte) buffer. NimbusIds are sr25519 public keys, whose
+/// SCALE encoding is the 32 raw bytes of the key.
+fn parse_author_id<Runtime: pallet_author_mapping::Config>(
+	input: &[u8],
+) -> Result<Runtime::AuthorId, ExitError> {
+	const AUTHOR_ID_SIZE_BYTES: usize = 32;
+
+	if input.len() != AUTHOR_ID_SIZE_BYTES {
+		log::trace!(target: "author-mapping-precompile",
+			"Unable to parse author id. Got {} bytes, expected {}",
+			input.len(),
+			AUTHOR_ID_SIZE_BYTES,
+		);
+		return Err(ExitError::Other(
+			"Incorrect input length for author id parsing".into(),
+		));
+	}
+
+	Runtime::AuthorId::decode(&mut &input[..])
+		.map_err(|_| ExitError::Other("Unable to decode author id".into()))
+}
@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.0;
+
+/// The interface through which solidity contracts will interact with the native token as an
+/// ERC20 (https://eips.ethereum.org/EIPS/eip-20)
+/// We follow this same interface including four-byte function selectors, in the precompile that
+/// wraps the pallet
+///
+/// A call that fails in the pallet fails without revert data, so Solidity callers can't read the
+/// reason. The RPC reports the pallet error, e.g. `Balances::InsufficientBalance`, in the error of `eth_call`
+/// and `eth_estimateGas`.
+interface IERC20 {
+    /// Returns the name of the token.
+    function name() external view returns (string memory);
+
+    /// Returns the symbol of the token.
+    function symbol() external view returns (string memory);
+
+    /// Returns the decimals places of the token.
+    function decimals() external view returns (uint8);
+
+    /// Total number of tokens in existence
+    function totalSupply() external view returns (uint256);
+
+    /// Gets the balance of the specified address.
+    function balanceOf(address who) external view returns (uint256);
+
+    /// Function to check the amount of tokens that an owner allowed to a spender.
+    function allowance(address owner, address spender)
+        external
+        view
+        returns (uint256);
+
+    /// Transfer token for a specified address
+    function transfer(address to, uint256 value) external returns (bool);
+
+    /// Approve the passed address to spend the specified amount of tokens on behalf
+    /// of msg.sender.
+    function approve(address spender, uint256 value) external returns (bool);
+
+    /// Transfer tokens from one address to another, using the allowance given to msg.sender
+    function transferFrom(
+        address from,
+        address to,
+        uint256 value
+    ) external returns (bool);
+
+    /// Event emited when a transfer has been performed.
+    event Transfer(address indexed from, address indexed to, uint256 value);
+
+    /// Event emited when an approval has been registered.
+    event Approval(
+        address indexed owner,
+        address indexed spender,
+        uint256 value
+    );
+}
+
+// These are the selectors generated by remix following this advice
+// https://ethereum.stackexchange.com/a/73405/9963
+// {
+// 	"dd62ed3e": "allowance(address,address)",
+// 	"095ea7b3": "approve(address,uint256)",
+// 	"70a08231": "balanceOf(address)",
+// 	"313ce567": "decimals()",
+// 	"06fdde03": "name()",
+// 	"95d89b41": "symbol()",
+// 	"18160ddd": "totalSupply()",
+// 	"a9059cbb": "transfer(address,uint256)",
+// 	"23b872dd": "transferFrom(address,address,uint256)"
+// }
+// Log topics
+// {
+// 	"ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef": "Transfer(address,address,uint256)",
+// 	"8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925": "Approval(address,address,uint256)"
+// }
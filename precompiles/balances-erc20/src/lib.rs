@@ -0,0 +1,451 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Precompile to expose the native currency (pallet-balances) as an ERC20 token via the EVM
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use evm::{backend::Log, executor::PrecompileOutput, Context, ExitError, ExitSucceed};
+use frame_support::dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo};
+use frame_support::storage::types::{StorageDoubleMap, ValueQuery};
+use frame_support::traits::{Get, StorageInstance};
+use frame_support::Blake2_128Concat;
+use pallet_evm::AddressMapping;
+use pallet_evm::GasWeightMapping;
+use pallet_evm::Precompile;
+use precompile_utils::{
+	bool_to_solidity_bytes, check_input_length, parse_account, parse_amount,
+	string_to_solidity_bytes, u256_to_solidity_bytes,
+};
+use sp_core::{H160, H256, U256};
+use sp_runtime::traits::StaticLookup;
+use sp_std::convert::TryFrom;
+use sp_std::fmt::Debug;
+use sp_std::marker::PhantomData;
+use sp_std::vec::Vec;
+
+type BalanceOf<Runtime> = <Runtime as pallet_balances::Config>::Balance;
+
+/// Solidity selector of the Transfer log, which is the Keccak of the Log signature.
+/// keccak256("Transfer(address,address,uint256)")
+pub const SELECTOR_LOG_TRANSFER: [u8; 32] = [
+	0xdd, 0xf2, 0x52, 0xad, 0x1b, 0xe2, 0xc8, 0x9b, 0x69, 0xc2, 0xb0, 0x68, 0xfc, 0x37, 0x8d, 0xaa,
+	0x95, 0x2b, 0xa7, 0xf1, 0x63, 0xc4, 0xa1, 0x16, 0x28, 0xf5, 0x5a, 0x4d, 0xf5, 0x23, 0xb3, 0xef,
+];
+
+/// Solidity selector of the Approval log, which is the Keccak of the Log signature.
+/// keccak256("Approval(address,address,uint256)")
+pub const SELECTOR_LOG_APPROVAL: [u8; 32] = [
+	0x8c, 0x5b, 0xe1, 0xe5, 0xeb, 0xec, 0x7d, 0x5b, 0xd1, 0x4f, 0x71, 0x42, 0x7d, 0x1e, 0x84, 0xf3,
+	0xdd, 0x03, 0x14, 0xc0, 0xf7, 0xb2, 0x29, 0x1e, 0x5b, 0x20, 0x0a, 0xc8, 0xc7, 0xc3, 0xb9, 0x25,
+];
+
+/// Prefix of the storage holding the ERC20 allowances. The precompile is not a pallet, so it
+/// provides its own (unique) pallet prefix.
+pub struct ApprovesPrefix;
+
+impl StorageInstance for ApprovesPrefix {
+	const STORAGE_PREFIX: &'static str = "Approves";
+
+	fn pallet_prefix() -> &'static str {
+		"Erc20Instance"
+	}
+}
+
+/// Storage type used to store ERC20 allowances: (owner, spender) => amount.
+pub type ApprovesStorage<Runtime> = StorageDoubleMap<
+	ApprovesPrefix,
+	Blake2_128Concat,
+	<Runtime as frame_system::Config>::AccountId,
+	Blake2_128Concat,
+	<Runtime as frame_system::Config>::AccountId,
+	BalanceOf<Runtime>,
+	ValueQuery,
+>;
+
+/// Metadata of the ERC20 token, provided by each runtime for its native currency.
+pub trait Erc20Metadata {
+	/// Returns the name of the token.
+	fn name() -> &'static str;
+
+	/// Returns the symbol of the token.
+	fn symbol() -> &'static str;
+
+	/// Returns the decimals places of the token.
+	fn decimals() -> u8;
+}
+
+/// A precompile to expose the native currency as an ERC20 token.
+///
+/// Balances, total supply and transfers are read from and dispatched to pallet_balances, while
+/// allowances are stored on chain by the precompile itself. `Transfer` and `Approval` logs are
+/// emitted so that the token behaves like any other ERC20 for Ethereum tooling.
+pub struct BalancesErc20Wrapper<Runtime, Metadata>(PhantomData<(Runtime, Metadata)>);
+
+impl<Runtime, Metadata> Precompile for BalancesErc20Wrapper<Runtime, Metadata>
+where
+	Runtime: pallet_balances::Config + pallet_evm::Config,
+	Runtime::AccountId: From<H160>,
+	BalanceOf<Runtime>: TryFrom<U256> + Into<U256> + Debug,
+	Runtime::Call: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	<Runtime::Call as Dispatchable>::Origin: From<Option<Runtime::AccountId>>,
+	Runtime::Call: From<pallet_balances::Call<Runtime>>,
+	Metadata: Erc20Metadata,
+{
+	fn execute(
+		input: &[u8], //Reminder this is big-endian
+		target_gas: Option<u64>,
+		context: &Context,
+	) -> Result<PrecompileOutput, ExitError> {
+		log::trace!(target: "erc20-precompile", "In balances erc20 wrapper");
+
+		const SELECTOR_SIZE_BYTES: usize = 4;
+
+		if input.len() < SELECTOR_SIZE_BYTES {
+			return Err(ExitError::Other("input length less than 4 bytes".into()));
+		}
+
+		let input_args = &input[SELECTOR_SIZE_BYTES..];
+
+		// Parse the function selector
+		// These are the four-byte function selectors calculated from the ERC20.sol
+		// according to the solidity specification
+		// https://docs.soliditylang.org/en/v0.8.0/abi-spec.html#function-selector
+		match input[0..SELECTOR_SIZE_BYTES] {
+			// Accessors
+			[0x18, 0x16, 0x0d, 0xdd] => Self::total_supply(input_args),
+			[0x70, 0xa0, 0x82, 0x31] => Self::balance_of(input_args),
+			[0xdd, 0x62, 0xed, 0x3e] => Self::allowance(input_args),
+			[0x06, 0xfd, 0xde, 0x03] => Self::name(input_args),
+			[0x95, 0xd8, 0x9b, 0x41] => Self::symbol(input_args),
+			[0x31, 0x3c, 0xe5, 0x67] => Self::decimals(input_args),
+
+			// Mutators
+			[0x09, 0x5e, 0xa7, 0xb3] => Self::approve(input_args, context),
+			[0xa9, 0x05, 0x9c, 0xbb] => Self::transfer(input_args, target_gas, context),
+			[0x23, 0xb8, 0x72, 0xdd] => Self::transfer_from(input_args, target_gas, context),
+			_ => {
+				log::trace!(
+					target: "erc20-precompile",
+					"Failed to match function selector in balances erc20 precompile"
+				);
+				Err(ExitError::Other(
+					"No erc20 wrapper method at given selector".into(),
+				))
+			}
+		}
+	}
+}
+
+impl<Runtime, Metadata> BalancesErc20Wrapper<Runtime, Metadata>
+where
+	Runtime: pallet_balances::Config + pallet_evm::Config,
+	Runtime::AccountId: From<H160>,
+	BalanceOf<Runtime>: TryFrom<U256> + Into<U256> + Debug,
+	Runtime::Call: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	<Runtime::Call as Dispatchable>::Origin: From<Option<Runtime::AccountId>>,
+	Runtime::Call: From<pallet_balances::Call<Runtime>>,
+	Metadata: Erc20Metadata,
+{
+	// The accessors are first. They directly return their result.
+
+	fn total_supply(input: &[u8]) -> Result<PrecompileOutput, ExitError> {
+		check_input_length(input, 0)?;
+
+		// fetch data from pallet
+		let total_supply: U256 = pallet_balances::Pallet::<Runtime>::total_issuance().into();
+
+		log::trace!(target: "erc20-precompile", "Total supply is {:?}", total_supply);
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: Self::read_cost(1),
+			output: u256_to_solidity_bytes(total_supply),
+			logs: Default::default(),
+		})
+	}
+
+	fn balance_of(input: &[u8]) -> Result<PrecompileOutput, ExitError> {
+		check_input_length(input, 32)?;
+		let owner = parse_account(input)?;
+
+		// fetch data from pallet
+		let amount: U256 =
+			pallet_balances::Pallet::<Runtime>::free_balance(&Runtime::AccountId::from(owner))
+				.into();
+
+		log::trace!(target: "erc20-precompile", "Balance of {:?} is {:?}", owner, amount);
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: Self::read_cost(1),
+			output: u256_to_solidity_bytes(amount),
+			logs: Default::default(),
+		})
+	}
+
+	fn allowance(input: &[u8]) -> Result<PrecompileOutput, ExitError> {
+		check_input_length(input, 64)?;
+		let owner = parse_account(&input[..32])?;
+		let spender = parse_account(&input[32..])?;
+
+		let amount: U256 = ApprovesStorage::<Runtime>::get(
+			Runtime::AccountId::from(owner),
+			Runtime::AccountId::from(spender),
+		)
+		.into();
+
+		log::trace!(
+			target: "erc20-precompile",
+			"Allowance of {:?} for {:?} is {:?}",
+			spender,
+			owner,
+			amount
+		);
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: Self::read_cost(1),
+			output: u256_to_solidity_bytes(amount),
+			logs: Default::default(),
+		})
+	}
+
+	fn name(input: &[u8]) -> Result<PrecompileOutput, ExitError> {
+		check_input_length(input, 0)?;
+
+		let output = string_to_solidity_bytes(Metadata::name());
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: output_cost(&output),
+			output,
+			logs: Default::default(),
+		})
+	}
+
+	fn symbol(input: &[u8]) -> Result<PrecompileOutput, ExitError> {
+		check_input_length(input, 0)?;
+
+		let output = string_to_solidity_bytes(Metadata::symbol());
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: output_cost(&output),
+			output,
+			logs: Default::default(),
+		})
+	}
+
+	fn decimals(input: &[u8]) -> Result<PrecompileOutput, ExitError> {
+		check_input_length(input, 0)?;
+
+		let output = u256_to_solidity_bytes(Metadata::decimals().into());
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: output_cost(&output),
+			output,
+			logs: Default::default(),
+		})
+	}
+
+	// The mutators are next. They modify the state and emit the corresponding logs.
+
+	fn approve(input: &[u8], context: &Context) -> Result<PrecompileOutput, ExitError> {
+		check_input_length(input, 64)?;
+		let spender = parse_account(&input[..32])?;
+		let amount = parse_amount::<BalanceOf<Runtime>>(&input[32..])?;
+
+		log::trace!(
+			target: "erc20-precompile",
+			"{:?} approves {:?} to spend {:?}",
+			context.caller,
+			spender,
+			amount
+		);
+
+		let owner = Runtime::AddressMapping::into_account_id(context.caller);
+		ApprovesStorage::<Runtime>::insert(owner, Runtime::AccountId::from(spender), amount);
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: Self::write_cost(1),
+			output: bool_to_solidity_bytes(true),
+			logs: sp_std::vec![log3(
+				context.address,
+				SELECTOR_LOG_APPROVAL,
+				context.caller,
+				spender,
+				u256_to_solidity_bytes(amount.into()),
+			)],
+		})
+	}
+
+	fn transfer(
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+	) -> Result<PrecompileOutput, ExitError> {
+		check_input_length(input, 64)?;
+		let to = parse_account(&input[..32])?;
+		let amount = parse_amount::<BalanceOf<Runtime>>(&input[32..])?;
+
+		log::trace!(
+			target: "erc20-precompile",
+			"{:?} transfers {:?} to {:?}",
+			context.caller,
+			amount,
+			to
+		);
+
+		let origin = Runtime::AddressMapping::into_account_id(context.caller);
+		let gas_used = Self::dispatch_transfer(origin, to.into(), amount, target_gas)?;
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: gas_used,
+			output: bool_to_solidity_bytes(true),
+			logs: sp_std::vec![log3(
+				context.address,
+				SELECTOR_LOG_TRANSFER,
+				context.caller,
+				to,
+				u256_to_solidity_bytes(amount.into()),
+			)],
+		})
+	}
+
+	fn transfer_from(
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+	) -> Result<PrecompileOutput, ExitError> {
+		check_input_length(input, 96)?;
+		let from = parse_account(&input[..32])?;
+		let to = parse_account(&input[32..64])?;
+		let amount = parse_amount::<BalanceOf<Runtime>>(&input[64..])?;
+
+		log::trace!(
+			target: "erc20-precompile",
+			"{:?} transfers {:?} from {:?} to {:?}",
+			context.caller,
+			amount,
+			from,
+			to
+		);
+
+		let owner: Runtime::AccountId = from.into();
+		let spender = Runtime::AddressMapping::into_account_id(context.caller);
+
+		// Check the allowance before doing the transfer. It is only updated once the transfer
+		// succeeded, as the state modified by a failing precompile is not reverted.
+		let allowance = ApprovesStorage::<Runtime>::get(&owner, &spender);
+		if allowance < amount {
+			return Err(ExitError::Other("Trying to spend more than allowed".into()));
+		}
+
+		let gas_used = Self::dispatch_transfer(owner.clone(), to.into(), amount, target_gas)?;
+
+		ApprovesStorage::<Runtime>::insert(owner, spender, allowance - amount);
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: gas_used
+				.saturating_add(Self::read_cost(1))
+				.saturating_add(Self::write_cost(1)),
+			output: bool_to_solidity_bytes(true),
+			logs: sp_std::vec![log3(
+				context.address,
+				SELECTOR_LOG_TRANSFER,
+				from,
+				to,
+				u256_to_solidity_bytes(amount.into()),
+			)],
+		})
+	}
+
+	/// Dispatches a pallet_balances transfer on behalf of `origin` and returns the gas used.
+	fn dispatch_transfer(
+		origin: Runtime::AccountId,
+		to: Runtime::AccountId,
+		amount: BalanceOf<Runtime>,
+		target_gas: Option<u64>,
+	) -> Result<u64, ExitError> {
+		let outer_call: Runtime::Call = pallet_balances::Call::<Runtime>::transfer(
+			<Runtime as frame_system::Config>::Lookup::unlookup(to),
+			amount,
+		)
+		.into();
+		let info = outer_call.get_dispatch_info();
+
+		// Make sure enough gas
+		if let Some(gas_limit) = target_gas {
+			let required_gas = Runtime::GasWeightMapping::weight_to_gas(info.weight);
+			if required_gas > gas_limit {
+				return Err(ExitError::OutOfGas);
+			}
+		}
+
+		match outer_call.dispatch(Some(origin).into()) {
+			Ok(post_info) => Ok(Runtime::GasWeightMapping::weight_to_gas(
+				post_info.actual_weight.unwrap_or(info.weight),
+			)),
+			Err(e) => {
+				log::trace!(
+					target: "erc20-precompile",
+					"Balances transfer via evm failed {:?}",
+					e
+				);
+				Err(precompile_utils::dispatch_error::<
+					Runtime,
+					pallet_balances::Pallet<Runtime>,
+				>("Balances transfer via EVM failed", e.error))
+			}
+		}
+	}
+
+	fn read_cost(reads: u64) -> u64 {
+		<Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
+			<Runtime as frame_system::Config>::DbWeight::get().reads(reads),
+		)
+	}
+
+	fn write_cost(writes: u64) -> u64 {
+		<Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
+			<Runtime as frame_system::Config>::DbWeight::get().writes(writes),
+		)
+	}
+}
+
+/// Gas charged by the metadata getters. They return constants without reading the storage, so
+/// like the Identity precompile they are only charged for their output: 15 gas plus 3 gas per
+/// 32-byte word.
+fn output_cost(output: &[u8]) -> u64 {
+	const BASE_COST: u64 = 15;
+	const WORD_COST: u64 = 3;
+
+	BASE_COST.saturating_add(WORD_COST.saturating_mul((output.len() as u64 + 31) / 32))
+}
+
+/// Builds a log with 3 topics: the event selector followed by 2 addresses.
+fn log3(address: H160, selector: [u8; 32], topic1: H160, topic2: H160, data: Vec<u8>) -> Log {
+	Log {
+		address,
+		topics: sp_std::vec![H256(selector), topic1.into(), topic2.into()],
+		data,
+	}
+}
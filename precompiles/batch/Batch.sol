@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.0;
+
+/// The interface through which EOAs can bundle several calls in a single transaction. We follow
+/// this same interface including four-byte function selectors, in the precompile. Contracts can't
+/// call it, as each subcall gets a call stack of its own.
+///
+/// Every subcall `i` is made with the caller of the batch as `msg.sender`, to `to[i]`, sending
+/// `value[i]` (taken from the caller balance) with `callData[i]` as input and `gasLimit[i]` as gas
+/// limit. `value`, `callData` and `gasLimit` can be shorter than `to`, in which case the missing
+/// entries default to 0, empty call data, and all the remaining gas respectively. A gas limit of 0
+/// also forwards all the remaining gas.
+///
+/// Each subcall is charged like a call transaction, including the base call cost, and the
+/// precompile can't be reentered from a subcall.
+interface Batch {
+    /// Emitted after the subcall at `index` succeeded, after the logs of the subcall itself
+    event SubcallSucceeded(uint256 index);
+
+    /// Emitted after the subcall at `index` failed
+    event SubcallFailed(uint256 index);
+
+    /// Make all the subcalls, whether they succeed or not
+    function batchSome(
+        address[] memory to,
+        uint256[] memory value,
+        bytes[] memory callData,
+        uint64[] memory gasLimit
+    ) external;
+
+    /// Make the subcalls until one of them fails. The subcalls made before the failure are kept.
+    function batchSomeUntilFailure(
+        address[] memory to,
+        uint256[] memory value,
+        bytes[] memory callData,
+        uint64[] memory gasLimit
+    ) external;
+
+    /// Make all the subcalls atomically: if one of them fails, the whole batch reverts
+    function batchAll(
+        address[] memory to,
+        uint256[] memory value,
+        bytes[] memory callData,
+        uint64[] memory gasLimit
+    ) external;
+}
+
+// These are the selectors generated by remix following this advice
+// https://ethereum.stackexchange.com/a/73405/9963
+// {
+// 	"79df4b9c": "batchSome(address[],uint256[],bytes[],uint64[])",
+// 	"cf0491c7": "batchSomeUntilFailure(address[],uint256[],bytes[],uint64[])",
+// 	"96e292b8": "batchAll(address[],uint256[],bytes[],uint64[])"
+// }
+
+// These are the event topics (keccak256 of the event signatures) emitted by the precompile
+// {
+// 	"bf855484633929c3d6688eb3caf8eff910fb4bef030a8d7dbc9390d26759714d": "SubcallSucceeded(uint256)",
+// 	"dbc5d06f4f877f959b1ff12d2161cdd693fa8e442ee53f1790b2804b24881f05": "SubcallFailed(uint256)"
+// }
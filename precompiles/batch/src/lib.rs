@@ -0,0 +1,390 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Precompile to make several EVM calls in a single transaction
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use evm::{backend::Log, executor::PrecompileOutput, Context, ExitError, ExitReason, ExitSucceed};
+use frame_support::{
+	storage::{with_transaction, TransactionOutcome},
+	traits::Get,
+};
+use pallet_evm::{AddressMapping, GasWeightMapping, Precompile, Runner};
+use precompile_utils::{parse_account, parse_uint256};
+use sp_core::{H160, H256, U256};
+use sp_std::marker::PhantomData;
+use sp_std::vec::Vec;
+
+/// Signature of the Solidity event logged after each successful subcall.
+pub const EVENT_SUBCALL_SUCCEEDED: &[u8] = b"SubcallSucceeded(uint256)";
+/// Signature of the Solidity event logged after each failed subcall.
+pub const EVENT_SUBCALL_FAILED: &[u8] = b"SubcallFailed(uint256)";
+
+// Set while a batch is executed. Each subcall is executed by a new EVM executor whose call depth
+// starts at 0, so the batch precompile can't be reentered from a subcall, otherwise the call
+// stack limit could be bypassed. Being only called by externally owned accounts, it is itself
+// at the start of the call stack, so the subcalls can't go deeper than a transaction.
+environmental::environmental!(executing_batch: ());
+
+/// How the batch behaves when a subcall fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Mode {
+	/// Make all the subcalls, whether they succeed or not.
+	BatchSome,
+	/// Make the subcalls until one of them fails.
+	BatchSomeUntilFailure,
+	/// Make all the subcalls, and revert them all if one of them fails.
+	BatchAll,
+}
+
+/// A call to make on behalf of the caller of the batch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Subcall {
+	to: H160,
+	value: U256,
+	call_data: Vec<u8>,
+	/// 0 forwards all the remaining gas.
+	gas_limit: u64,
+}
+
+/// A precompile to bundle several EVM calls in a single transaction.
+///
+/// The subcalls are made with the caller of the precompile as source, and are each charged to it
+/// like a call transaction, without any gas fee on top of the one of the batch transaction. The
+/// precompile can only be called by externally owned accounts.
+pub struct BatchPrecompile<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> Precompile for BatchPrecompile<Runtime>
+where
+	Runtime: pallet_evm::Config,
+{
+	fn execute(
+		input: &[u8], //Reminder this is big-endian
+		target_gas: Option<u64>,
+		context: &Context,
+	) -> Result<PrecompileOutput, ExitError> {
+		log::trace!(target: "batch-precompile", "In batch precompile");
+
+		const SELECTOR_SIZE_BYTES: usize = 4;
+
+		if input.len() < SELECTOR_SIZE_BYTES {
+			return Err(ExitError::Other("input length less than 4 bytes".into()));
+		}
+
+		// Parse the function selector
+		// These are the four-byte function selectors calculated from the Batch.sol
+		// according to the solidity specification
+		// https://docs.soliditylang.org/en/v0.8.0/abi-spec.html#function-selector
+		let mode = match input[0..SELECTOR_SIZE_BYTES] {
+			[0x79, 0xdf, 0x4b, 0x9c] => Mode::BatchSome,
+			[0xcf, 0x04, 0x91, 0xc7] => Mode::BatchSomeUntilFailure,
+			[0x96, 0xe2, 0x92, 0xb8] => Mode::BatchAll,
+			_ => {
+				log::trace!(
+					target: "batch-precompile",
+					"Failed to match function selector in batch precompile"
+				);
+				return Err(ExitError::Other(
+					"No batch precompile method at given selector".into(),
+				));
+			}
+		};
+
+		// The values of the subcalls are taken from the balance of the caller, the precompile
+		// itself has no use for the value it receives.
+		if !context.apparent_value.is_zero() {
+			return Err(ExitError::Other(
+				"Batch precompile does not accept value".into(),
+			));
+		}
+
+		let subcalls = parse_subcalls(&input[SELECTOR_SIZE_BYTES..])?;
+
+		log::trace!(
+			target: "batch-precompile",
+			"{:?} with {} subcalls",
+			mode,
+			subcalls.len()
+		);
+
+		if executing_batch::with(|_| ()).is_some() {
+			return Err(ExitError::Other(
+				"Batch precompile cannot be reentered".into(),
+			));
+		}
+
+		// A contract could call the precompile from deep in the call stack, and each subcall
+		// would then get a full call stack of its own.
+		if pallet_evm::AccountCodes::<Runtime>::contains_key(context.caller) {
+			return Err(ExitError::Other(
+				"Batch precompile can only be called by externally owned accounts".into(),
+			));
+		}
+		let caller_check_cost = Runtime::GasWeightMapping::weight_to_gas(
+			<Runtime as frame_system::Config>::DbWeight::get().read,
+		);
+
+		// Each subcall is run by its own executor, which writes its changes to the storage when
+		// it exits. They are made in a storage transaction so that a failing batch reverts them.
+		let mut output = executing_batch::using(&mut (), || {
+			with_transaction(|| {
+				let result = Self::batch(mode, subcalls, target_gas, context);
+				if result.is_ok() {
+					TransactionOutcome::Commit(result)
+				} else {
+					TransactionOutcome::Rollback(result)
+				}
+			})
+		})?;
+		output.cost = output.cost.saturating_add(caller_check_cost);
+
+		Ok(output)
+	}
+}
+
+impl<Runtime> BatchPrecompile<Runtime>
+where
+	Runtime: pallet_evm::Config,
+{
+	fn batch(
+		mode: Mode,
+		subcalls: Vec<Subcall>,
+		target_gas: Option<u64>,
+		context: &Context,
+	) -> Result<PrecompileOutput, ExitError> {
+		let gas_limit = target_gas.unwrap_or_else(|| Runtime::BlockGasLimit::get().low_u64());
+		let caller_account = Runtime::AddressMapping::into_account_id(context.caller);
+
+		let mut used_gas = 0u64;
+		let mut logs = Vec::new();
+
+		for (index, subcall) in subcalls.into_iter().enumerate() {
+			let remaining_gas = gas_limit.saturating_sub(used_gas);
+			let subcall_gas_limit = match subcall.gas_limit {
+				0 => remaining_gas,
+				limit if limit <= remaining_gas => limit,
+				_ => return Err(ExitError::OutOfGas),
+			};
+
+			// The runner increments the nonce of the source as for a transaction, which a subcall
+			// is not. The gas price is left empty so that no fee is withdrawn from the caller on
+			// top of the one of the batch transaction.
+			let nonce = frame_system::Pallet::<Runtime>::account_nonce(&caller_account);
+			let result = Runtime::Runner::call(
+				context.caller,
+				subcall.to,
+				subcall.call_data,
+				subcall.value,
+				subcall_gas_limit,
+				None,
+				None,
+				Runtime::config(),
+			);
+			frame_system::Account::<Runtime>::mutate(&caller_account, |account| {
+				account.nonce = nonce
+			});
+
+			let succeeded = match result {
+				Ok(info) => {
+					used_gas = used_gas.saturating_add(info.used_gas.low_u64());
+					log::trace!(
+						target: "batch-precompile",
+						"Subcall {} exited with {:?}",
+						index,
+						info.exit_reason
+					);
+
+					// A subcall that didn't succeed has no logs, they were reverted
+					logs.extend(info.logs);
+					matches!(info.exit_reason, ExitReason::Succeed(_))
+				}
+				// The runner rejects the subcall before executing it, e.g. when the caller can't
+				// afford its value
+				Err(_) => {
+					log::trace!(target: "batch-precompile", "Subcall {} was rejected", index);
+					false
+				}
+			};
+
+			if succeeded {
+				logs.push(log1(context.address, EVENT_SUBCALL_SUCCEEDED, index));
+				continue;
+			}
+
+			match mode {
+				Mode::BatchSome => {
+					logs.push(log1(context.address, EVENT_SUBCALL_FAILED, index));
+				}
+				Mode::BatchSomeUntilFailure => {
+					logs.push(log1(context.address, EVENT_SUBCALL_FAILED, index));
+					break;
+				}
+				// Failing rolls back the changes of all the subcalls made so far
+				Mode::BatchAll => {
+					return Err(ExitError::Other("A subcall of batchAll failed".into()));
+				}
+			}
+		}
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Stopped,
+			cost: used_gas,
+			output: Default::default(),
+			logs,
+		})
+	}
+}
+
+/// Parses the subcalls from the four arrays (address[] to, uint256[] value, bytes[] callData,
+/// uint64[] gasLimit). The last three arrays can be shorter than the first one, in which case the
+/// missing entries take their default value.
+fn parse_subcalls(input: &[u8]) -> Result<Vec<Subcall>, ExitError> {
+	let (to_position, to_length) = read_array(input, 0, 0)?;
+	let (value_position, value_length) = read_array(input, 32, 0)?;
+	let (call_data_position, call_data_length) = read_array(input, 64, 0)?;
+	let (gas_limit_position, gas_limit_length) = read_array(input, 96, 0)?;
+
+	if value_length > to_length || call_data_length > to_length || gas_limit_length > to_length {
+		return Err(ExitError::Other(
+			"Value, call data and gas limit arrays can't be longer than the address array".into(),
+		));
+	}
+
+	let mut subcalls = Vec::with_capacity(to_length);
+	for index in 0..to_length {
+		let to = read_address(input, element_position(to_position, index)?)?;
+
+		let value = if index < value_length {
+			read_word(input, element_position(value_position, index)?)?
+		} else {
+			U256::zero()
+		};
+
+		// The elements of a bytes[] are the offsets of each bytes, from the start of the elements
+		let call_data = if index < call_data_length {
+			let (bytes_position, bytes_length) = read_array(
+				input,
+				element_position(call_data_position, index)?,
+				call_data_position,
+			)?;
+			read_bytes(input, bytes_position, bytes_length)?
+		} else {
+			Vec::new()
+		};
+
+		let gas_limit = if index < gas_limit_length {
+			read_u64(input, element_position(gas_limit_position, index)?)?
+		} else {
+			0
+		};
+
+		subcalls.push(Subcall {
+			to,
+			value,
+			call_data,
+			gas_limit,
+		});
+	}
+
+	Ok(subcalls)
+}
+
+/// Returns the 256 bit (32 byte) word at `position`.
+fn word_at(input: &[u8], position: usize) -> Result<&[u8], ExitError> {
+	position
+		.checked_add(32)
+		.and_then(|end| input.get(position..end))
+		.ok_or_else(|| ExitError::Other("Incorrect input length for batch parsing".into()))
+}
+
+/// Reads the 256 bit (32 byte) word at `position`.
+fn read_word(input: &[u8], position: usize) -> Result<U256, ExitError> {
+	parse_uint256(word_at(input, position)?)
+}
+
+/// Reads an offset or a length, which can't exceed the size of the input.
+fn read_usize(input: &[u8], position: usize) -> Result<usize, ExitError> {
+	let value = read_word(input, position)?;
+
+	if value > U256::from(input.len()) {
+		return Err(ExitError::Other(
+			"Offset or length out of bounds in batch parsing".into(),
+		));
+	}
+
+	Ok(value.low_u64() as usize)
+}
+
+/// Reads the position of the first element and the length of the dynamic array whose offset,
+/// counted from `base`, is stored at `head_position`.
+fn read_array(
+	input: &[u8],
+	head_position: usize,
+	base: usize,
+) -> Result<(usize, usize), ExitError> {
+	let position = base
+		.checked_add(read_usize(input, head_position)?)
+		.ok_or_else(|| ExitError::Other("Offset out of bounds in batch parsing".into()))?;
+	let length = read_usize(input, position)?;
+
+	Ok((position + 32, length))
+}
+
+/// Computes the position of the element at `index` of an array of 32 bytes elements.
+fn element_position(first_position: usize, index: usize) -> Result<usize, ExitError> {
+	index
+		.checked_mul(32)
+		.and_then(|offset| offset.checked_add(first_position))
+		.ok_or_else(|| ExitError::Other("Offset out of bounds in batch parsing".into()))
+}
+
+/// Reads an H160 address from the 256 bit word at `position`. Only the last 20 bytes are used.
+fn read_address(input: &[u8], position: usize) -> Result<H160, ExitError> {
+	parse_account(word_at(input, position)?)
+}
+
+/// Reads a uint64 from the 256 bit word at `position`.
+fn read_u64(input: &[u8], position: usize) -> Result<u64, ExitError> {
+	let value = read_word(input, position)?;
+
+	if value > U256::from(u64::MAX) {
+		return Err(ExitError::Other("Gas limit is too large for u64".into()));
+	}
+
+	Ok(value.low_u64())
+}
+
+/// Reads `length` bytes starting at `position`.
+fn read_bytes(input: &[u8], position: usize, length: usize) -> Result<Vec<u8>, ExitError> {
+	position
+		.checked_add(length)
+		.and_then(|end| input.get(position..end))
+		.map(|bytes| bytes.to_vec())
+		.ok_or_else(|| ExitError::Other("Incorrect input length for batch parsing".into()))
+}
+
+/// Builds a log with a single topic, the hashed event signature, and the subcall index as data.
+fn log1(address: H160, signature: &[u8], index: usize) -> Log {
+	let mut data = [0u8; 32];
+	U256::from(index).to_big_endian(&mut data);
+
+	Log {
+		address,
+		topics: sp_std::vec![H256::from(sp_io::hashing::keccak_256(signature))],
+		data: data.to_vec(),
+	}
+}
@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.0;
+
+/// The interface through which solidity contracts will interact with Crowdloan Rewards
+/// We follow this same interface including four-byte function selectors, in the precompile that
+/// wraps the pallet
+///
+/// A call that fails in the pallet fails without revert data, so Solidity callers can't read the
+/// reason. The RPC reports the pallet error, e.g. `CrowdloanRewards::NoAssociatedClaim`, in the error of `eth_call`
+/// and `eth_estimateGas`.
+interface CrowdloanRewards {
+    // First some simple accessors
+
+    /// Check whether the specified address has an associated reward
+    function is_contributor(address contributor) external view returns (bool);
+
+    /// Get the total and already claimed rewards of the specified address
+    function reward_info(address contributor)
+        external
+        view
+        returns (uint256 total_reward, uint256 claimed_reward);
+
+    // Now the dispatchables
+
+    /// Claim the vested amount of rewards for the caller
+    function claim() external;
+
+    /// Move the caller's associated reward to another address
+    function update_reward_address(address new_address) external;
+}
+
+// These are the selectors generated by remix following this advice
+// https://ethereum.stackexchange.com/a/73405/9963
+// {
+// 	"4e71d92d": "claim()",
+// 	"53440c90": "is_contributor(address)",
+// 	"76f70249": "reward_info(address)",
+// 	"aaac61d6": "update_reward_address(address)"
+// }
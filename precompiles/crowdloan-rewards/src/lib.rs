@@ -0,0 +1,249 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Precompile to call pallet-crowdloan-rewards runtime methods via the EVM
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use evm::{executor::PrecompileOutput, Context, ExitError, ExitSucceed};
+use frame_support::dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo};
+use frame_support::traits::{Currency, Get};
+use pallet_evm::AddressMapping;
+use pallet_evm::GasWeightMapping;
+use pallet_evm::Precompile;
+use precompile_utils::{bool_to_solidity_bytes, parse_account};
+use sp_core::{H160, U256};
+use sp_std::fmt::Debug;
+use sp_std::marker::PhantomData;
+
+type BalanceOf<Runtime> =
+	<<Runtime as pallet_crowdloan_rewards::Config>::RewardCurrency as Currency<
+		<Runtime as frame_system::Config>::AccountId,
+	>>::Balance;
+
+/// A precompile to wrap the functionality from pallet_crowdloan_rewards.
+///
+/// It allows contributors whose reward is associated with an Ethereum address to check and claim
+/// their rewards using only Ethereum tooling.
+pub struct CrowdloanRewardsWrapper<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> Precompile for CrowdloanRewardsWrapper<Runtime>
+where
+	Runtime: pallet_crowdloan_rewards::Config + pallet_evm::Config,
+	Runtime::AccountId: From<H160>,
+	BalanceOf<Runtime>: Into<U256> + Debug,
+	Runtime::Call: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	<Runtime::Call as Dispatchable>::Origin: From<Option<Runtime::AccountId>>,
+	Runtime::Call: From<pallet_crowdloan_rewards::Call<Runtime>>,
+{
+	fn execute(
+		input: &[u8], //Reminder this is big-endian
+		target_gas: Option<u64>,
+		context: &Context,
+	) -> Result<PrecompileOutput, ExitError> {
+		log::trace!(target: "crowdloan-rewards-precompile", "In crowdloan rewards wrapper");
+
+		const SELECTOR_SIZE_BYTES: usize = 4;
+
+		if input.len() < SELECTOR_SIZE_BYTES {
+			return Err(ExitError::Other("input length less than 4 bytes".into()));
+		}
+
+		let input_args = &input[SELECTOR_SIZE_BYTES..];
+
+		// Parse the function selector
+		// These are the four-byte function selectors calculated from the CrowdloanInterface.sol
+		// according to the solidity specification
+		// https://docs.soliditylang.org/en/v0.8.0/abi-spec.html#function-selector
+		let inner_call = match input[0..SELECTOR_SIZE_BYTES] {
+			// Check for accessor methods first. These return results immediately
+			[0x53, 0x44, 0x0c, 0x90] => {
+				return Self::is_contributor(input_args);
+			}
+			[0x76, 0xf7, 0x02, 0x49] => {
+				return Self::reward_info(input_args);
+			}
+
+			// If not an accessor, check for dispatchables. These calls ready for dispatch below.
+			[0x4e, 0x71, 0xd9, 0x2d] => Self::claim(input_args)?,
+			[0xaa, 0xac, 0x61, 0xd6] => Self::update_reward_address(input_args)?,
+			_ => {
+				log::trace!(
+					target: "crowdloan-rewards-precompile",
+					"Failed to match function selector in crowdloan rewards precompile"
+				);
+				return Err(ExitError::Other(
+					"No crowdloan rewards wrapper method at given selector".into(),
+				));
+			}
+		};
+
+		let outer_call: Runtime::Call = inner_call.into();
+		let info = outer_call.get_dispatch_info();
+
+		// Make sure enough gas
+		if let Some(gas_limit) = target_gas {
+			let required_gas = Runtime::GasWeightMapping::weight_to_gas(info.weight);
+			if required_gas > gas_limit {
+				return Err(ExitError::OutOfGas);
+			}
+		}
+		log::trace!(target: "crowdloan-rewards-precompile", "Made it past gas check");
+
+		// Dispatch that call
+		let origin = Runtime::AddressMapping::into_account_id(context.caller);
+
+		log::trace!(
+			target: "crowdloan-rewards-precompile",
+			"Gonna call with origin {:?}",
+			origin
+		);
+
+		match outer_call.dispatch(Some(origin).into()) {
+			Ok(post_info) => {
+				let gas_used = Runtime::GasWeightMapping::weight_to_gas(
+					post_info.actual_weight.unwrap_or(info.weight),
+				);
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Stopped,
+					cost: gas_used,
+					output: Default::default(),
+					logs: Default::default(),
+				})
+			}
+			Err(e) => {
+				log::trace!(
+					target: "crowdloan-rewards-precompile",
+					"Crowdloan rewards call via evm failed {:?}",
+					e
+				);
+				Err(precompile_utils::dispatch_error::<
+					Runtime,
+					pallet_crowdloan_rewards::Pallet<Runtime>,
+				>("Crowdloan rewards call via EVM failed", e.error))
+			}
+		}
+	}
+}
+
+impl<Runtime> CrowdloanRewardsWrapper<Runtime>
+where
+	Runtime: pallet_crowdloan_rewards::Config + pallet_evm::Config,
+	Runtime::AccountId: From<H160>,
+	BalanceOf<Runtime>: Into<U256> + Debug,
+	Runtime::Call: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	<Runtime::Call as Dispatchable>::Origin: From<Option<Runtime::AccountId>>,
+	Runtime::Call: From<pallet_crowdloan_rewards::Call<Runtime>>,
+{
+	// The accessors are first. They directly return their result.
+
+	fn is_contributor(input: &[u8]) -> Result<PrecompileOutput, ExitError> {
+		let contributor = parse_account(input)?;
+
+		log::trace!(
+			target: "crowdloan-rewards-precompile",
+			"Checking whether {:?} is a contributor",
+			contributor
+		);
+
+		// fetch data from pallet
+		let is_contributor =
+			pallet_crowdloan_rewards::Pallet::<Runtime>::accounts_payable(&contributor.into())
+				.is_some();
+
+		log::trace!(
+			target: "crowdloan-rewards-precompile",
+			"Result from pallet is {:?}",
+			is_contributor
+		);
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: Self::read_cost(),
+			output: bool_to_solidity_bytes(is_contributor),
+			logs: Default::default(),
+		})
+	}
+
+	fn reward_info(input: &[u8]) -> Result<PrecompileOutput, ExitError> {
+		let contributor = parse_account(input)?;
+
+		log::trace!(
+			target: "crowdloan-rewards-precompile",
+			"Checking reward info for {:?}",
+			contributor
+		);
+
+		// fetch data from pallet
+		let (total, claimed): (U256, U256) =
+			match pallet_crowdloan_rewards::Pallet::<Runtime>::accounts_payable(&contributor.into())
+			{
+				Some(reward_info) => (
+					reward_info.total_reward.into(),
+					reward_info.claimed_reward.into(),
+				),
+				None => (U256::zero(), U256::zero()),
+			};
+
+		log::trace!(
+			target: "crowdloan-rewards-precompile",
+			"Result from pallet is {:?} {:?}",
+			total,
+			claimed
+		);
+
+		let mut output = [0u8; 64];
+		total.to_big_endian(&mut output[..32]);
+		claimed.to_big_endian(&mut output[32..]);
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: Self::read_cost(),
+			output: output.to_vec(),
+			logs: Default::default(),
+		})
+	}
+
+	// The dispatchable wrappers are next. They return a substrate inner Call ready for dispatch.
+
+	fn claim(input: &[u8]) -> Result<pallet_crowdloan_rewards::Call<Runtime>, ExitError> {
+		if !input.is_empty() {
+			return Err(ExitError::Other("Incorrect input length for claim".into()));
+		}
+
+		Ok(pallet_crowdloan_rewards::Call::<Runtime>::claim())
+	}
+
+	fn update_reward_address(
+		input: &[u8],
+	) -> Result<pallet_crowdloan_rewards::Call<Runtime>, ExitError> {
+		let new_address = parse_account(input)?;
+
+		log::trace!(
+			target: "crowdloan-rewards-precompile",
+			"New reward address is {:?}",
+			new_address
+		);
+
+		Ok(pallet_crowdloan_rewards::Call::<Runtime>::update_reward_address(new_address.into()))
+	}
+
+	fn read_cost() -> u64 {
+		<Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
+			<Runtime as frame_system::Config>::DbWeight::get().read,
+		)
+	}
+}
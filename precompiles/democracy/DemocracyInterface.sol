@@ -0,0 +1,95 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.0;
+
+/// The interface through which solidity contracts will interact with pallet-democracy
+/// We follow this same interface including four-byte function selectors, in the precompile that
+/// wraps the pallet
+///
+/// A call that fails in the pallet fails without revert data, so Solidity callers can't read the
+/// reason. The RPC reports the pallet error, e.g. `Democracy::ValueLow`, in the error of `eth_call`
+/// and `eth_estimateGas`.
+interface Democracy {
+    // First some simple accessors
+
+    /// Get the total number of public proposals past or present
+    function public_prop_count() external view returns (uint256);
+
+    /// Get details about the specified proposal
+    /// Returns the amount of tokens deposited by the proposer (and seconders)
+    function deposit_of(uint256 prop_index) external view returns (uint256);
+
+    /// Get the index of the lowest unbaked referendum
+    function lowest_unbaked() external view returns (uint256);
+
+    /// Get the details about an ongoing referendum.
+    /// The threshold is encoded as 0 = SuperMajorityApprove, 1 = SuperMajorityAgainst and
+    /// 2 = SimpleMajority.
+    function ongoing_referendum_info(uint256 ref_index)
+        external
+        view
+        returns (
+            uint256 end,
+            bytes32 proposal_hash,
+            uint256 threshold,
+            uint256 delay,
+            uint256 ayes,
+            uint256 nays,
+            uint256 turnout
+        );
+
+    // Now the dispatchables
+
+    /// Make a new proposal
+    function propose(bytes32 proposal_hash, uint256 value) external;
+
+    /// Signal agreement with a proposal
+    /// The seconds upper bound is the number of existing seconds, used as a weight hint
+    function second(uint256 prop_index, uint256 seconds_upper_bound) external;
+
+    /// Vote in a referendum.
+    /// The conviction must be an integer in the range 0-6
+    function standard_vote(
+        uint256 ref_index,
+        bool aye,
+        uint256 vote_amount,
+        uint256 conviction
+    ) external;
+
+    /// Remove a vote for a referendum
+    function remove_vote(uint256 ref_index) external;
+
+    /// Delegate voting power to another account.
+    /// The conviction must be an integer in the range 0-6
+    function delegate(
+        address representative,
+        uint256 conviction,
+        uint256 amount
+    ) external;
+
+    /// Undelegate voting power
+    function un_delegate() external;
+
+    /// Unlock tokens that have an expired lock.
+    function unlock(address target) external;
+
+    /// Register the preimage for an upcoming proposal. This doesn't require the proposal to be
+    /// in the dispatch queue but does require a deposit, returned once enacted.
+    function note_preimage(bytes memory encoded_proposal) external;
+}
+
+// These are the selectors generated by remix following this advice
+// https://ethereum.stackexchange.com/a/73405/9963
+// {
+// 	"0185921e": "delegate(address,uint256,uint256)",
+// 	"a30305e9": "deposit_of(uint256)",
+// 	"0388f282": "lowest_unbaked()",
+// 	"200881f5": "note_preimage(bytes)",
+// 	"8b93d11a": "ongoing_referendum_info(uint256)",
+// 	"7824e7d1": "propose(bytes32,uint256)",
+// 	"56fdf547": "public_prop_count()",
+// 	"2042f50b": "remove_vote(uint256)",
+// 	"c7a76601": "second(uint256,uint256)",
+// 	"3f3c21cc": "standard_vote(uint256,bool,uint256,uint256)",
+// 	"cb37b8ea": "un_delegate()",
+// 	"2f6c493c": "unlock(address)"
+// }
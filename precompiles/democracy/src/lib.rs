@@ -0,0 +1,417 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Precompile to interact with pallet democracy through the EVM
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use evm::{executor::PrecompileOutput, Context, ExitError, ExitSucceed};
+use frame_support::dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo};
+use frame_support::traits::{Currency, Get};
+use pallet_democracy::{AccountVote, Conviction, ReferendumInfo, Vote, VoteThreshold};
+use pallet_evm::AddressMapping;
+use pallet_evm::GasWeightMapping;
+use pallet_evm::Precompile;
+use precompile_utils::{
+	check_input_length, parse_account, parse_amount, parse_bool, parse_bytes, parse_u32,
+	parse_uint256, u256_to_solidity_bytes,
+};
+use sp_core::{H160, H256, U256};
+use sp_std::convert::TryFrom;
+use sp_std::fmt::Debug;
+use sp_std::marker::PhantomData;
+use sp_std::vec::Vec;
+
+type BalanceOf<Runtime> = <<Runtime as pallet_democracy::Config>::Currency as Currency<
+	<Runtime as frame_system::Config>::AccountId,
+>>::Balance;
+
+/// A precompile to wrap the functionality from pallet democracy.
+///
+/// Grants evm-based DAOs the right to vote making them first-class citizens.
+/// Calls are dispatched with the caller's address as origin.
+pub struct DemocracyWrapper<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> Precompile for DemocracyWrapper<Runtime>
+where
+	Runtime: pallet_democracy::Config + pallet_evm::Config,
+	Runtime::AccountId: From<H160>,
+	BalanceOf<Runtime>: TryFrom<U256> + Into<U256> + Debug,
+	Runtime::Hash: From<H256> + Into<H256>,
+	Runtime::BlockNumber: Into<U256>,
+	Runtime::Call: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	<Runtime::Call as Dispatchable>::Origin: From<Option<Runtime::AccountId>>,
+	Runtime::Call: From<pallet_democracy::Call<Runtime>>,
+{
+	fn execute(
+		input: &[u8], //Reminder this is big-endian
+		target_gas: Option<u64>,
+		context: &Context,
+	) -> Result<PrecompileOutput, ExitError> {
+		log::trace!(target: "democracy-precompile", "In democracy wrapper");
+
+		const SELECTOR_SIZE_BYTES: usize = 4;
+
+		if input.len() < SELECTOR_SIZE_BYTES {
+			return Err(ExitError::Other("input length less than 4 bytes".into()));
+		}
+
+		let input_args = &input[SELECTOR_SIZE_BYTES..];
+
+		// Parse the function selector
+		// These are the four-byte function selectors calculated from the DemocracyInterface.sol
+		// according to the solidity specification
+		// https://docs.soliditylang.org/en/v0.8.0/abi-spec.html#function-selector
+		let inner_call = match input[0..SELECTOR_SIZE_BYTES] {
+			// Check for accessor methods first. These return results immediately
+			[0x56, 0xfd, 0xf5, 0x47] => {
+				return Self::public_prop_count(input_args);
+			}
+			[0xa3, 0x03, 0x05, 0xe9] => {
+				return Self::deposit_of(input_args);
+			}
+			[0x03, 0x88, 0xf2, 0x82] => {
+				return Self::lowest_unbaked(input_args);
+			}
+			[0x8b, 0x93, 0xd1, 0x1a] => {
+				return Self::ongoing_referendum_info(input_args);
+			}
+
+			// If not an accessor, check for dispatchables. These calls ready for dispatch below.
+			[0x78, 0x24, 0xe7, 0xd1] => Self::propose(input_args)?,
+			[0xc7, 0xa7, 0x66, 0x01] => Self::second(input_args)?,
+			[0x3f, 0x3c, 0x21, 0xcc] => Self::standard_vote(input_args)?,
+			[0x20, 0x42, 0xf5, 0x0b] => Self::remove_vote(input_args)?,
+			[0x01, 0x85, 0x92, 0x1e] => Self::delegate(input_args)?,
+			[0xcb, 0x37, 0xb8, 0xea] => Self::un_delegate(input_args)?,
+			[0x2f, 0x6c, 0x49, 0x3c] => Self::unlock(input_args)?,
+			[0x20, 0x08, 0x81, 0xf5] => Self::note_preimage(input_args)?,
+			_ => {
+				log::trace!(
+					target: "democracy-precompile",
+					"Failed to match function selector in democracy precompile"
+				);
+				return Err(ExitError::Other(
+					"No democracy wrapper method at given selector".into(),
+				));
+			}
+		};
+
+		let outer_call: Runtime::Call = inner_call.into();
+		let info = outer_call.get_dispatch_info();
+
+		// Make sure enough gas
+		if let Some(gas_limit) = target_gas {
+			let required_gas = Runtime::GasWeightMapping::weight_to_gas(info.weight);
+			if required_gas > gas_limit {
+				return Err(ExitError::OutOfGas);
+			}
+		}
+		log::trace!(target: "democracy-precompile", "Made it past gas check");
+
+		// Dispatch that call
+		let origin = Runtime::AddressMapping::into_account_id(context.caller);
+
+		log::trace!(target: "democracy-precompile", "Gonna call with origin {:?}", origin);
+
+		match outer_call.dispatch(Some(origin).into()) {
+			Ok(post_info) => {
+				let gas_used = Runtime::GasWeightMapping::weight_to_gas(
+					post_info.actual_weight.unwrap_or(info.weight),
+				);
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Stopped,
+					cost: gas_used,
+					output: Default::default(),
+					logs: Default::default(),
+				})
+			}
+			Err(e) => {
+				log::trace!(
+					target: "democracy-precompile",
+					"Democracy call via evm failed {:?}",
+					e
+				);
+				Err(precompile_utils::dispatch_error::<
+					Runtime,
+					pallet_democracy::Pallet<Runtime>,
+				>("Democracy call via EVM failed", e.error))
+			}
+		}
+	}
+}
+
+impl<Runtime> DemocracyWrapper<Runtime>
+where
+	Runtime: pallet_democracy::Config + pallet_evm::Config,
+	Runtime::AccountId: From<H160>,
+	BalanceOf<Runtime>: TryFrom<U256> + Into<U256> + Debug,
+	Runtime::Hash: From<H256> + Into<H256>,
+	Runtime::BlockNumber: Into<U256>,
+	Runtime::Call: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	<Runtime::Call as Dispatchable>::Origin: From<Option<Runtime::AccountId>>,
+	Runtime::Call: From<pallet_democracy::Call<Runtime>>,
+{
+	// The accessors are first. They directly return their result.
+
+	fn public_prop_count(input: &[u8]) -> Result<PrecompileOutput, ExitError> {
+		check_input_length(input, 0)?;
+
+		// fetch data from pallet
+		let prop_count = pallet_democracy::Pallet::<Runtime>::public_prop_count();
+
+		log::trace!(target: "democracy-precompile", "Prop count from pallet is {:?}", prop_count);
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: Self::read_cost(),
+			output: u256_to_solidity_bytes(prop_count.into()),
+			logs: Default::default(),
+		})
+	}
+
+	fn deposit_of(input: &[u8]) -> Result<PrecompileOutput, ExitError> {
+		let prop_index = parse_u32(input)?;
+
+		log::trace!(
+			target: "democracy-precompile",
+			"Checking the deposit of proposal {:?}",
+			prop_index
+		);
+
+		// fetch data from pallet
+		let (_seconds, deposit) = pallet_democracy::Pallet::<Runtime>::deposit_of(prop_index)
+			.ok_or_else(|| ExitError::Other("No such proposal in pallet democracy".into()))?;
+
+		log::trace!(target: "democracy-precompile", "Deposit from pallet is {:?}", deposit);
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: Self::read_cost(),
+			output: u256_to_solidity_bytes(deposit.into()),
+			logs: Default::default(),
+		})
+	}
+
+	fn lowest_unbaked(input: &[u8]) -> Result<PrecompileOutput, ExitError> {
+		check_input_length(input, 0)?;
+
+		// fetch data from pallet
+		let lowest_unbaked = pallet_democracy::Pallet::<Runtime>::lowest_unbaked();
+
+		log::trace!(
+			target: "democracy-precompile",
+			"Lowest unbaked referendum from pallet is {:?}",
+			lowest_unbaked
+		);
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: Self::read_cost(),
+			output: u256_to_solidity_bytes(lowest_unbaked.into()),
+			logs: Default::default(),
+		})
+	}
+
+	/// Returns the status of an ongoing referendum as the tuple
+	/// (end, proposalHash, threshold, delay, ayes, nays, turnout).
+	/// The threshold is encoded as 0 = SuperMajorityApprove, 1 = SuperMajorityAgainst and
+	/// 2 = SimpleMajority.
+	fn ongoing_referendum_info(input: &[u8]) -> Result<PrecompileOutput, ExitError> {
+		let ref_index = parse_u32(input)?;
+
+		log::trace!(
+			target: "democracy-precompile",
+			"Checking the status of referendum {:?}",
+			ref_index
+		);
+
+		// fetch data from pallet
+		let status = match pallet_democracy::Pallet::<Runtime>::referendum_info(ref_index) {
+			Some(ReferendumInfo::Ongoing(status)) => status,
+			Some(ReferendumInfo::Finished { .. }) => {
+				return Err(ExitError::Other("Referendum is finished".into()))
+			}
+			None => return Err(ExitError::Other("Unknown referendum".into())),
+		};
+
+		let threshold: u8 = match status.threshold {
+			VoteThreshold::SuperMajorityApprove => 0,
+			VoteThreshold::SuperMajorityAgainst => 1,
+			VoteThreshold::SimpleMajority => 2,
+		};
+
+		let proposal_hash: H256 = status.proposal_hash.into();
+
+		let mut output = Vec::with_capacity(7 * 32);
+		output.extend_from_slice(&u256_to_solidity_bytes(status.end.into()));
+		output.extend_from_slice(proposal_hash.as_bytes());
+		output.extend_from_slice(&u256_to_solidity_bytes(threshold.into()));
+		output.extend_from_slice(&u256_to_solidity_bytes(status.delay.into()));
+		output.extend_from_slice(&u256_to_solidity_bytes(status.tally.ayes.into()));
+		output.extend_from_slice(&u256_to_solidity_bytes(status.tally.nays.into()));
+		output.extend_from_slice(&u256_to_solidity_bytes(status.tally.turnout.into()));
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: Self::read_cost(),
+			output,
+			logs: Default::default(),
+		})
+	}
+
+	// The dispatchable wrappers are next. They return a substrate inner Call ready for dispatch.
+
+	fn propose(input: &[u8]) -> Result<pallet_democracy::Call<Runtime>, ExitError> {
+		check_input_length(input, 64)?;
+		let proposal_hash = H256::from_slice(&input[..32]);
+		let amount = parse_amount::<BalanceOf<Runtime>>(&input[32..])?;
+
+		log::trace!(
+			target: "democracy-precompile",
+			"Proposing with hash {:?}, and amount {:?}",
+			proposal_hash,
+			amount
+		);
+
+		Ok(pallet_democracy::Call::<Runtime>::propose(
+			proposal_hash.into(),
+			amount,
+		))
+	}
+
+	fn second(input: &[u8]) -> Result<pallet_democracy::Call<Runtime>, ExitError> {
+		check_input_length(input, 64)?;
+		let proposal_index = parse_u32(&input[..32])?;
+		let seconds_upper_bound = parse_u32(&input[32..])?;
+
+		log::trace!(
+			target: "democracy-precompile",
+			"Seconding proposal {:?}, with bound {:?}",
+			proposal_index,
+			seconds_upper_bound
+		);
+
+		Ok(pallet_democracy::Call::<Runtime>::second(
+			proposal_index,
+			seconds_upper_bound,
+		))
+	}
+
+	fn standard_vote(input: &[u8]) -> Result<pallet_democracy::Call<Runtime>, ExitError> {
+		check_input_length(input, 128)?;
+		let ref_index = parse_u32(&input[..32])?;
+		let aye = parse_bool(&input[32..64])?;
+		let vote_amount = parse_amount::<BalanceOf<Runtime>>(&input[64..96])?;
+		let conviction = parse_conviction(&input[96..])?;
+
+		let vote = AccountVote::Standard {
+			vote: Vote { aye, conviction },
+			balance: vote_amount,
+		};
+
+		log::trace!(
+			target: "democracy-precompile",
+			"Voting {:?} on referendum #{:?}, with conviction {:?}",
+			aye,
+			ref_index,
+			conviction
+		);
+
+		Ok(pallet_democracy::Call::<Runtime>::vote(ref_index, vote))
+	}
+
+	fn remove_vote(input: &[u8]) -> Result<pallet_democracy::Call<Runtime>, ExitError> {
+		let ref_index = parse_u32(input)?;
+
+		log::trace!(
+			target: "democracy-precompile",
+			"Removing vote from referendum {:?}",
+			ref_index
+		);
+
+		Ok(pallet_democracy::Call::<Runtime>::remove_vote(ref_index))
+	}
+
+	fn delegate(input: &[u8]) -> Result<pallet_democracy::Call<Runtime>, ExitError> {
+		check_input_length(input, 96)?;
+		let to = parse_account(&input[..32])?;
+		let conviction = parse_conviction(&input[32..64])?;
+		let amount = parse_amount::<BalanceOf<Runtime>>(&input[64..])?;
+
+		log::trace!(
+			target: "democracy-precompile",
+			"Delegating vote to {:?} with balance {:?} and conviction {:?}",
+			to,
+			amount,
+			conviction
+		);
+
+		Ok(pallet_democracy::Call::<Runtime>::delegate(
+			to.into(),
+			conviction,
+			amount,
+		))
+	}
+
+	fn un_delegate(input: &[u8]) -> Result<pallet_democracy::Call<Runtime>, ExitError> {
+		check_input_length(input, 0)?;
+
+		Ok(pallet_democracy::Call::<Runtime>::undelegate())
+	}
+
+	fn unlock(input: &[u8]) -> Result<pallet_democracy::Call<Runtime>, ExitError> {
+		let target = parse_account(input)?;
+
+		log::trace!(target: "democracy-precompile", "Unlocking democracy tokens for {:?}", target);
+
+		Ok(pallet_democracy::Call::<Runtime>::unlock(target.into()))
+	}
+
+	fn note_preimage(input: &[u8]) -> Result<pallet_democracy::Call<Runtime>, ExitError> {
+		let encoded_proposal = parse_bytes(input)?;
+
+		log::trace!(
+			target: "democracy-precompile",
+			"Noting preimage {:?}",
+			encoded_proposal
+		);
+
+		Ok(pallet_democracy::Call::<Runtime>::note_preimage(
+			encoded_proposal,
+		))
+	}
+
+	fn read_cost() -> u64 {
+		<Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
+			<Runtime as frame_system::Config>::DbWeight::get().read,
+		)
+	}
+}
+
+/// Parses a vote conviction, which is a value between 0 and 6 (included).
+fn parse_conviction(input: &[u8]) -> Result<Conviction, ExitError> {
+	let conviction = parse_uint256(input)?;
+
+	if conviction > U256::from(6u8) {
+		return Err(ExitError::Other(
+			"Conviction must be an integer in the range 0-6".into(),
+		));
+	}
+
+	Conviction::try_from(conviction.low_u32() as u8)
+		.map_err(|_| ExitError::Other("Conviction must be an integer in the range 0-6".into()))
+}
@@ -4,8 +4,74 @@ pragma solidity >=0.8.0;
 /// The interface through which solidity contracts will interact with Parachain Staking
 /// We follow this same interface including four-byte function selectors, in the precompile that
 /// wraps the pallet
+///
+/// A call that fails in the pallet fails without revert data, so Solidity callers can't read the
+/// reason. The RPC reports the pallet error, e.g. `ParachainStaking::NomBondBelowMin`, in the error of `eth_call`
+/// and `eth_estimateGas`.
 interface ParachainStaking {
-    // First some simple accessors
+    // First the events emitted by the precompile when a dispatchable succeeds. The first topic
+    // of each log is the keccak256 hash of the event signature, given in the event docs.
+
+    /// Emitted when `candidate` joins the set of collator candidates
+    /// Topic: 0xa85ae5d22893f1618b98a1bc730da98cfa4714590a39ea541e1c57f21a10fec3
+    event CandidateJoined(address indexed candidate, uint256 amount);
+
+    /// Emitted when `candidate` requests to leave the set of collator candidates
+    /// Topic: 0xc5f6ab9a3a97ba4d47bba7415bb817dae7c5a6d5f1f678f91e5b5398d890dd33
+    event CandidateLeft(address indexed candidate);
+
+    /// Emitted when `candidate` temporarily leaves the set of collator candidates
+    /// Topic: 0xbca7c1ac4161b742d870348ef1dceda7b8364ef0a939e649d8141149bb9c1e56
+    event CandidateWentOffline(address indexed candidate);
+
+    /// Emitted when `candidate` rejoins the set of collator candidates
+    /// Topic: 0x92071c63ff16340a84c7fe2b83f4941f0bb42afecfe762a36165f2c688681478
+    event CandidateBackOnline(address indexed candidate);
+
+    /// Emitted when `candidate` bonds `amount` more
+    /// Topic: 0x9ecef6fd689de69f199b676b859992613c9b4108192b721956b02e8a572d2adc
+    event CandidateBondedMore(address indexed candidate, uint256 amount);
+
+    /// Emitted when `candidate` bonds `amount` less
+    /// Topic: 0x749627b695335383fe5b8e5c5735eb6fce45bb92a6f3eafa46740edca0757972
+    event CandidateBondedLess(address indexed candidate, uint256 amount);
+
+    /// Emitted when `nominator` nominates `collator` with `amount`
+    /// Topic: 0xe89e9c9c7af6c43992fa46c3d7e955f6b3d01d8e594eeda65b0b746412d93d47
+    event Nominated(
+        address indexed nominator,
+        address indexed collator,
+        uint256 amount
+    );
+
+    /// Emitted when `nominator` leaves the set of nominators
+    /// Topic: 0x671c1a6a68775f4d1869b183c7c9229ddd8884ccb435635c98ad5287ca69a9a3
+    event NominatorLeft(address indexed nominator);
+
+    /// Emitted when `nominator` revokes its nomination of `collator`
+    /// Topic: 0xb222dc33217c4c704f7cdb44179e2c0517c57390969d454375ead7be45c2631e
+    event NominationRevoked(
+        address indexed nominator,
+        address indexed collator
+    );
+
+    /// Emitted when `nominator` bonds `amount` more for `collator`
+    /// Topic: 0xf46d90310dd88d93ab777637cd5a29b5e391e68aa198c313214afaf10ae6004e
+    event NominationIncreased(
+        address indexed nominator,
+        address indexed collator,
+        uint256 amount
+    );
+
+    /// Emitted when `nominator` bonds `amount` less for `collator`
+    /// Topic: 0x724f01fb79f365d8972eab71f18a705b7f73a157dc4261c674a7decc31715b6a
+    event NominationDecreased(
+        address indexed nominator,
+        address indexed collator,
+        uint256 amount
+    );
+
+    // Then some simple accessors
 
     /// Check whether the specified address is currently a staking nominator
     function is_nominator(address nominator) external view returns (bool);
@@ -25,6 +91,62 @@ interface ParachainStaking {
     /// Get the minimum nomination amount
     function min_nomination() external view returns (uint256);
 
+    /// Points awarded to a collator in a particular round
+    function awarded_pts(uint256 round, address collator)
+        external
+        view
+        returns (uint256);
+
+    /// The current round index
+    function round() external view returns (uint256);
+
+    /// The number of collator candidates, which is the weight hint of join/leave_candidates
+    function candidate_count() external view returns (uint256);
+
+    /// The collator candidates selected for the current round
+    function selected_candidates() external view returns (address[] memory);
+
+    /// The self bond of a collator candidate, 0 if the address is not a candidate
+    function candidate_bond(address collator) external view returns (uint256);
+
+    /// The self bond plus all the nominations of a collator candidate
+    function candidate_total_backing(address collator)
+        external
+        view
+        returns (uint256);
+
+    /// The number of nominations of a collator candidate, which is the
+    /// `collatorNominationCount` weight hint of `nominate`
+    function collator_nomination_count(address collator)
+        external
+        view
+        returns (uint256);
+
+    /// The round at which a leaving collator candidate exits, 0 if it is not leaving
+    function candidate_exit_round(address collator)
+        external
+        view
+        returns (uint256);
+
+    /// The total amount nominated by a nominator
+    function nominator_total(address nominator)
+        external
+        view
+        returns (uint256);
+
+    /// The number of nominations of a nominator, which is the `nominatorNominationCount`
+    /// weight hint of `nominate` and `leave_nominators`
+    function nominator_nomination_count(address nominator)
+        external
+        view
+        returns (uint256);
+
+    /// The amount nominated by a nominator to a collator, 0 if there is no such nomination
+    function nomination_amount(address nominator, address collator)
+        external
+        view
+        returns (uint256);
+
     // Now the dispatchables
 
     /// Join the set of collator candidates
@@ -88,5 +210,17 @@ interface ParachainStaking {
 // 	"f6a52569": "nominator_bond_less(address,uint256)",
 // 	"971d44c8": "nominator_bond_more(address,uint256)",
 // 	"4b65c34b": "revoke_nomination(address)"
-// 	"9799b4e7": "points(uint256)"
+// 	"9799b4e7": "points(uint256)",
+// 	"ec7e7a22": "awarded_pts(uint256,address)",
+// 	"146ca531": "round()",
+// 	"4b1c4c29": "candidate_count()",
+// 	"89f47a21": "selected_candidates()",
+// 	"1b4c6ef1": "candidate_bond(address)",
+// 	"ea61a29e": "candidate_total_backing(address)",
+// 	"0ad6a7be": "collator_nomination_count(address)",
+// 	"43892755": "candidate_exit_round(address)",
+// 	"b6c29bcd": "nominator_total(address)",
+// 	"dae5659b": "nominator_nomination_count(address)",
+// 	"a19a26cb": "nomination_amount(address,address)"
 // }
+
@@ -0,0 +1,287 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+//! Benchmarking of the precompile views. The component `x` is the number of entries of the
+//! storage value read by the view.
+use crate::{BalanceOf, ParachainStakingWrapper};
+use evm::Context;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo};
+use frame_support::traits::{Currency, Get, OnInitialize};
+use frame_system::RawOrigin;
+use pallet_evm::Precompile;
+use sp_core::{H160, U256};
+use sp_std::{convert::TryFrom, fmt::Debug, marker::PhantomData, vec::Vec};
+
+/// The precompile is not a pallet, so its benchmarks are implemented for this type instead.
+pub struct Pallet<T: Config>(PhantomData<T>);
+
+pub trait Config: parachain_staking::Config + pallet_evm::Config {}
+
+const USER_SEED: u32 = 999666;
+
+/// Create a user funded with enough balance to collate or nominate several times.
+fn create_funded_user<T: Config>(string: &'static str, n: u32) -> T::AccountId {
+	let user = account(string, n, USER_SEED);
+	let balance = <<T as parachain_staking::Config>::MinCollatorStk as Get<BalanceOf<T>>>::get()
+		* 1_000u32.into();
+	<T as parachain_staking::Config>::Currency::make_free_balance_be(&user, balance);
+	<T as parachain_staking::Config>::Currency::issue(balance);
+	user
+}
+
+/// Number of candidates in the pool, used as weight hint when joining the candidates.
+fn candidate_pool_size<T: Config>() -> u32 {
+	parachain_staking::Pallet::<T>::candidate_pool().0.len() as u32
+}
+
+/// Create a collator candidate with `nominator_count` nominators.
+fn create_collator<T: Config>(n: u32, nominator_count: u32) -> Result<T::AccountId, &'static str> {
+	let collator = create_funded_user::<T>("collator", n);
+	parachain_staking::Pallet::<T>::join_candidates(
+		RawOrigin::Signed(collator.clone()).into(),
+		<<T as parachain_staking::Config>::MinCollatorStk as Get<BalanceOf<T>>>::get(),
+		candidate_pool_size::<T>(),
+	)?;
+	for i in 0..nominator_count {
+		let nominator = create_funded_user::<T>("nominator", n * 10_000 + i);
+		parachain_staking::Pallet::<T>::nominate(
+			RawOrigin::Signed(nominator).into(),
+			collator.clone(),
+			<<T as parachain_staking::Config>::MinNominatorStk as Get<BalanceOf<T>>>::get(),
+			i,
+			0u32,
+		)?;
+	}
+	Ok(collator)
+}
+
+/// Create a nominator with `nomination_count` nominations. Also returns the nominated collators.
+fn create_nominator<T: Config>(
+	nomination_count: u32,
+) -> Result<(T::AccountId, Vec<T::AccountId>), &'static str> {
+	let nominator = create_funded_user::<T>("nominator", 0);
+	let mut collators = Vec::new();
+	for i in 0..nomination_count {
+		let collator = create_collator::<T>(i + 1, 0)?;
+		parachain_staking::Pallet::<T>::nominate(
+			RawOrigin::Signed(nominator.clone()).into(),
+			collator.clone(),
+			<<T as parachain_staking::Config>::MinNominatorStk as Get<BalanceOf<T>>>::get(),
+			0u32,
+			i,
+		)?;
+		collators.push(collator);
+	}
+	Ok((nominator, collators))
+}
+
+/// Create `count` collators and start a new round so that all of them are selected.
+fn select_collators<T: Config>(count: u32) -> Result<(), &'static str> {
+	for i in 0..count {
+		create_collator::<T>(i + 1, 0)?;
+	}
+	let min_selected = <<T as parachain_staking::Config>::MinSelectedCandidates as Get<u32>>::get();
+	parachain_staking::Pallet::<T>::set_total_selected(
+		RawOrigin::Root.into(),
+		candidate_pool_size::<T>().max(min_selected),
+	)?;
+	let round_info = parachain_staking::Pallet::<T>::round();
+	parachain_staking::Pallet::<T>::on_initialize(round_info.first + round_info.length.into());
+	Ok(())
+}
+
+/// Input of a view from its selector and its arguments.
+fn input(selector: [u8; 4], arguments: &[U256]) -> Vec<u8> {
+	let mut input = selector.to_vec();
+	for argument in arguments {
+		let mut word = [0u8; 32];
+		argument.to_big_endian(&mut word);
+		input.extend_from_slice(&word);
+	}
+	input
+}
+
+/// Encodes an account as a view argument.
+fn address<T: Config>(account: T::AccountId) -> U256
+where
+	T::AccountId: Into<H160>,
+{
+	U256::from_big_endian(account.into().as_bytes())
+}
+
+/// Execute a view of the staking precompile.
+fn execute<T: Config>(input: &[u8]) -> Result<Vec<u8>, &'static str>
+where
+	T::AccountId: From<H160> + Into<H160>,
+	BalanceOf<T>: TryFrom<U256> + Into<U256> + Debug,
+	<T as frame_system::Config>::Call: Dispatchable<PostInfo = PostDispatchInfo>
+		+ GetDispatchInfo
+		+ From<parachain_staking::Call<T>>,
+	<<T as frame_system::Config>::Call as Dispatchable>::Origin: From<Option<T::AccountId>>,
+{
+	let context = Context {
+		address: H160::from_low_u64_be(2048),
+		caller: H160::zero(),
+		apparent_value: U256::zero(),
+	};
+	ParachainStakingWrapper::<T>::execute(input, None, &context)
+		.map(|output| output.output)
+		.map_err(|_| "Staking precompile view failed")
+}
+
+benchmarks! {
+	where_clause {
+		where
+			T::AccountId: From<H160> + Into<H160>,
+			BalanceOf<T>: TryFrom<U256> + Into<U256> + Debug,
+			<T as frame_system::Config>::Call: Dispatchable<PostInfo = PostDispatchInfo>
+				+ GetDispatchInfo
+				+ From<parachain_staking::Call<T>>,
+			<<T as frame_system::Config>::Call as Dispatchable>::Origin:
+				From<Option<T::AccountId>>,
+	}
+
+	is_nominator {
+		let x in 1..<<T as parachain_staking::Config>::MaxCollatorsPerNominator as Get<u32>>::get();
+		let (nominator, _) = create_nominator::<T>(x)?;
+		let input = input([0x8e, 0x50, 0x80, 0xe7], &[address::<T>(nominator)]);
+	}: {
+		execute::<T>(&input)?;
+	}
+
+	is_candidate {
+		let x in 0..<<T as parachain_staking::Config>::MaxNominatorsPerCollator as Get<u32>>::get();
+		let collator = create_collator::<T>(1, x)?;
+		let input = input([0x85, 0x45, 0xc8, 0x33], &[address::<T>(collator)]);
+	}: {
+		execute::<T>(&input)?;
+	}
+
+	is_selected_candidate {
+		let x in 1..100;
+		select_collators::<T>(x)?;
+		let input = input([0x8f, 0x6d, 0x27, 0xc7], &[U256::zero()]);
+	}: {
+		execute::<T>(&input)?;
+	}
+
+	min_nomination {
+		let input = input([0xc9, 0xf5, 0x93, 0xb2], &[]);
+	}: {
+		execute::<T>(&input)?;
+	}
+
+	points {
+		let input = input([0x97, 0x99, 0xb4, 0xe7], &[U256::one()]);
+	}: {
+		execute::<T>(&input)?;
+	}
+
+	awarded_pts {
+		let collator = create_collator::<T>(1, 0)?;
+		let input = input([0xec, 0x7e, 0x7a, 0x22], &[U256::one(), address::<T>(collator)]);
+	}: {
+		execute::<T>(&input)?;
+	}
+
+	round {
+		let input = input([0x14, 0x6c, 0xa5, 0x31], &[]);
+	}: {
+		execute::<T>(&input)?;
+	}
+
+	candidate_count {
+		let x in 1..100;
+		for i in 0..x {
+			create_collator::<T>(i + 1, 0)?;
+		}
+		let input = input([0x4b, 0x1c, 0x4c, 0x29], &[]);
+	}: {
+		execute::<T>(&input)?;
+	}
+
+	selected_candidates {
+		let x in 1..100;
+		select_collators::<T>(x)?;
+		let input = input([0x89, 0xf4, 0x7a, 0x21], &[]);
+	}: {
+		execute::<T>(&input)?;
+	}
+
+	candidate_bond {
+		let x in 0..<<T as parachain_staking::Config>::MaxNominatorsPerCollator as Get<u32>>::get();
+		let collator = create_collator::<T>(1, x)?;
+		let input = input([0x1b, 0x4c, 0x6e, 0xf1], &[address::<T>(collator)]);
+	}: {
+		execute::<T>(&input)?;
+	}
+
+	candidate_total_backing {
+		let x in 0..<<T as parachain_staking::Config>::MaxNominatorsPerCollator as Get<u32>>::get();
+		let collator = create_collator::<T>(1, x)?;
+		let input = input([0xea, 0x61, 0xa2, 0x9e], &[address::<T>(collator)]);
+	}: {
+		execute::<T>(&input)?;
+	}
+
+	collator_nomination_count {
+		let x in 0..<<T as parachain_staking::Config>::MaxNominatorsPerCollator as Get<u32>>::get();
+		let collator = create_collator::<T>(1, x)?;
+		let input = input([0x0a, 0xd6, 0xa7, 0xbe], &[address::<T>(collator)]);
+	}: {
+		execute::<T>(&input)?;
+	}
+
+	candidate_exit_round {
+		let x in 0..<<T as parachain_staking::Config>::MaxNominatorsPerCollator as Get<u32>>::get();
+		let collator = create_collator::<T>(1, x)?;
+		let input = input([0x43, 0x89, 0x27, 0x55], &[address::<T>(collator)]);
+	}: {
+		execute::<T>(&input)?;
+	}
+
+	nominator_total {
+		let x in 1..<<T as parachain_staking::Config>::MaxCollatorsPerNominator as Get<u32>>::get();
+		let (nominator, _) = create_nominator::<T>(x)?;
+		let input = input([0xb6, 0xc2, 0x9b, 0xcd], &[address::<T>(nominator)]);
+	}: {
+		execute::<T>(&input)?;
+	}
+
+	nominator_nomination_count {
+		let x in 1..<<T as parachain_staking::Config>::MaxCollatorsPerNominator as Get<u32>>::get();
+		let (nominator, _) = create_nominator::<T>(x)?;
+		let input = input([0xda, 0xe5, 0x65, 0x9b], &[address::<T>(nominator)]);
+	}: {
+		execute::<T>(&input)?;
+	}
+
+	nomination_amount {
+		let x in 1..<<T as parachain_staking::Config>::MaxCollatorsPerNominator as Get<u32>>::get();
+		let (nominator, collators) = create_nominator::<T>(x)?;
+		// Worst case is the nomination of the last collator
+		let collator = collators.last().cloned().ok_or("No nomination")?;
+		let input = input(
+			[0xa1, 0x9a, 0x26, 0xcb],
+			&[address::<T>(nominator), address::<T>(collator)],
+		);
+	}: {
+		execute::<T>(&input)?;
+	}
+}
@@ -18,22 +18,48 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use evm::{executor::PrecompileOutput, Context, ExitError, ExitSucceed};
+use evm::{backend::Log, executor::PrecompileOutput, Context, ExitError, ExitSucceed};
 use frame_support::dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo};
 use frame_support::traits::{Currency, Get};
+use frame_support::weights::Weight;
 use pallet_evm::AddressMapping;
 use pallet_evm::GasWeightMapping;
 use pallet_evm::Precompile;
-use sp_core::{H160, U256};
+use precompile_utils::{
+	accounts_to_solidity_bytes, bool_to_solidity_bytes, parse_account, parse_amount, parse_u32,
+	u256_to_solidity_bytes,
+};
+use sp_core::{H160, H256, U256};
 use sp_std::convert::{TryFrom, TryInto};
 use sp_std::fmt::Debug;
 use sp_std::marker::PhantomData;
 use sp_std::vec::Vec;
+use weights::{SubstrateWeight, WeightInfo};
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarks;
+pub mod weights;
 
 type BalanceOf<Runtime> = <<Runtime as parachain_staking::Config>::Currency as Currency<
 	<Runtime as frame_system::Config>::AccountId,
 >>::Balance;
 
+// Signatures of the Solidity events emitted as logs by the precompile when a staking call is
+// successfully dispatched. The first topic of each log is the keccak256 hash of the signature,
+// the following topics are the (indexed) addresses, and the data is the amount if any.
+// The events are declared in StakingInterface.sol, whose docs give the topic of each event.
+pub const EVENT_CANDIDATE_JOINED: &[u8] = b"CandidateJoined(address,uint256)";
+pub const EVENT_CANDIDATE_LEFT: &[u8] = b"CandidateLeft(address)";
+pub const EVENT_CANDIDATE_WENT_OFFLINE: &[u8] = b"CandidateWentOffline(address)";
+pub const EVENT_CANDIDATE_BACK_ONLINE: &[u8] = b"CandidateBackOnline(address)";
+pub const EVENT_CANDIDATE_BONDED_MORE: &[u8] = b"CandidateBondedMore(address,uint256)";
+pub const EVENT_CANDIDATE_BONDED_LESS: &[u8] = b"CandidateBondedLess(address,uint256)";
+pub const EVENT_NOMINATED: &[u8] = b"Nominated(address,address,uint256)";
+pub const EVENT_NOMINATOR_LEFT: &[u8] = b"NominatorLeft(address)";
+pub const EVENT_NOMINATION_REVOKED: &[u8] = b"NominationRevoked(address,address)";
+pub const EVENT_NOMINATION_INCREASED: &[u8] = b"NominationIncreased(address,address,uint256)";
+pub const EVENT_NOMINATION_DECREASED: &[u8] = b"NominationDecreased(address,address,uint256)";
+
 /// A precompile to wrap the functionality from parachain_staking.
 ///
 /// EXAMPLE USECASE:
@@ -46,8 +72,8 @@ pub struct ParachainStakingWrapper<Runtime>(PhantomData<Runtime>);
 impl<Runtime> Precompile for ParachainStakingWrapper<Runtime>
 where
 	Runtime: parachain_staking::Config + pallet_evm::Config,
-	Runtime::AccountId: From<H160>,
-	BalanceOf<Runtime>: TryFrom<U256> + Debug,
+	Runtime::AccountId: From<H160> + Into<H160>,
+	BalanceOf<Runtime>: TryFrom<U256> + Into<U256> + Debug,
 	Runtime::Call: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
 	<Runtime::Call as Dispatchable>::Origin: From<Option<Runtime::AccountId>>,
 	Runtime::Call: From<parachain_staking::Call<Runtime>>,
@@ -78,20 +104,53 @@ where
 		let inner_call = match input[0..SELECTOR_SIZE_BYTES] {
 			// Check for accessor methods first. These return results immediately
 			[0x8e, 0x50, 0x80, 0xe7] => {
-				return Self::is_nominator(&input[SELECTOR_SIZE_BYTES..]);
+				return Self::is_nominator(&input[SELECTOR_SIZE_BYTES..], target_gas);
 			}
 			[0x85, 0x45, 0xc8, 0x33] => {
-				return Self::is_candidate(&input[SELECTOR_SIZE_BYTES..]);
+				return Self::is_candidate(&input[SELECTOR_SIZE_BYTES..], target_gas);
 			}
 			[0x8f, 0x6d, 0x27, 0xc7] => {
-				return Self::is_selected_candidate(&input[SELECTOR_SIZE_BYTES..]);
+				return Self::is_selected_candidate(&input[SELECTOR_SIZE_BYTES..], target_gas);
 			}
 			[0xc9, 0xf5, 0x93, 0xb2] => {
 				//TODO Do we need to verify that there were no additional bytes passed in here?
-				return Self::min_nomination();
+				return Self::min_nomination(target_gas);
 			}
 			[0x97, 0x99, 0xb4, 0xe7] => {
-				return Self::points(&input[SELECTOR_SIZE_BYTES..]);
+				return Self::points(&input[SELECTOR_SIZE_BYTES..], target_gas);
+			}
+			[0xec, 0x7e, 0x7a, 0x22] => {
+				return Self::awarded_pts(&input[SELECTOR_SIZE_BYTES..], target_gas);
+			}
+			[0x14, 0x6c, 0xa5, 0x31] => {
+				return Self::round(target_gas);
+			}
+			[0x4b, 0x1c, 0x4c, 0x29] => {
+				return Self::candidate_count(target_gas);
+			}
+			[0x89, 0xf4, 0x7a, 0x21] => {
+				return Self::selected_candidates(target_gas);
+			}
+			[0x1b, 0x4c, 0x6e, 0xf1] => {
+				return Self::candidate_bond(&input[SELECTOR_SIZE_BYTES..], target_gas);
+			}
+			[0xea, 0x61, 0xa2, 0x9e] => {
+				return Self::candidate_total_backing(&input[SELECTOR_SIZE_BYTES..], target_gas);
+			}
+			[0x0a, 0xd6, 0xa7, 0xbe] => {
+				return Self::collator_nomination_count(&input[SELECTOR_SIZE_BYTES..], target_gas);
+			}
+			[0x43, 0x89, 0x27, 0x55] => {
+				return Self::candidate_exit_round(&input[SELECTOR_SIZE_BYTES..], target_gas);
+			}
+			[0xb6, 0xc2, 0x9b, 0xcd] => {
+				return Self::nominator_total(&input[SELECTOR_SIZE_BYTES..], target_gas);
+			}
+			[0xda, 0xe5, 0x65, 0x9b] => {
+				return Self::nominator_nomination_count(&input[SELECTOR_SIZE_BYTES..], target_gas);
+			}
+			[0xa1, 0x9a, 0x26, 0xcb] => {
+				return Self::nomination_amount(&input[SELECTOR_SIZE_BYTES..], target_gas);
 			}
 
 			// If not an accessor, check for dispatchables. These calls ready for dispatch below.
@@ -117,6 +176,9 @@ where
 			}
 		};
 
+		// The logs are only emitted if the dispatch below succeeds
+		let logs = Self::logs_for_call(&inner_call, context);
+
 		let outer_call: Runtime::Call = inner_call.into();
 		let info = outer_call.get_dispatch_info();
 
@@ -143,7 +205,7 @@ where
 					exit_status: ExitSucceed::Stopped,
 					cost: gas_used,
 					output: Default::default(),
-					logs: Default::default(),
+					logs,
 				})
 			}
 			Err(e) => {
@@ -152,95 +214,28 @@ where
 					"Parachain staking call via evm failed {:?}",
 					e
 				);
-				Err(ExitError::Other(
-					"Parachain staking call via EVM failed".into(),
-				))
+				Err(precompile_utils::dispatch_error::<
+					Runtime,
+					parachain_staking::Pallet<Runtime>,
+				>("Parachain staking call via EVM failed", e.error))
 			}
 		}
 	}
 }
 
-/// Parses an H160 account address from a 256 bit (32 byte) buffer. Only the last 20 bytes are used.
-fn parse_account(input: &[u8]) -> Result<H160, ExitError> {
-	const PADDING_SIZE_BYTES: usize = 12;
-	const ACCOUNT_SIZE_BYTES: usize = 20;
-	const TOTAL_SIZE_BYTES: usize = PADDING_SIZE_BYTES + ACCOUNT_SIZE_BYTES;
-
-	if input.len() != TOTAL_SIZE_BYTES {
-		log::trace!(target: "staking-precompile",
-			"Unable to parse address. Got {} bytes, expected {}",
-			input.len(),
-			TOTAL_SIZE_BYTES,
-		);
-		return Err(ExitError::Other(
-			"Incorrect input length for account parsing".into(),
-		));
-	}
-
-	Ok(H160::from_slice(
-		&input[PADDING_SIZE_BYTES..TOTAL_SIZE_BYTES],
-	))
-}
-
-/// Parses an amount of ether from a 256 bit (32 byte) slice. The balance type is generic.
-fn parse_amount<Balance: TryFrom<U256>>(input: &[u8]) -> Result<Balance, ExitError> {
-	Ok(parse_uint256(input)?
-		.try_into()
-		.map_err(|_| ExitError::Other("Amount is too large for provided balance type".into()))?)
-}
-
-/// Parses a uint256 value
-fn parse_uint256(input: &[u8]) -> Result<U256, ExitError> {
-	// In solidity all values are encoded to this width
-	const SIZE_BYTES: usize = 32;
-
-	if input.len() != SIZE_BYTES {
-		log::trace!(target: "staking-precompile",
-			"Unable to parse uint256. Got {} bytes, expected {}",
-			input.len(),
-			SIZE_BYTES,
-		);
-		return Err(ExitError::Other(
-			"Incorrect input length for uint256 parsing".into(),
-		));
-	}
-
-	Ok(U256::from_big_endian(&input[0..SIZE_BYTES]))
-}
-
-/// Parses Weight Hint: u32 from a 256 bit (32 byte) slice.
-fn parse_weight_hint(input: &[u8]) -> Result<u32, ExitError> {
-	const WEIGHT_HINT_SIZE_BYTES: usize = 32;
-
-	if input.len() != WEIGHT_HINT_SIZE_BYTES {
-		log::trace!(target: "staking-precompile",
-			"Unable to parse weight hint. Got {} bytes, expected {}",
-			input.len(),
-			WEIGHT_HINT_SIZE_BYTES,
-		);
-		return Err(ExitError::Other(
-			"Incorrect input length for weight hint parsing".into(),
-		));
-	}
-
-	let weight_hint: u32 = U256::from_big_endian(&input[0..WEIGHT_HINT_SIZE_BYTES])
-		.try_into()
-		.map_err(|_| ExitError::Other("Weight hint is too large for u32".into()))?;
-	Ok(weight_hint)
-}
-
 impl<Runtime> ParachainStakingWrapper<Runtime>
 where
 	Runtime: parachain_staking::Config + pallet_evm::Config + frame_system::Config,
-	Runtime::AccountId: From<H160>,
-	BalanceOf<Runtime>: TryFrom<U256> + TryInto<u128> + Debug,
+	Runtime::AccountId: From<H160> + Into<H160>,
+	BalanceOf<Runtime>: TryFrom<U256> + TryInto<u128> + Into<U256> + Debug,
 	Runtime::Call: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
 	<Runtime::Call as Dispatchable>::Origin: From<Option<Runtime::AccountId>>,
 	Runtime::Call: From<parachain_staking::Call<Runtime>>,
 {
-	// The accessors are first. They directly return their result.
+	// The accessors are first. They directly return their result. Each of them is charged the
+	// gas equivalent of its benchmarked weight, which grows with the size of the value read.
 
-	fn is_nominator(input: &[u8]) -> Result<PrecompileOutput, ExitError> {
+	fn is_nominator(input: &[u8], target_gas: Option<u64>) -> Result<PrecompileOutput, ExitError> {
 		// parse the address
 		let nominator = H160::from_slice(&input[12..32]);
 
@@ -251,23 +246,19 @@ where
 		);
 
 		// fetch data from pallet
-		let is_nominator = parachain_staking::Pallet::<Runtime>::is_nominator(&nominator.into());
+		let nomination_count = Self::nominator_state_size_of(nominator);
+		let is_nominator = nomination_count.is_some();
 
 		log::trace!(target: "staking-precompile", "Result from pallet is {:?}", is_nominator);
 
-		let gas_consumed = <Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
-			<Runtime as frame_system::Config>::DbWeight::get().read,
-		);
-
-		Ok(PrecompileOutput {
-			exit_status: ExitSucceed::Returned,
-			cost: gas_consumed,
-			output: bool_to_solidity_bytes(is_nominator),
-			logs: Default::default(),
-		})
+		Self::view_output(
+			SubstrateWeight::<Runtime>::is_nominator(nomination_count.unwrap_or_default()),
+			target_gas,
+			bool_to_solidity_bytes(is_nominator),
+		)
 	}
 
-	fn is_candidate(input: &[u8]) -> Result<PrecompileOutput, ExitError> {
+	fn is_candidate(input: &[u8], target_gas: Option<u64>) -> Result<PrecompileOutput, ExitError> {
 		// parse the address
 		let candidate = H160::from_slice(&input[12..32]);
 
@@ -278,23 +269,22 @@ where
 		);
 
 		// fetch data from pallet
-		let is_candidate = parachain_staking::Pallet::<Runtime>::is_candidate(&candidate.into());
+		let nominator_count = Self::collator_state_size_of(candidate);
+		let is_candidate = nominator_count.is_some();
 
 		log::trace!(target: "staking-precompile", "Result from pallet is {:?}", is_candidate);
 
-		let gas_consumed = <Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
-			<Runtime as frame_system::Config>::DbWeight::get().read,
-		);
-
-		Ok(PrecompileOutput {
-			exit_status: ExitSucceed::Returned,
-			cost: gas_consumed,
-			output: bool_to_solidity_bytes(is_candidate),
-			logs: Default::default(),
-		})
+		Self::view_output(
+			SubstrateWeight::<Runtime>::is_candidate(nominator_count.unwrap_or_default()),
+			target_gas,
+			bool_to_solidity_bytes(is_candidate),
+		)
 	}
 
-	fn is_selected_candidate(input: &[u8]) -> Result<PrecompileOutput, ExitError> {
+	fn is_selected_candidate(
+		input: &[u8],
+		target_gas: Option<u64>,
+	) -> Result<PrecompileOutput, ExitError> {
 		// parse the address
 		let candidate = H160::from_slice(&input[12..32]);
 
@@ -305,24 +295,21 @@ where
 		);
 
 		// fetch data from pallet
-		let is_selected =
-			parachain_staking::Pallet::<Runtime>::is_selected_candidate(&candidate.into());
+		let selected_candidates = parachain_staking::Pallet::<Runtime>::selected_candidates();
+		let is_selected = selected_candidates
+			.binary_search(&Runtime::AccountId::from(candidate))
+			.is_ok();
 
 		log::trace!(target: "staking-precompile", "Result from pallet is {:?}", is_selected);
 
-		let gas_consumed = <Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
-			<Runtime as frame_system::Config>::DbWeight::get().read,
-		);
-
-		Ok(PrecompileOutput {
-			exit_status: ExitSucceed::Returned,
-			cost: gas_consumed,
-			output: bool_to_solidity_bytes(is_selected),
-			logs: Default::default(),
-		})
+		Self::view_output(
+			SubstrateWeight::<Runtime>::is_selected_candidate(selected_candidates.len() as u32),
+			target_gas,
+			bool_to_solidity_bytes(is_selected),
+		)
 	}
 
-	fn min_nomination() -> Result<PrecompileOutput, ExitError> {
+	fn min_nomination(target_gas: Option<u64>) -> Result<PrecompileOutput, ExitError> {
 		// fetch data from pallet
 		let raw_min_nomination: u128 = <
 			<Runtime as parachain_staking::Config>::MinNomination
@@ -335,50 +322,356 @@ where
 
 		log::trace!(target: "staking-precompile", "Result from pallet is {:?}", min_nomination);
 
-		// TODO find cost of Config associated type read
-		// For now assume it is as bad as a storage read in the worst case
-		let gas_consumed = <Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
-			<Runtime as frame_system::Config>::DbWeight::get().read,
+		Self::view_output(
+			SubstrateWeight::<Runtime>::min_nomination(),
+			target_gas,
+			u256_to_solidity_bytes(min_nomination),
+		)
+	}
+
+	fn points(input: &[u8], target_gas: Option<u64>) -> Result<PrecompileOutput, ExitError> {
+		let round = parse_u32(input)?;
+
+		log::trace!(target: "staking-precompile", "🥩round is {}", round);
+		// Read the point value and format it for Solidity
+		let points: u32 = parachain_staking::Pallet::<Runtime>::points(round);
+		log::trace!(target: "staking-precompile", "🥩points is {}", points);
+
+		Self::view_output(
+			SubstrateWeight::<Runtime>::points(),
+			target_gas,
+			u256_to_solidity_bytes(points.into()),
+		)
+	}
+
+	fn awarded_pts(input: &[u8], target_gas: Option<u64>) -> Result<PrecompileOutput, ExitError> {
+		if input.len() != 64 {
+			return Err(ExitError::Other(
+				"Incorrect input length for awarded_pts".into(),
+			));
+		}
+		let round = parse_u32(&input[..32])?;
+		let candidate = parse_account(&input[32..])?;
+
+		// Read the points awarded to the candidate in the round
+		let points: u32 = parachain_staking::Pallet::<Runtime>::awarded_pts(
+			round,
+			Runtime::AccountId::from(candidate),
 		);
 
-		let mut buffer = [0u8; 32];
-		min_nomination.to_big_endian(&mut buffer);
+		log::trace!(
+			target: "staking-precompile",
+			"Points awarded to {:?} in round {} are {}",
+			candidate,
+			round,
+			points
+		);
 
-		Ok(PrecompileOutput {
-			exit_status: ExitSucceed::Returned,
-			cost: gas_consumed,
-			output: buffer.to_vec(),
-			logs: Default::default(),
-		})
+		Self::view_output(
+			SubstrateWeight::<Runtime>::awarded_pts(),
+			target_gas,
+			u256_to_solidity_bytes(points.into()),
+		)
+	}
+
+	fn round(target_gas: Option<u64>) -> Result<PrecompileOutput, ExitError> {
+		// fetch data from pallet
+		let round: u32 = parachain_staking::Pallet::<Runtime>::round().current;
+
+		log::trace!(target: "staking-precompile", "Result from pallet is {:?}", round);
+
+		Self::view_output(
+			SubstrateWeight::<Runtime>::round(),
+			target_gas,
+			u256_to_solidity_bytes(round.into()),
+		)
+	}
+
+	fn candidate_count(target_gas: Option<u64>) -> Result<PrecompileOutput, ExitError> {
+		// fetch data from pallet. This is the weight hint of join_candidates and leave_candidates
+		let candidate_count: u32 = parachain_staking::Pallet::<Runtime>::candidate_pool()
+			.0
+			.len() as u32;
+
+		log::trace!(target: "staking-precompile", "Result from pallet is {:?}", candidate_count);
+
+		Self::view_output(
+			SubstrateWeight::<Runtime>::candidate_count(candidate_count),
+			target_gas,
+			u256_to_solidity_bytes(candidate_count.into()),
+		)
+	}
+
+	fn selected_candidates(target_gas: Option<u64>) -> Result<PrecompileOutput, ExitError> {
+		// fetch data from pallet
+		let selected_candidates: Vec<H160> =
+			parachain_staking::Pallet::<Runtime>::selected_candidates()
+				.into_iter()
+				.map(Into::into)
+				.collect();
+
+		log::trace!(
+			target: "staking-precompile",
+			"Result from pallet is {:?}",
+			selected_candidates
+		);
+
+		Self::view_output(
+			SubstrateWeight::<Runtime>::selected_candidates(selected_candidates.len() as u32),
+			target_gas,
+			accounts_to_solidity_bytes(&selected_candidates),
+		)
+	}
+
+	fn candidate_bond(
+		input: &[u8],
+		target_gas: Option<u64>,
+	) -> Result<PrecompileOutput, ExitError> {
+		let candidate = parse_account(input)?;
+
+		// fetch data from pallet. Unknown candidates have no bond.
+		let state = parachain_staking::Pallet::<Runtime>::collator_state2(
+			Runtime::AccountId::from(candidate),
+		);
+		let nominator_count = Self::collator_state_size(&state);
+		let bond: U256 = state.map(|state| state.bond.into()).unwrap_or_default();
+
+		log::trace!(target: "staking-precompile", "Bond of {:?} is {:?}", candidate, bond);
+
+		Self::view_output(
+			SubstrateWeight::<Runtime>::candidate_bond(nominator_count),
+			target_gas,
+			u256_to_solidity_bytes(bond),
+		)
+	}
+
+	fn candidate_total_backing(
+		input: &[u8],
+		target_gas: Option<u64>,
+	) -> Result<PrecompileOutput, ExitError> {
+		let candidate = parse_account(input)?;
+
+		// fetch data from pallet. Unknown candidates have no backing.
+		let state = parachain_staking::Pallet::<Runtime>::collator_state2(
+			Runtime::AccountId::from(candidate),
+		);
+		let nominator_count = Self::collator_state_size(&state);
+		let total_backing: U256 = state
+			.map(|state| state.total_backing.into())
+			.unwrap_or_default();
+
+		log::trace!(
+			target: "staking-precompile",
+			"Total backing of {:?} is {:?}",
+			candidate,
+			total_backing
+		);
+
+		Self::view_output(
+			SubstrateWeight::<Runtime>::candidate_total_backing(nominator_count),
+			target_gas,
+			u256_to_solidity_bytes(total_backing),
+		)
+	}
+
+	fn collator_nomination_count(
+		input: &[u8],
+		target_gas: Option<u64>,
+	) -> Result<PrecompileOutput, ExitError> {
+		let candidate = parse_account(input)?;
+
+		// fetch data from pallet. This is the collator weight hint of nominate.
+		let nomination_count = Self::collator_state_size_of(candidate).unwrap_or_default();
+
+		log::trace!(
+			target: "staking-precompile",
+			"{:?} has {} nominations",
+			candidate,
+			nomination_count
+		);
+
+		Self::view_output(
+			SubstrateWeight::<Runtime>::collator_nomination_count(nomination_count),
+			target_gas,
+			u256_to_solidity_bytes(nomination_count.into()),
+		)
 	}
 
-	fn points(input: &[u8]) -> Result<PrecompileOutput, ExitError> {
-		let round_u256 = parse_uint256(input)?;
+	fn candidate_exit_round(
+		input: &[u8],
+		target_gas: Option<u64>,
+	) -> Result<PrecompileOutput, ExitError> {
+		let candidate = parse_account(input)?;
+
+		// fetch data from pallet. Candidates that are not leaving are reported with round 0.
+		let state = parachain_staking::Pallet::<Runtime>::collator_state2(
+			Runtime::AccountId::from(candidate),
+		);
+		let nominator_count = Self::collator_state_size(&state);
+		let exit_round: u32 = match state {
+			Some(parachain_staking::Collator2 {
+				state: parachain_staking::CollatorStatus::Leaving(round),
+				..
+			}) => round,
+			_ => 0,
+		};
+
+		log::trace!(
+			target: "staking-precompile",
+			"{:?} exits at round {}",
+			candidate,
+			exit_round
+		);
+
+		Self::view_output(
+			SubstrateWeight::<Runtime>::candidate_exit_round(nominator_count),
+			target_gas,
+			u256_to_solidity_bytes(exit_round.into()),
+		)
+	}
+
+	fn nominator_total(
+		input: &[u8],
+		target_gas: Option<u64>,
+	) -> Result<PrecompileOutput, ExitError> {
+		let nominator = parse_account(input)?;
+
+		// fetch data from pallet. Unknown nominators have nothing at stake.
+		let state = parachain_staking::Pallet::<Runtime>::nominator_state(
+			Runtime::AccountId::from(nominator),
+		);
+		let nomination_count = Self::nominator_state_size(&state);
+		let total: U256 = state.map(|state| state.total.into()).unwrap_or_default();
+
+		log::trace!(target: "staking-precompile", "Total of {:?} is {:?}", nominator, total);
+
+		Self::view_output(
+			SubstrateWeight::<Runtime>::nominator_total(nomination_count),
+			target_gas,
+			u256_to_solidity_bytes(total),
+		)
+	}
+
+	fn nominator_nomination_count(
+		input: &[u8],
+		target_gas: Option<u64>,
+	) -> Result<PrecompileOutput, ExitError> {
+		let nominator = parse_account(input)?;
+
+		// fetch data from pallet. This is the nominator weight hint of nominate and
+		// leave_nominators.
+		let nomination_count = Self::nominator_state_size_of(nominator).unwrap_or_default();
+
+		log::trace!(
+			target: "staking-precompile",
+			"{:?} made {} nominations",
+			nominator,
+			nomination_count
+		);
 
-		// Make sure the round number fits in a u32
-		if round_u256.leading_zeros() < 256 - 32 {
+		Self::view_output(
+			SubstrateWeight::<Runtime>::nominator_nomination_count(nomination_count),
+			target_gas,
+			u256_to_solidity_bytes(nomination_count.into()),
+		)
+	}
+
+	fn nomination_amount(
+		input: &[u8],
+		target_gas: Option<u64>,
+	) -> Result<PrecompileOutput, ExitError> {
+		if input.len() != 64 {
 			return Err(ExitError::Other(
-				"Round is too large. 32 bit maximum".into(),
+				"Incorrect input length for nomination_amount".into(),
 			));
 		}
-		let round: u32 = round_u256.low_u32();
+		let nominator = parse_account(&input[..32])?;
+		let collator: Runtime::AccountId = parse_account(&input[32..])?.into();
 
-		log::trace!(target: "staking-precompile", "🥩round is {}", round);
-		// Read the point value and format it for Solidity
-		let points: u32 = parachain_staking::Pallet::<Runtime>::points(round);
-		log::trace!(target: "staking-precompile", "🥩points is {}", points);
-		let mut output = [0u8; 32];
-		U256::from(points).to_big_endian(&mut output);
-		log::trace!(target: "staking-precompile", "🥩output is {:?}", output);
+		// fetch data from pallet. Missing nominations have a zero amount.
+		let state = parachain_staking::Pallet::<Runtime>::nominator_state(
+			Runtime::AccountId::from(nominator),
+		);
+		let nomination_count = Self::nominator_state_size(&state);
+		let amount: U256 = state
+			.and_then(|state| {
+				state
+					.nominations
+					.0
+					.into_iter()
+					.find(|nomination| nomination.owner == collator)
+			})
+			.map(|nomination| nomination.amount.into())
+			.unwrap_or_default();
 
-		let gas_consumed = <Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
-			<Runtime as frame_system::Config>::DbWeight::get().read,
+		log::trace!(
+			target: "staking-precompile",
+			"Nomination of {:?} is {:?}",
+			nominator,
+			amount
+		);
+
+		Self::view_output(
+			SubstrateWeight::<Runtime>::nomination_amount(nomination_count),
+			target_gas,
+			u256_to_solidity_bytes(amount),
+		)
+	}
+
+	/// Number of nominators of a candidate, which sizes its state. None if not a candidate.
+	fn collator_state_size_of(candidate: H160) -> Option<u32> {
+		let state = parachain_staking::Pallet::<Runtime>::collator_state2(
+			Runtime::AccountId::from(candidate),
 		);
+		state.as_ref().map(|_| Self::collator_state_size(&state))
+	}
+
+	/// Number of nominations of a nominator, which sizes its state. None if not a nominator.
+	fn nominator_state_size_of(nominator: H160) -> Option<u32> {
+		let state = parachain_staking::Pallet::<Runtime>::nominator_state(
+			Runtime::AccountId::from(nominator),
+		);
+		state.as_ref().map(|_| Self::nominator_state_size(&state))
+	}
+
+	/// Number of nominators in a candidate state
+	fn collator_state_size(
+		state: &Option<parachain_staking::Collator2<Runtime::AccountId, BalanceOf<Runtime>>>,
+	) -> u32 {
+		state
+			.as_ref()
+			.map(|state| state.nominators.0.len() as u32)
+			.unwrap_or_default()
+	}
+
+	/// Number of nominations in a nominator state
+	fn nominator_state_size(
+		state: &Option<parachain_staking::Nominator<Runtime::AccountId, BalanceOf<Runtime>>>,
+	) -> u32 {
+		state
+			.as_ref()
+			.map(|state| state.nominations.0.len() as u32)
+			.unwrap_or_default()
+	}
+
+	/// Builds the output of a view, charging the gas equivalent of its weight
+	fn view_output(
+		weight: Weight,
+		target_gas: Option<u64>,
+		output: Vec<u8>,
+	) -> Result<PrecompileOutput, ExitError> {
+		let cost = Runtime::GasWeightMapping::weight_to_gas(weight);
+
+		if let Some(gas_limit) = target_gas {
+			if cost > gas_limit {
+				return Err(ExitError::OutOfGas);
+			}
+		}
 
 		Ok(PrecompileOutput {
 			exit_status: ExitSucceed::Returned,
-			cost: gas_consumed,
-			output: output.to_vec(),
+			cost,
+			output,
 			logs: Default::default(),
 		})
 	}
@@ -387,7 +680,7 @@ where
 
 	fn join_candidates(input: &[u8]) -> Result<parachain_staking::Call<Runtime>, ExitError> {
 		let amount = parse_amount::<BalanceOf<Runtime>>(&input[..32])?;
-		let collator_candidate_count = parse_weight_hint(&input[32..])?;
+		let collator_candidate_count = parse_u32(&input[32..])?;
 
 		log::trace!(target: "staking-precompile", "Collator stake amount is {:?}", amount);
 		log::trace!(
@@ -403,7 +696,7 @@ where
 	}
 
 	fn leave_candidates(input: &[u8]) -> Result<parachain_staking::Call<Runtime>, ExitError> {
-		let collator_candidate_count = parse_weight_hint(input)?;
+		let collator_candidate_count = parse_u32(input)?;
 		Ok(parachain_staking::Call::<Runtime>::leave_candidates(
 			collator_candidate_count,
 		))
@@ -442,8 +735,8 @@ where
 		log::trace!(target: "staking-precompile", "input is {:?}", input);
 		let collator = parse_account(&input[..32])?;
 		let amount = parse_amount::<BalanceOf<Runtime>>(&input[32..64])?;
-		let collator_nomination_count = parse_weight_hint(&input[64..96])?;
-		let nominator_nomination_count = parse_weight_hint(&input[96..])?;
+		let collator_nomination_count = parse_u32(&input[64..96])?;
+		let nominator_nomination_count = parse_u32(&input[96..])?;
 
 		log::trace!(target: "staking-precompile", "Collator account is {:?}", collator);
 		log::trace!(target: "staking-precompile", "Nomination amount is {:?}", amount);
@@ -467,7 +760,7 @@ where
 	}
 
 	fn leave_nominators(input: &[u8]) -> Result<parachain_staking::Call<Runtime>, ExitError> {
-		let nomination_count = parse_weight_hint(&input[..])?;
+		let nomination_count = parse_u32(&input[..])?;
 		Ok(parachain_staking::Call::<Runtime>::leave_nominators(
 			nomination_count,
 		))
@@ -509,17 +802,101 @@ where
 			amount,
 		))
 	}
+
+	// Finally the logs. They are built from the inner call ready for dispatch.
+
+	/// Builds the Solidity-style logs describing the given staking call made by `context.caller`.
+	fn logs_for_call(call: &parachain_staking::Call<Runtime>, context: &Context) -> Vec<Log> {
+		use parachain_staking::Call as StakingCall;
+
+		let address = context.address;
+		let caller = context.caller;
+
+		let log = match call {
+			StakingCall::join_candidates(bond, _) => log2(
+				address,
+				EVENT_CANDIDATE_JOINED,
+				caller,
+				u256_to_solidity_bytes((*bond).into()),
+			),
+			StakingCall::leave_candidates(_) => {
+				log2(address, EVENT_CANDIDATE_LEFT, caller, Vec::new())
+			}
+			StakingCall::go_offline() => {
+				log2(address, EVENT_CANDIDATE_WENT_OFFLINE, caller, Vec::new())
+			}
+			StakingCall::go_online() => {
+				log2(address, EVENT_CANDIDATE_BACK_ONLINE, caller, Vec::new())
+			}
+			StakingCall::candidate_bond_more(more) => log2(
+				address,
+				EVENT_CANDIDATE_BONDED_MORE,
+				caller,
+				u256_to_solidity_bytes((*more).into()),
+			),
+			StakingCall::candidate_bond_less(less) => log2(
+				address,
+				EVENT_CANDIDATE_BONDED_LESS,
+				caller,
+				u256_to_solidity_bytes((*less).into()),
+			),
+			StakingCall::nominate(collator, amount, _, _) => log3(
+				address,
+				EVENT_NOMINATED,
+				caller,
+				collator.clone().into(),
+				u256_to_solidity_bytes((*amount).into()),
+			),
+			StakingCall::leave_nominators(_) => {
+				log2(address, EVENT_NOMINATOR_LEFT, caller, Vec::new())
+			}
+			StakingCall::revoke_nomination(collator) => log3(
+				address,
+				EVENT_NOMINATION_REVOKED,
+				caller,
+				collator.clone().into(),
+				Vec::new(),
+			),
+			StakingCall::nominator_bond_more(collator, more) => log3(
+				address,
+				EVENT_NOMINATION_INCREASED,
+				caller,
+				collator.clone().into(),
+				u256_to_solidity_bytes((*more).into()),
+			),
+			StakingCall::nominator_bond_less(collator, less) => log3(
+				address,
+				EVENT_NOMINATION_DECREASED,
+				caller,
+				collator.clone().into(),
+				u256_to_solidity_bytes((*less).into()),
+			),
+			_ => return Vec::new(),
+		};
+
+		sp_std::vec![log]
+	}
 }
 
-// Solidity's bool type is 256 bits as shown by these examples
-// https://docs.soliditylang.org/en/v0.8.0/abi-spec.html
-// This utility function converts a Rust bool into the corresponding Solidity type
-fn bool_to_solidity_bytes(b: bool) -> Vec<u8> {
-	let mut result_bytes = [0u8; 32];
+/// Computes the topic of a Solidity event, which is the keccak256 hash of its signature.
+fn event_topic(signature: &[u8]) -> H256 {
+	sp_io::hashing::keccak_256(signature).into()
+}
 
-	if b {
-		result_bytes[31] = 1;
+/// Builds a log with 2 topics: the event topic followed by an (indexed) address.
+fn log2(address: H160, signature: &[u8], topic1: H160, data: Vec<u8>) -> Log {
+	Log {
+		address,
+		topics: sp_std::vec![event_topic(signature), topic1.into()],
+		data,
 	}
+}
 
-	result_bytes.to_vec()
+/// Builds a log with 3 topics: the event topic followed by two (indexed) addresses.
+fn log3(address: H160, signature: &[u8], topic1: H160, topic2: H160, data: Vec<u8>) -> Log {
+	Log {
+		address,
+		topics: sp_std::vec![event_topic(signature), topic1.into(), topic2.into()],
+		data,
+	}
 }
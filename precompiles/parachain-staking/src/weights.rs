@@ -0,0 +1,154 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for parachain_staking_precompiles
+//!
+//! The views have not been benchmarked on the reference hardware yet. Each of them reads a single
+//! storage value, so they are charged one database read. For the values holding a list (the
+//! nominators of a candidate, the nominations of a nominator or the candidates of a list), the
+//! component `x` is the number of entries, which the benchmarks of `benchmarks.rs` measure the
+//! decoding cost of.
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for parachain_staking_precompiles.
+pub trait WeightInfo {
+	fn is_nominator(x: u32) -> Weight;
+	fn is_candidate(x: u32) -> Weight;
+	fn is_selected_candidate(x: u32) -> Weight;
+	fn min_nomination() -> Weight;
+	fn points() -> Weight;
+	fn awarded_pts() -> Weight;
+	fn round() -> Weight;
+	fn candidate_count(x: u32) -> Weight;
+	fn selected_candidates(x: u32) -> Weight;
+	fn candidate_bond(x: u32) -> Weight;
+	fn candidate_total_backing(x: u32) -> Weight;
+	fn collator_nomination_count(x: u32) -> Weight;
+	fn candidate_exit_round(x: u32) -> Weight;
+	fn nominator_total(x: u32) -> Weight;
+	fn nominator_nomination_count(x: u32) -> Weight;
+	fn nomination_amount(x: u32) -> Weight;
+}
+
+/// Weights for parachain_staking_precompiles, using the database weights of the runtime.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn is_nominator(_x: u32) -> Weight {
+		T::DbWeight::get().reads(1)
+	}
+	fn is_candidate(_x: u32) -> Weight {
+		T::DbWeight::get().reads(1)
+	}
+	fn is_selected_candidate(_x: u32) -> Weight {
+		T::DbWeight::get().reads(1)
+	}
+	fn min_nomination() -> Weight {
+		T::DbWeight::get().reads(1)
+	}
+	fn points() -> Weight {
+		T::DbWeight::get().reads(1)
+	}
+	fn awarded_pts() -> Weight {
+		T::DbWeight::get().reads(1)
+	}
+	fn round() -> Weight {
+		T::DbWeight::get().reads(1)
+	}
+	fn candidate_count(_x: u32) -> Weight {
+		T::DbWeight::get().reads(1)
+	}
+	fn selected_candidates(_x: u32) -> Weight {
+		T::DbWeight::get().reads(1)
+	}
+	fn candidate_bond(_x: u32) -> Weight {
+		T::DbWeight::get().reads(1)
+	}
+	fn candidate_total_backing(_x: u32) -> Weight {
+		T::DbWeight::get().reads(1)
+	}
+	fn collator_nomination_count(_x: u32) -> Weight {
+		T::DbWeight::get().reads(1)
+	}
+	fn candidate_exit_round(_x: u32) -> Weight {
+		T::DbWeight::get().reads(1)
+	}
+	fn nominator_total(_x: u32) -> Weight {
+		T::DbWeight::get().reads(1)
+	}
+	fn nominator_nomination_count(_x: u32) -> Weight {
+		T::DbWeight::get().reads(1)
+	}
+	fn nomination_amount(_x: u32) -> Weight {
+		T::DbWeight::get().reads(1)
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn is_nominator(_x: u32) -> Weight {
+		RocksDbWeight::get().reads(1)
+	}
+	fn is_candidate(_x: u32) -> Weight {
+		RocksDbWeight::get().reads(1)
+	}
+	fn is_selected_candidate(_x: u32) -> Weight {
+		RocksDbWeight::get().reads(1)
+	}
+	fn min_nomination() -> Weight {
+		RocksDbWeight::get().reads(1)
+	}
+	fn points() -> Weight {
+		RocksDbWeight::get().reads(1)
+	}
+	fn awarded_pts() -> Weight {
+		RocksDbWeight::get().reads(1)
+	}
+	fn round() -> Weight {
+		RocksDbWeight::get().reads(1)
+	}
+	fn candidate_count(_x: u32) -> Weight {
+		RocksDbWeight::get().reads(1)
+	}
+	fn selected_candidates(_x: u32) -> Weight {
+		RocksDbWeight::get().reads(1)
+	}
+	fn candidate_bond(_x: u32) -> Weight {
+		RocksDbWeight::get().reads(1)
+	}
+	fn candidate_total_backing(_x: u32) -> Weight {
+		RocksDbWeight::get().reads(1)
+	}
+	fn collator_nomination_count(_x: u32) -> Weight {
+		RocksDbWeight::get().reads(1)
+	}
+	fn candidate_exit_round(_x: u32) -> Weight {
+		RocksDbWeight::get().reads(1)
+	}
+	fn nominator_total(_x: u32) -> Weight {
+		RocksDbWeight::get().reads(1)
+	}
+	fn nominator_nomination_count(_x: u32) -> Weight {
+		RocksDbWeight::get().reads(1)
+	}
+	fn nomination_amount(_x: u32) -> Weight {
+		RocksDbWeight::get().reads(1)
+	}
+}
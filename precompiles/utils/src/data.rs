@@ -0,0 +1,177 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Parsing of the Solidity ABI encoded arguments of the precompiles, and encoding of their
+//! results. See https://docs.soliditylang.org/en/v0.8.0/abi-spec.html
+
+use evm::ExitError;
+use sp_core::{H160, U256};
+use sp_std::convert::{TryFrom, TryInto};
+use sp_std::vec::Vec;
+
+/// Ensures the arguments (without selector) have the exact expected length.
+pub fn check_input_length(input: &[u8], expected: usize) -> Result<(), ExitError> {
+	if input.len() != expected {
+		log::trace!(target: "precompile-utils",
+			"Incorrect input length. Got {} bytes, expected {}",
+			input.len(),
+			expected,
+		);
+		return Err(ExitError::Other("Incorrect input length".into()));
+	}
+
+	Ok(())
+}
+
+/// Parses an H160 account address from a 256 bit (32 byte) buffer. Only the last 20 bytes are used.
+pub fn parse_account(input: &[u8]) -> Result<H160, ExitError> {
+	const PADDING_SIZE_BYTES: usize = 12;
+	const ACCOUNT_SIZE_BYTES: usize = 20;
+	const TOTAL_SIZE_BYTES: usize = PADDING_SIZE_BYTES + ACCOUNT_SIZE_BYTES;
+
+	if input.len() != TOTAL_SIZE_BYTES {
+		log::trace!(target: "precompile-utils",
+			"Unable to parse address. Got {} bytes, expected {}",
+			input.len(),
+			TOTAL_SIZE_BYTES,
+		);
+		return Err(ExitError::Other(
+			"Incorrect input length for account parsing".into(),
+		));
+	}
+
+	Ok(H160::from_slice(
+		&input[PADDING_SIZE_BYTES..TOTAL_SIZE_BYTES],
+	))
+}
+
+/// Parses a uint256 value
+pub fn parse_uint256(input: &[u8]) -> Result<U256, ExitError> {
+	// In solidity all values are encoded to this width
+	const SIZE_BYTES: usize = 32;
+
+	if input.len() != SIZE_BYTES {
+		log::trace!(target: "precompile-utils",
+			"Unable to parse uint256. Got {} bytes, expected {}",
+			input.len(),
+			SIZE_BYTES,
+		);
+		return Err(ExitError::Other(
+			"Incorrect input length for uint256 parsing".into(),
+		));
+	}
+
+	Ok(U256::from_big_endian(&input[0..SIZE_BYTES]))
+}
+
+/// Parses an amount of ether from a 256 bit (32 byte) slice. The balance type is generic.
+pub fn parse_amount<Balance: TryFrom<U256>>(input: &[u8]) -> Result<Balance, ExitError> {
+	parse_uint256(input)?
+		.try_into()
+		.map_err(|_| ExitError::Other("Amount is too large for provided balance type".into()))
+}
+
+/// Parses a u32 (e.g. an index) from a 256 bit (32 byte) slice.
+pub fn parse_u32(input: &[u8]) -> Result<u32, ExitError> {
+	parse_uint256(input)?
+		.try_into()
+		.map_err(|_| ExitError::Other("Value is too large for u32".into()))
+}
+
+/// Parses a solidity bool, which is a 256 bit (32 byte) value of either 0 or 1.
+pub fn parse_bool(input: &[u8]) -> Result<bool, ExitError> {
+	match parse_uint256(input)? {
+		value if value.is_zero() => Ok(false),
+		value if value == U256::one() => Ok(true),
+		_ => Err(ExitError::Other("Incorrect value for bool parsing".into())),
+	}
+}
+
+/// Parses a solidity dynamic `bytes` argument when it is the only argument of the call:
+/// the offset of the data, followed by its length and the data right-padded to 32 bytes.
+pub fn parse_bytes(input: &[u8]) -> Result<Vec<u8>, ExitError> {
+	if input.len() < 64 {
+		return Err(ExitError::Other(
+			"Incorrect input length for bytes parsing".into(),
+		));
+	}
+
+	let offset: usize = parse_uint256(&input[..32])?
+		.try_into()
+		.map_err(|_| ExitError::Other("Bytes offset is too large".into()))?;
+	let length_end = offset
+		.checked_add(32)
+		.filter(|end| *end <= input.len())
+		.ok_or_else(|| ExitError::Other("Bytes offset is out of bounds".into()))?;
+	let length: usize = parse_uint256(&input[offset..length_end])?
+		.try_into()
+		.map_err(|_| ExitError::Other("Bytes length is too large".into()))?;
+	let data_end = length_end
+		.checked_add(length)
+		.filter(|end| *end <= input.len())
+		.ok_or_else(|| ExitError::Other("Bytes length is out of bounds".into()))?;
+
+	Ok(input[length_end..data_end].to_vec())
+}
+
+// Solidity's bool type is 256 bits as shown by these examples
+// https://docs.soliditylang.org/en/v0.8.0/abi-spec.html
+// This utility function converts a Rust bool into the corresponding Solidity type
+pub fn bool_to_solidity_bytes(b: bool) -> Vec<u8> {
+	let mut result_bytes = [0u8; 32];
+
+	if b {
+		result_bytes[31] = 1;
+	}
+
+	result_bytes.to_vec()
+}
+
+/// Converts a U256 into the corresponding Solidity uint256.
+pub fn u256_to_solidity_bytes(value: U256) -> Vec<u8> {
+	let mut buffer = [0u8; 32];
+	value.to_big_endian(&mut buffer);
+	buffer.to_vec()
+}
+
+// Solidity's string type is dynamic: the return data contains the offset of the string, then
+// its length in bytes, and finally the UTF-8 data right-padded to a multiple of 32 bytes.
+pub fn string_to_solidity_bytes(s: &str) -> Vec<u8> {
+	let mut output = u256_to_solidity_bytes(32.into());
+	output.extend_from_slice(&u256_to_solidity_bytes(s.len().into()));
+
+	let mut data = s.as_bytes().to_vec();
+	data.resize((s.len() + 31) / 32 * 32, 0);
+	output.extend_from_slice(&data);
+
+	output
+}
+
+/// Converts a list of accounts into the corresponding Solidity address[]. Dynamic arrays are
+/// encoded as the offset of their content, their length and then their elements.
+pub fn accounts_to_solidity_bytes(accounts: &[H160]) -> Vec<u8> {
+	let mut output = Vec::with_capacity(32 * (2 + accounts.len()));
+	output.extend_from_slice(&u256_to_solidity_bytes(32.into()));
+	output.extend_from_slice(&u256_to_solidity_bytes(accounts.len().into()));
+
+	for account in accounts {
+		let mut word = [0u8; 32];
+		word[12..32].copy_from_slice(account.as_bytes());
+		output.extend_from_slice(&word);
+	}
+
+	output
+}
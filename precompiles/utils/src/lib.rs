@@ -0,0 +1,90 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Utilities shared by the precompiles wrapping runtime pallets
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod data;
+
+pub use data::*;
+
+use alloc::{format, string::String};
+use evm::ExitError;
+use frame_support::traits::PalletInfo;
+use sp_runtime::DispatchError;
+
+/// Describes a `DispatchError` as `<Pallet>::<Error>` when it comes from a pallet, so that EVM
+/// callers can tell which check made the dispatched call fail.
+///
+/// Only the runtime type of a pallet gives access to its name, so module errors are named after
+/// `Pallet` (usually the pallet wrapped by the precompile) when they come from it, and after their
+/// pallet index otherwise.
+pub fn dispatch_error_message<Runtime, Pallet>(error: DispatchError) -> String
+where
+	Runtime: frame_system::Config,
+	Pallet: 'static,
+{
+	match error {
+		DispatchError::Module {
+			index,
+			error,
+			message,
+		} => {
+			let pallet = match <Runtime::PalletInfo as PalletInfo>::index::<Pallet>() {
+				Some(pallet_index) if pallet_index == index as usize => {
+					<Runtime::PalletInfo as PalletInfo>::name::<Pallet>().map(String::from)
+				}
+				_ => None,
+			}
+			.unwrap_or_else(|| format!("Pallet{}", index));
+
+			match message {
+				Some(message) => format!("{}::{}", pallet, message),
+				None => format!("{}::Error{}", pallet, error),
+			}
+		}
+		error => String::from(<&'static str>::from(error)),
+	}
+}
+
+/// Builds the error returned by a precompile when the call it dispatched failed. The message
+/// starts with `context` and ends with the description of the `DispatchError`, e.g.
+/// "Parachain staking call via EVM failed: ParachainStaking::NomBondBelowMin".
+///
+/// Reverting with ABI-encoded `Error(string)` data is not possible with evm 0.27: a precompile
+/// returns `Result<PrecompileOutput, ExitError>`, where the output can only carry an
+/// `ExitSucceed` status and the error no data. The executor turns the error into an
+/// `ExitReason::Error` with empty return data, so Solidity callers can't decode a reason and only
+/// see the call fail. The message is therefore carried by `ExitError::Other`, which the RPC
+/// reports in the `eth_call` and `eth_estimateGas` errors. Reverting with data needs the
+/// `PrecompileFailure::Revert` of later evm versions.
+pub fn dispatch_error<Runtime, Pallet>(context: &str, error: DispatchError) -> ExitError
+where
+	Runtime: frame_system::Config,
+	Pallet: 'static,
+{
+	ExitError::Other(
+		format!(
+			"{}: {}",
+			context,
+			dispatch_error_message::<Runtime, Pallet>(error)
+		)
+		.into(),
+	)
+}
@@ -0,0 +1,477 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! EIP-712 typed data representation of a signed extrinsic payload.
+//!
+//! The payload of a signed extrinsic is the SCALE encoding of `(call, extra, additional_signed)`.
+//! With the `SignedExtra` shared by the Moonbeam runtimes, `extra` is `(era, Compact(nonce),
+//! Compact(tip))` and `additional_signed` is `(spec_version, transaction_version, genesis_hash,
+//! block_hash)`. This module reads the payload as such and exposes it to wallets as the
+//! `SubstrateTransaction` typed data, so that its fields are shown to the user when signing.
+//!
+//! The call is read with the runtime `Call` type, see [`SubstrateTransaction::decode`]. The
+//! runtime gives a struct type to the staking and governance calls through [`Eip712Call`], so
+//! that wallets also show their arguments, e.g. the collator and amount of a nomination. The
+//! other calls are signed as their SCALE encoding, in a `bytes` member.
+//!
+//! The domain includes the EIP-155 chain id of the runtime, so that a signature made for one
+//! Moonbeam network is not valid on another one.
+//!
+//! Payloads longer than 256 bytes are hashed with Blake2-256 before being signed. The hash can't
+//! be read back as typed data, so such extrinsics, e.g. with a large call, can't be signed with
+//! EIP-712 and must use a raw signature. The calls with a struct type have small arguments, and
+//! their payloads stay below this limit.
+
+use parity_scale_codec::{Compact, Decode, Encode};
+use sha3::{Digest, Keccak256};
+use sp_core::{H160, H256, U256};
+use sp_runtime::generic::Era;
+use sp_std::vec::Vec;
+
+/// Name of the EIP-712 domain of Moonbeam transactions.
+pub const DOMAIN_NAME: &str = "Moonbeam";
+/// Version of the EIP-712 domain of Moonbeam transactions.
+pub const DOMAIN_VERSION: &str = "1";
+
+const DOMAIN_TYPE: &[u8] = b"EIP712Domain(string name,string version,uint256 chainId)";
+/// The members of `SubstrateTransaction` following its `call`.
+const TRANSACTION_MEMBERS: &[u8] = b"uint32 nonce,uint64 eraPeriod,uint64 eraPhase,uint128 tip,\
+bytes32 genesisHash,uint32 specVersion,uint32 transactionVersion,bytes32 blockHash";
+
+/// The runtime `Call`, as read from the payloads signed as EIP-712 typed data.
+pub trait Eip712Call: Decode {
+	/// The EIP-155 chain id of the runtime, part of the EIP-712 domain.
+	fn chain_id() -> u64;
+
+	/// The call as a struct, or `None` to sign it as its SCALE encoding.
+	///
+	/// The struct must have all the arguments of the call as members, and its name must identify
+	/// the call, so that a signature can't authorize another call with the same struct.
+	fn typed_call(&self) -> Option<TypedCall>;
+}
+
+/// A call signed as an EIP-712 struct.
+#[derive(Clone, Eq, PartialEq, sp_core::RuntimeDebug)]
+pub struct TypedCall {
+	/// Name of the struct type, e.g. `Nominate`.
+	pub name: &'static str,
+	pub members: Vec<Member>,
+}
+
+/// A member of a [`TypedCall`], of an atomic Solidity type.
+#[derive(Clone, Eq, PartialEq, sp_core::RuntimeDebug)]
+pub struct Member {
+	/// Solidity type of the member, e.g. `uint256`.
+	pub ty: &'static str,
+	pub name: &'static str,
+	/// The value, encoded as a 32 bytes word.
+	pub value: [u8; 32],
+}
+
+impl Member {
+	pub fn address(name: &'static str, value: H160) -> Self {
+		Member {
+			ty: "address",
+			name,
+			value: H256::from(value).0,
+		}
+	}
+
+	pub fn bool(name: &'static str, value: bool) -> Self {
+		Member::uint("bool", name, (value as u8).into())
+	}
+
+	pub fn bytes32(name: &'static str, value: H256) -> Self {
+		Member {
+			ty: "bytes32",
+			name,
+			value: value.0,
+		}
+	}
+
+	/// A member of the unsigned integer type `ty`, e.g. `uint32`.
+	pub fn uint(ty: &'static str, name: &'static str, value: U256) -> Self {
+		Member {
+			ty,
+			name,
+			value: word(value),
+		}
+	}
+}
+
+impl TypedCall {
+	pub fn new(name: &'static str, members: Vec<Member>) -> Self {
+		TypedCall { name, members }
+	}
+
+	/// The EIP-712 `encodeType` of the struct: `Name(type1 name1,type2 name2,...)`.
+	fn encode_type(&self) -> Vec<u8> {
+		let mut encoded = self.name.as_bytes().to_vec();
+		encoded.push(b'(');
+		for (index, member) in self.members.iter().enumerate() {
+			if index > 0 {
+				encoded.push(b',');
+			}
+			encoded.extend_from_slice(member.ty.as_bytes());
+			encoded.push(b' ');
+			encoded.extend_from_slice(member.name.as_bytes());
+		}
+		encoded.push(b')');
+		encoded
+	}
+
+	/// The EIP-712 `hashStruct` of the call.
+	pub fn hash_struct(&self) -> H256 {
+		let mut encoded = Vec::with_capacity(32 * (1 + self.members.len()));
+		encoded.extend_from_slice(&keccak_256(&self.encode_type()));
+		for member in &self.members {
+			encoded.extend_from_slice(&member.value);
+		}
+		H256(keccak_256(&encoded))
+	}
+}
+
+/// The fields of a signed extrinsic payload, as signed in EIP-712 typed data.
+#[derive(Clone, Eq, PartialEq, sp_core::RuntimeDebug)]
+pub struct SubstrateTransaction {
+	/// The SCALE encoded call.
+	pub call: Vec<u8>,
+	/// The call as a struct, signed instead of its encoding when it has one.
+	pub typed_call: Option<TypedCall>,
+	pub nonce: u32,
+	pub era: Era,
+	pub tip: u128,
+	pub genesis_hash: H256,
+	pub spec_version: u32,
+	pub transaction_version: u32,
+	/// Hash of the block the era starts at, the genesis hash for immortal transactions.
+	pub block_hash: H256,
+}
+
+impl SubstrateTransaction {
+	/// The transaction whose payload is `payload`, for an extrinsic dispatching a `Call`.
+	///
+	/// The call isn't length prefixed, so it is read as a `Call` to know where it ends, the way
+	/// the runtime decodes the extrinsic. Reading it from the end instead could find several
+	/// splits between the call and the extra, and let a signature over one of them authorize
+	/// another. Payloads longer than 256 bytes are hashed before being signed, they can't be read
+	/// back and are never decoded.
+	pub fn decode<Call: Eip712Call>(payload: &[u8]) -> Option<Self> {
+		let mut input = payload;
+		let typed_call = Call::decode(&mut input).ok()?.typed_call();
+		let call = payload[..payload.len() - input.len()].to_vec();
+		let (era, nonce, tip) = <(Era, Compact<u32>, Compact<u128>)>::decode(&mut input).ok()?;
+		let (spec_version, transaction_version, genesis_hash, block_hash) =
+			<(u32, u32, H256, H256)>::decode(&mut input).ok()?;
+		if !input.is_empty() {
+			return None;
+		}
+
+		Some(SubstrateTransaction {
+			call,
+			typed_call,
+			nonce: nonce.0,
+			era,
+			tip: tip.0,
+			genesis_hash,
+			spec_version,
+			transaction_version,
+			block_hash,
+		})
+	}
+
+	/// The signed payload of the transaction.
+	pub fn payload(&self) -> Vec<u8> {
+		let mut payload = self.call.clone();
+		(self.era, Compact(self.nonce), Compact(self.tip)).encode_to(&mut payload);
+		(
+			self.spec_version,
+			self.transaction_version,
+			self.genesis_hash,
+			self.block_hash,
+		)
+			.encode_to(&mut payload);
+		payload
+	}
+
+	/// The EIP-712 `encodeType` of the transaction. The type of its `call` member is the struct
+	/// of the call, whose definition follows, or `bytes`.
+	fn encode_type(&self) -> Vec<u8> {
+		let mut encoded = b"SubstrateTransaction(".to_vec();
+		match &self.typed_call {
+			Some(typed_call) => encoded.extend_from_slice(typed_call.name.as_bytes()),
+			None => encoded.extend_from_slice(b"bytes"),
+		}
+		encoded.extend_from_slice(b" call,");
+		encoded.extend_from_slice(TRANSACTION_MEMBERS);
+		encoded.push(b')');
+		if let Some(typed_call) = &self.typed_call {
+			encoded.extend_from_slice(&typed_call.encode_type());
+		}
+		encoded
+	}
+
+	/// The EIP-712 `hashStruct` of the transaction.
+	pub fn hash_struct(&self) -> H256 {
+		let (era_period, era_phase) = match self.era {
+			Era::Immortal => (0, 0),
+			Era::Mortal(period, phase) => (period, phase),
+		};
+		let call = match &self.typed_call {
+			Some(typed_call) => typed_call.hash_struct().0,
+			None => keccak_256(&self.call),
+		};
+
+		let mut encoded = Vec::with_capacity(32 * 10);
+		encoded.extend_from_slice(&keccak_256(&self.encode_type()));
+		encoded.extend_from_slice(&call);
+		encoded.extend_from_slice(&word(self.nonce.into()));
+		encoded.extend_from_slice(&word(era_period.into()));
+		encoded.extend_from_slice(&word(era_phase.into()));
+		encoded.extend_from_slice(&word(self.tip.into()));
+		encoded.extend_from_slice(self.genesis_hash.as_bytes());
+		encoded.extend_from_slice(&word(self.spec_version.into()));
+		encoded.extend_from_slice(&word(self.transaction_version.into()));
+		encoded.extend_from_slice(self.block_hash.as_bytes());
+		H256(keccak_256(&encoded))
+	}
+
+	/// The hash signed by the wallet on the chain `chain_id`:
+	/// `keccak256("\x19\x01" ‖ domainSeparator ‖ hashStruct)`.
+	pub fn signing_hash(&self, chain_id: u64) -> [u8; 32] {
+		let mut encoded = Vec::with_capacity(2 + 32 + 32);
+		encoded.extend_from_slice(&[0x19, 0x01]);
+		encoded.extend_from_slice(domain_separator(chain_id).as_bytes());
+		encoded.extend_from_slice(self.hash_struct().as_bytes());
+		keccak_256(&encoded)
+	}
+}
+
+/// The EIP-712 domain separator of Moonbeam transactions on the chain `chain_id`.
+pub fn domain_separator(chain_id: u64) -> H256 {
+	let mut encoded = Vec::with_capacity(32 * 4);
+	encoded.extend_from_slice(&keccak_256(DOMAIN_TYPE));
+	encoded.extend_from_slice(&keccak_256(DOMAIN_NAME.as_bytes()));
+	encoded.extend_from_slice(&keccak_256(DOMAIN_VERSION.as_bytes()));
+	encoded.extend_from_slice(&word(chain_id.into()));
+	H256(keccak_256(&encoded))
+}
+
+fn keccak_256(data: &[u8]) -> [u8; 32] {
+	let mut hash = [0u8; 32];
+	hash.copy_from_slice(Keccak256::digest(data).as_slice());
+	hash
+}
+
+fn word(value: U256) -> [u8; 32] {
+	let mut word = [0u8; 32];
+	value.to_big_endian(&mut word);
+	word
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+	use super::*;
+
+	/// A call made of a pallet index, a call index and a SCALE encoded argument.
+	pub(crate) type TestCall = (u8, u8, Vec<u8>);
+
+	pub(crate) const CHAIN_ID: u64 = 1281;
+	/// The pallet whose calls are signed as a `Remark` struct.
+	const TYPED_PALLET: u8 = 9;
+
+	impl Eip712Call for TestCall {
+		fn chain_id() -> u64 {
+			CHAIN_ID
+		}
+
+		fn typed_call(&self) -> Option<TypedCall> {
+			if self.0 != TYPED_PALLET {
+				return None;
+			}
+			Some(TypedCall::new(
+				"Remark",
+				vec![
+					Member::uint("uint8", "callIndex", self.1.into()),
+					Member::bytes32("remarkHash", H256(keccak_256(&self.2))),
+				],
+			))
+		}
+	}
+
+	pub(crate) fn transaction() -> SubstrateTransaction {
+		SubstrateTransaction {
+			call: (7u8, 3u8, vec![1u8, 2, 3]).encode(),
+			typed_call: None,
+			nonce: 7,
+			era: Era::mortal(64, 1_000),
+			tip: 1_000_000,
+			genesis_hash: H256::repeat_byte(1),
+			spec_version: 156,
+			transaction_version: 2,
+			block_hash: H256::repeat_byte(2),
+		}
+	}
+
+	#[test]
+	fn decodes_the_signed_payload() {
+		let transaction = transaction();
+		assert_eq!(
+			SubstrateTransaction::decode::<TestCall>(&transaction.payload()),
+			Some(transaction)
+		);
+	}
+
+	#[test]
+	fn decodes_typed_calls() {
+		let call: TestCall = (TYPED_PALLET, 3, vec![1, 2, 3]);
+		let transaction = SubstrateTransaction {
+			call: call.encode(),
+			typed_call: call.typed_call(),
+			..transaction()
+		};
+		assert!(transaction.typed_call.is_some());
+		assert_eq!(
+			SubstrateTransaction::decode::<TestCall>(&transaction.payload()),
+			Some(transaction)
+		);
+	}
+
+	#[test]
+	fn typed_calls_are_defined_after_the_transaction() {
+		let call: TestCall = (TYPED_PALLET, 3, vec![1, 2, 3]);
+		let transaction = SubstrateTransaction {
+			call: call.encode(),
+			typed_call: call.typed_call(),
+			..transaction()
+		};
+		assert_eq!(
+			&transaction.encode_type()[..],
+			&b"SubstrateTransaction(Remark call,uint32 nonce,uint64 eraPeriod,uint64 eraPhase,\
+uint128 tip,bytes32 genesisHash,uint32 specVersion,uint32 transactionVersion,\
+bytes32 blockHash)Remark(uint8 callIndex,bytes32 remarkHash)"[..]
+		);
+	}
+
+	#[test]
+	fn decodes_immortal_transactions() {
+		let transaction = SubstrateTransaction {
+			era: Era::Immortal,
+			block_hash: H256::repeat_byte(1),
+			..transaction()
+		};
+		assert_eq!(
+			SubstrateTransaction::decode::<TestCall>(&transaction.payload()),
+			Some(transaction)
+		);
+	}
+
+	#[test]
+	fn splits_the_call_where_the_runtime_does() {
+		// The payload also reads as a shorter call followed by a mortal era, and as a longer
+		// call followed by another era, nonce and tip.
+		let transaction = SubstrateTransaction {
+			era: Era::Immortal,
+			..transaction()
+		};
+		let payload = transaction.payload();
+		let shorter_call = SubstrateTransaction {
+			call: transaction.call[..transaction.call.len() - 1].to_vec(),
+			era: Era::Mortal(16, 0),
+			..transaction.clone()
+		};
+		assert_eq!(shorter_call.payload(), payload);
+
+		assert_eq!(
+			SubstrateTransaction::decode::<TestCall>(&payload),
+			Some(transaction)
+		);
+	}
+
+	#[test]
+	fn rejects_truncated_payloads() {
+		let payload = transaction().payload();
+		assert_eq!(
+			SubstrateTransaction::decode::<TestCall>(&payload[..payload.len() - 1]),
+			None
+		);
+	}
+
+	#[test]
+	fn rejects_trailing_bytes() {
+		let mut payload = transaction().payload();
+		payload.push(0);
+		assert_eq!(SubstrateTransaction::decode::<TestCall>(&payload), None);
+	}
+
+	#[test]
+	fn signing_hash_depends_on_every_field() {
+		let transaction = transaction();
+		let variants = [
+			SubstrateTransaction {
+				call: (7u8, 3u8, vec![1u8, 2, 4]).encode(),
+				..transaction.clone()
+			},
+			SubstrateTransaction {
+				typed_call: (TYPED_PALLET, 3u8, vec![1u8, 2, 3]).typed_call(),
+				..transaction.clone()
+			},
+			SubstrateTransaction {
+				nonce: 8,
+				..transaction.clone()
+			},
+			SubstrateTransaction {
+				era: Era::Immortal,
+				..transaction.clone()
+			},
+			SubstrateTransaction {
+				tip: 0,
+				..transaction.clone()
+			},
+			SubstrateTransaction {
+				genesis_hash: H256::repeat_byte(3),
+				..transaction.clone()
+			},
+			SubstrateTransaction {
+				spec_version: 157,
+				..transaction.clone()
+			},
+			SubstrateTransaction {
+				transaction_version: 3,
+				..transaction.clone()
+			},
+			SubstrateTransaction {
+				block_hash: H256::repeat_byte(3),
+				..transaction.clone()
+			},
+		];
+		for variant in variants.iter() {
+			assert_ne!(
+				variant.signing_hash(CHAIN_ID),
+				transaction.signing_hash(CHAIN_ID)
+			);
+		}
+	}
+
+	#[test]
+	fn signing_hash_depends_on_the_chain_id() {
+		let transaction = transaction();
+		assert_ne!(
+			transaction.signing_hash(CHAIN_ID + 1),
+			transaction.signing_hash(CHAIN_ID)
+		);
+	}
+}
@@ -16,17 +16,24 @@
 
 //! The Ethereum Signature implementation.
 //!
-//! It includes the Verify and IdentifyAccount traits for the AccountId20
+//! It includes the Verify and IdentifyAccount traits for the AccountId20, and the
+//! EthereumMultiSignature used to sign extrinsics either over their raw payload or over its
+//! EIP-712 typed data representation.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod eip712;
+
 use parity_scale_codec::{Decode, Encode};
 use sha3::{Digest, Keccak256};
 use sp_core::{ecdsa, H160, H256};
+use sp_std::marker::PhantomData;
 
 #[cfg(feature = "std")]
 pub use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
+/// Signature of either the Keccak-256 hash of a message, or of the message as signed by
+/// `personal_sign` for wallets that can't sign raw hashes.
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Eq, PartialEq, Clone, Encode, Decode, sp_core::RuntimeDebug)]
 pub struct EthereumSignature(ecdsa::Signature);
@@ -37,12 +44,10 @@ impl From<ecdsa::Signature> for EthereumSignature {
 	}
 }
 
-impl sp_runtime::traits::Verify for EthereumSignature {
-	type Signer = EthereumSigner;
-	fn verify<L: sp_runtime::traits::Lazy<[u8]>>(&self, mut msg: L, signer: &H160) -> bool {
-		let mut m = [0u8; 32];
-		m.copy_from_slice(Keccak256::digest(msg.get()).as_slice());
-		match sp_io::crypto::secp256k1_ecdsa_recover(self.0.as_ref(), &m) {
+impl EthereumSignature {
+	/// Whether the signature of `hash` was made by `signer`.
+	fn verify_hash(&self, hash: &[u8; 32], signer: &H160) -> bool {
+		match sp_io::crypto::secp256k1_ecdsa_recover(self.0.as_ref(), hash) {
 			Ok(pubkey) => {
 				// TODO This conversion could use a comment. Why H256 first, then H160?
 				H160::from(H256::from_slice(Keccak256::digest(&pubkey).as_slice())) == *signer
@@ -63,6 +68,101 @@ impl sp_runtime::traits::Verify for EthereumSignature {
 	}
 }
 
+impl sp_runtime::traits::Verify for EthereumSignature {
+	type Signer = EthereumSigner;
+	fn verify<L: sp_runtime::traits::Lazy<[u8]>>(&self, mut msg: L, signer: &H160) -> bool {
+		let msg = msg.get();
+		let mut m = [0u8; 32];
+		m.copy_from_slice(Keccak256::digest(msg).as_slice());
+		self.verify_hash(&m, signer) || self.verify_hash(&personal_sign_hash(msg), signer)
+	}
+}
+
+/// Hash of `message` as signed by `personal_sign` (EIP-191 version `0x45`):
+/// `keccak256("\x19Ethereum Signed Message:\n" ‖ len(message) ‖ message)`.
+pub fn personal_sign_hash(message: &[u8]) -> [u8; 32] {
+	// Decimal representation of the message length
+	let mut length = [0u8; 20];
+	let mut start = length.len();
+	let mut remaining = message.len();
+	loop {
+		start -= 1;
+		length[start] = b'0' + (remaining % 10) as u8;
+		remaining /= 10;
+		if remaining == 0 {
+			break;
+		}
+	}
+
+	let mut hash = [0u8; 32];
+	hash.copy_from_slice(
+		Keccak256::new()
+			.chain(b"\x19Ethereum Signed Message:\n")
+			.chain(&length[start..])
+			.chain(message)
+			.result()
+			.as_slice(),
+	);
+	hash
+}
+
+/// Signature of an extrinsic dispatching a `Call`, with the scheme used to sign its payload.
+///
+/// `Call` is only used to read the payload of EIP-712 signatures and isn't encoded.
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(bound = ""))]
+#[derive(Eq, PartialEq, Clone, Encode, Decode, sp_core::RuntimeDebug)]
+pub enum EthereumMultiSignature<Call> {
+	/// Signature of the Keccak-256 hash of the payload.
+	Raw(EthereumSignature),
+	/// Signature of the payload as EIP-712 typed data, see [`eip712`].
+	///
+	/// Only payloads of at most 256 bytes can be signed this way, longer ones are hashed with
+	/// Blake2-256 before being signed and must use `Raw`.
+	Eip712(
+		EthereumSignature,
+		#[cfg_attr(feature = "std", serde(skip))] PhantomData<Call>,
+	),
+}
+
+impl<Call> EthereumMultiSignature<Call> {
+	/// The EIP-712 signature `signature`.
+	pub fn eip712(signature: EthereumSignature) -> Self {
+		EthereumMultiSignature::Eip712(signature, PhantomData)
+	}
+}
+
+impl<Call> From<EthereumSignature> for EthereumMultiSignature<Call> {
+	fn from(x: EthereumSignature) -> Self {
+		EthereumMultiSignature::Raw(x)
+	}
+}
+
+impl<Call> From<ecdsa::Signature> for EthereumMultiSignature<Call> {
+	fn from(x: ecdsa::Signature) -> Self {
+		EthereumMultiSignature::Raw(x.into())
+	}
+}
+
+impl<Call: eip712::Eip712Call> sp_runtime::traits::Verify for EthereumMultiSignature<Call> {
+	type Signer = EthereumSigner;
+	fn verify<L: sp_runtime::traits::Lazy<[u8]>>(&self, mut msg: L, signer: &H160) -> bool {
+		match self {
+			EthereumMultiSignature::Raw(signature) => {
+				sp_runtime::traits::Verify::verify(signature, msg, signer)
+			}
+			EthereumMultiSignature::Eip712(signature, _) => {
+				match eip712::SubstrateTransaction::decode::<Call>(msg.get()) {
+					Some(transaction) => {
+						signature.verify_hash(&transaction.signing_hash(Call::chain_id()), signer)
+					}
+					None => false,
+				}
+			}
+		}
+	}
+}
+
 /// Public key for an Ethereum / H160 compatible account
 #[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Encode, Decode, sp_core::RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
@@ -114,7 +214,22 @@ impl std::fmt::Display for EthereumSigner {
 mod tests {
 	use super::*;
 	use sp_core::{ecdsa, Pair};
-	use sp_runtime::traits::IdentifyAccount;
+	use sp_runtime::traits::{IdentifyAccount, Verify};
+
+	type MultiSignature = EthereumMultiSignature<eip712::tests::TestCall>;
+
+	fn sign(secret_key: &secp256k1::SecretKey, hash: [u8; 32]) -> EthereumSignature {
+		let (signature, recovery_id) =
+			secp256k1::sign(&secp256k1::Message::parse(&hash), secret_key);
+		let mut raw = [0u8; 65];
+		raw[..64].copy_from_slice(&signature.serialize());
+		raw[64] = recovery_id.serialize();
+		ecdsa::Signature::from_raw(raw).into()
+	}
+
+	fn signer(secret_key: &secp256k1::SecretKey) -> H160 {
+		EthereumSigner::from(secp256k1::PublicKey::from_secret_key(secret_key)).into_account()
+	}
 
 	#[test]
 	fn test_account_derivation_1() {
@@ -142,4 +257,133 @@ mod tests {
 		let expected_account = H160::from_slice(&expected_hex_account);
 		assert_eq!(account.into_account(), expected_account);
 	}
+
+	#[test]
+	fn eip712_signature_is_verified() {
+		let secret_key = secp256k1::SecretKey::parse(&[1u8; 32]).unwrap();
+		let transaction = eip712::tests::transaction();
+		let payload = transaction.payload();
+		let signature = MultiSignature::eip712(sign(
+			&secret_key,
+			transaction.signing_hash(eip712::tests::CHAIN_ID),
+		));
+
+		assert!(signature.verify(&payload[..], &signer(&secret_key)));
+		assert!(!signature.verify(&payload[..], &H160::repeat_byte(1)));
+	}
+
+	#[test]
+	fn eip712_signature_is_bound_to_the_payload() {
+		let secret_key = secp256k1::SecretKey::parse(&[1u8; 32]).unwrap();
+		let transaction = eip712::tests::transaction();
+		let payload = transaction.payload();
+		let signature = MultiSignature::eip712(sign(
+			&secret_key,
+			transaction.signing_hash(eip712::tests::CHAIN_ID),
+		));
+
+		let mut other_payload = payload.clone();
+		other_payload[1] = 1;
+		assert!(!signature.verify(&other_payload[..], &signer(&secret_key)));
+	}
+
+	#[test]
+	fn eip712_signature_of_another_call_split_is_rejected() {
+		let secret_key = secp256k1::SecretKey::parse(&[1u8; 32]).unwrap();
+		let transaction = eip712::SubstrateTransaction {
+			era: sp_runtime::generic::Era::Immortal,
+			..eip712::tests::transaction()
+		};
+		let shorter_call = eip712::SubstrateTransaction {
+			call: transaction.call[..transaction.call.len() - 1].to_vec(),
+			era: sp_runtime::generic::Era::Mortal(16, 0),
+			..transaction.clone()
+		};
+		let payload = transaction.payload();
+		assert_eq!(shorter_call.payload(), payload);
+
+		let signature = MultiSignature::eip712(sign(
+			&secret_key,
+			shorter_call.signing_hash(eip712::tests::CHAIN_ID),
+		));
+		assert!(!signature.verify(&payload[..], &signer(&secret_key)));
+	}
+
+	#[test]
+	fn signature_schemes_are_not_interchangeable() {
+		let secret_key = secp256k1::SecretKey::parse(&[1u8; 32]).unwrap();
+		let payload = eip712::tests::transaction().payload();
+		let mut hash = [0u8; 32];
+		hash.copy_from_slice(Keccak256::digest(&payload).as_slice());
+		let signature = sign(&secret_key, hash);
+
+		assert!(MultiSignature::Raw(signature.clone()).verify(&payload[..], &signer(&secret_key)));
+		assert!(!MultiSignature::eip712(signature).verify(&payload[..], &signer(&secret_key)));
+	}
+
+	// Signatures produced by ethers.js `Wallet.signMessage`, which signs deterministically
+	// (RFC 6979), with the private key of Alith.
+	const ALITH_SECRET_KEY: &str =
+		"5fb92d6e98884f76de468fa3f6278f8807c48bebc13595d45af5bdc4da702133";
+	const ALITH: &str = "f24ff3a9cf04c71dbc94d0b566f7a27b94566cac";
+
+	fn ethers_signature(signature: &str) -> EthereumSignature {
+		let mut raw = [0u8; 65];
+		raw.copy_from_slice(&hex::decode(signature).unwrap());
+		ecdsa::Signature::from_raw(raw).into()
+	}
+
+	fn alith() -> H160 {
+		H160::from_slice(&hex::decode(ALITH).unwrap())
+	}
+
+	#[test]
+	fn personal_sign_signature_of_text_is_verified() {
+		let signature = ethers_signature(
+			"44233677bfabd683a1eed3a5043521b3107a845e849811ab3d04b60fdded54c0\
+			58bc30ee945456c8a54e4bf3c42da09d13bafa0c9b605cfda7088cc526b7c6dc1b",
+		);
+
+		assert!(signature.verify(&b"Hello World"[..], &alith()));
+		assert!(!signature.verify(&b"Hello World!"[..], &alith()));
+	}
+
+	#[test]
+	fn personal_sign_signature_of_empty_message_is_verified() {
+		let signature = ethers_signature(
+			"27c7e1ff1311d402a0a3191fc1890dc0bcab1ce8413d9c23afd7ea3ccf6fcdbf\
+			2b392c0e18ba0a854dc25027d5afd54a475ff4142d93a487438320c06f321d361b",
+		);
+
+		assert!(signature.verify(&b""[..], &alith()));
+	}
+
+	#[test]
+	fn personal_sign_signature_of_payload_is_verified() {
+		// Bytes are signed as `signMessage(arrayify(payload))`
+		let payload: Vec<u8> = (1..=40).collect();
+		let signature = ethers_signature(
+			"aa315120a4f86d08c66209a6e6f59e08f45945c06e44f1096357475c4b8dcd2e\
+			3c64fcb13a3ef27d23d128e4074ad00e500b2286ec1e44577a8caedbc4cf24ba1b",
+		);
+
+		assert!(signature.verify(&payload[..], &alith()));
+		assert!(MultiSignature::Raw(signature).verify(&payload[..], &alith()));
+	}
+
+	#[test]
+	fn personal_sign_hash_prefixes_the_length() {
+		let secret_key =
+			secp256k1::SecretKey::parse_slice(&hex::decode(ALITH_SECRET_KEY).unwrap()).unwrap();
+		assert_eq!(signer(&secret_key), alith());
+
+		let message = [7u8; 1234];
+		let mut hash = [0u8; 32];
+		hash.copy_from_slice(
+			Keccak256::digest(&[&b"\x19Ethereum Signed Message:\n1234"[..], &message[..]].concat())
+				.as_slice(),
+		);
+		assert_eq!(personal_sign_hash(&message), hash);
+		assert!(sign(&secret_key, hash).verify(&message[..], &alith()));
+	}
 }
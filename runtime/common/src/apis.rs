@@ -365,6 +365,15 @@ macro_rules! impl_runtime_apis_plus_common {
 				}
 			}
 
+			#[cfg(feature = "try-runtime")]
+			impl frame_try_runtime::TryRuntime<Block> for Runtime {
+				fn on_runtime_upgrade() -> Result<(Weight, Weight), sp_runtime::RuntimeString> {
+					log::info!("try-runtime::on_runtime_upgrade");
+					let weight = Executive::try_runtime_upgrade()?;
+					Ok((weight, BlockWeights::get().max_block))
+				}
+			}
+
 			#[cfg(feature = "runtime-benchmarks")]
 			impl frame_benchmarking::Benchmark<Block> for Runtime {
 				fn dispatch_benchmark(
@@ -380,6 +389,8 @@ macro_rules! impl_runtime_apis_plus_common {
 					use pallet_crowdloan_rewards::Pallet as PalletCrowdloanRewardsBench;
 					use parachain_staking::Pallet as ParachainStakingBench;
 					use pallet_author_mapping::Pallet as PalletAuthorMappingBench;
+					use parachain_staking_precompiles::benchmarks::Pallet as StakingPrecompileBench;
+					impl parachain_staking_precompiles::benchmarks::Config for Runtime {}
 					let whitelist: Vec<TrackedStorageKey> = vec![];
 
 					let mut batches = Vec::<BenchmarkBatch>::new();
@@ -403,6 +414,12 @@ macro_rules! impl_runtime_apis_plus_common {
 						pallet_author_mapping,
 						PalletAuthorMappingBench::<Runtime>
 					);
+					add_benchmark!(
+						params,
+						batches,
+						parachain_staking_precompiles,
+						StakingPrecompileBench::<Runtime>
+					);
 					add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
 
 					if batches.is_empty() {
@@ -0,0 +1,58 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+/// Declares the block and extrinsic types of the runtime, including `SignedExtra` and
+/// `Executive`.
+///
+/// It must be called after `construct_runtime!`, as it relies on `Call` and `AllPallets`. The
+/// runtime provides `Migrations`, the list of storage migrations run by `Executive` on runtime
+/// upgrades.
+#[macro_export]
+macro_rules! impl_block_types {
+	() => {
+		/// Block type as expected by this runtime.
+		pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
+		/// A Block signed with a Justification
+		pub type SignedBlock = sp_runtime::generic::SignedBlock<Block>;
+		/// BlockId type as expected by this runtime.
+		pub type BlockId = sp_runtime::generic::BlockId<Block>;
+		/// The SignedExtension to the basic transaction logic.
+		pub type SignedExtra = (
+			frame_system::CheckSpecVersion<Runtime>,
+			frame_system::CheckTxVersion<Runtime>,
+			frame_system::CheckGenesis<Runtime>,
+			frame_system::CheckEra<Runtime>,
+			frame_system::CheckNonce<Runtime>,
+			frame_system::CheckWeight<Runtime>,
+			pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+		);
+		/// Unchecked extrinsic type as expected by this runtime.
+		pub type UncheckedExtrinsic =
+			sp_runtime::generic::UncheckedExtrinsic<Address, Call, Signature<Call>, SignedExtra>;
+		/// Extrinsic type that has already been checked.
+		pub type CheckedExtrinsic =
+			sp_runtime::generic::CheckedExtrinsic<AccountId, Call, SignedExtra>;
+		/// Executive: handles dispatch to the various pallets.
+		pub type Executive = frame_executive::Executive<
+			Runtime,
+			Block,
+			frame_system::ChainContext<Runtime>,
+			Runtime,
+			AllPallets,
+			$crate::RunMigrations<Runtime, Migrations>,
+		>;
+	};
+}
@@ -0,0 +1,184 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+/// Implements `account::eip712::Eip712Call` for the `Call` of the runtime, to sign its
+/// extrinsics as EIP-712 typed data.
+///
+/// The domain uses the chain id of `EthereumChainId`. The calls of `ParachainStaking` and
+/// `Democracy` made by users are signed as a struct with all their arguments, e.g.
+/// `Nominate(address collator,uint256 amount,uint32 collatorNominatorCount,uint32
+/// nominationCount)`, and the other calls as their SCALE encoding. The payloads of these calls
+/// stay below the 256 bytes that can be signed as typed data.
+#[macro_export]
+macro_rules! impl_eip712_call {
+	() => {
+		impl account::eip712::Eip712Call for Call {
+			fn chain_id() -> u64 {
+				EthereumChainId::chain_id()
+			}
+
+			fn typed_call(&self) -> Option<account::eip712::TypedCall> {
+				use account::eip712::{Member, TypedCall};
+				use pallet_democracy::AccountVote;
+
+				let (name, members) = match self {
+					Call::ParachainStaking(call) => match call {
+						parachain_staking::Call::join_candidates(bond, candidate_count) => (
+							"JoinCandidates",
+							sp_std::vec![
+								Member::uint("uint256", "bond", (*bond).into()),
+								Member::uint("uint32", "candidateCount", (*candidate_count).into()),
+							],
+						),
+						parachain_staking::Call::leave_candidates(candidate_count) => (
+							"LeaveCandidates",
+							sp_std::vec![Member::uint(
+								"uint32",
+								"candidateCount",
+								(*candidate_count).into(),
+							)],
+						),
+						parachain_staking::Call::go_offline() => ("GoOffline", sp_std::vec![]),
+						parachain_staking::Call::go_online() => ("GoOnline", sp_std::vec![]),
+						parachain_staking::Call::candidate_bond_more(more) => (
+							"CandidateBondMore",
+							sp_std::vec![Member::uint("uint256", "more", (*more).into())],
+						),
+						parachain_staking::Call::candidate_bond_less(less) => (
+							"CandidateBondLess",
+							sp_std::vec![Member::uint("uint256", "less", (*less).into())],
+						),
+						parachain_staking::Call::nominate(
+							collator,
+							amount,
+							collator_nominator_count,
+							nomination_count,
+						) => (
+							"Nominate",
+							sp_std::vec![
+								Member::address("collator", *collator),
+								Member::uint("uint256", "amount", (*amount).into()),
+								Member::uint(
+									"uint32",
+									"collatorNominatorCount",
+									(*collator_nominator_count).into(),
+								),
+								Member::uint(
+									"uint32",
+									"nominationCount",
+									(*nomination_count).into(),
+								),
+							],
+						),
+						parachain_staking::Call::leave_nominators(nomination_count) => (
+							"LeaveNominators",
+							sp_std::vec![Member::uint(
+								"uint32",
+								"nominationCount",
+								(*nomination_count).into(),
+							)],
+						),
+						parachain_staking::Call::revoke_nomination(collator) => (
+							"RevokeNomination",
+							sp_std::vec![Member::address("collator", *collator)],
+						),
+						parachain_staking::Call::nominator_bond_more(candidate, more) => (
+							"NominatorBondMore",
+							sp_std::vec![
+								Member::address("candidate", *candidate),
+								Member::uint("uint256", "more", (*more).into()),
+							],
+						),
+						parachain_staking::Call::nominator_bond_less(candidate, less) => (
+							"NominatorBondLess",
+							sp_std::vec![
+								Member::address("candidate", *candidate),
+								Member::uint("uint256", "less", (*less).into()),
+							],
+						),
+						_ => return None,
+					},
+					Call::Democracy(call) => match call {
+						pallet_democracy::Call::propose(proposal_hash, value) => (
+							"Propose",
+							sp_std::vec![
+								Member::bytes32("proposalHash", *proposal_hash),
+								Member::uint("uint256", "value", (*value).into()),
+							],
+						),
+						pallet_democracy::Call::second(proposal, seconds_upper_bound) => (
+							"Second",
+							sp_std::vec![
+								Member::uint("uint32", "proposal", (*proposal).into()),
+								Member::uint(
+									"uint32",
+									"secondsUpperBound",
+									(*seconds_upper_bound).into(),
+								),
+							],
+						),
+						pallet_democracy::Call::vote(
+							ref_index,
+							AccountVote::Standard { vote, balance },
+						) => (
+							"StandardVote",
+							sp_std::vec![
+								Member::uint("uint32", "refIndex", (*ref_index).into()),
+								Member::bool("aye", vote.aye),
+								Member::uint(
+									"uint8",
+									"conviction",
+									u8::from(vote.conviction).into(),
+								),
+								Member::uint("uint256", "balance", (*balance).into()),
+							],
+						),
+						pallet_democracy::Call::vote(
+							ref_index,
+							AccountVote::Split { aye, nay },
+						) => (
+							"SplitVote",
+							sp_std::vec![
+								Member::uint("uint32", "refIndex", (*ref_index).into()),
+								Member::uint("uint256", "aye", (*aye).into()),
+								Member::uint("uint256", "nay", (*nay).into()),
+							],
+						),
+						pallet_democracy::Call::delegate(to, conviction, balance) => (
+							"Delegate",
+							sp_std::vec![
+								Member::address("to", *to),
+								Member::uint("uint8", "conviction", u8::from(*conviction).into()),
+								Member::uint("uint256", "balance", (*balance).into()),
+							],
+						),
+						pallet_democracy::Call::undelegate() => ("Undelegate", sp_std::vec![]),
+						pallet_democracy::Call::remove_vote(index) => (
+							"RemoveVote",
+							sp_std::vec![Member::uint("uint32", "index", (*index).into())],
+						),
+						pallet_democracy::Call::unlock(target) => {
+							("Unlock", sp_std::vec![Member::address("target", *target)])
+						}
+						_ => return None,
+					},
+					_ => return None,
+				};
+				Some(TypedCall::new(name, members))
+			}
+		}
+	};
+}
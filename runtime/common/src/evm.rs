@@ -0,0 +1,120 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+use frame_support::weights::{constants::WEIGHT_PER_SECOND, Weight};
+use sp_std::convert::TryFrom;
+
+/// Current approximation of the gas/s consumption considering
+/// EVM execution over compiled WASM (on 4.4Ghz CPU).
+/// Given the 500ms Weight, from which 75% only are used for transactions,
+/// the total EVM execution gas limit is: GAS_PER_SECOND * 0.500 * 0.75 ~= 15_000_000.
+pub const GAS_PER_SECOND: u64 = 40_000_000;
+
+/// Approximate ratio of the amount of Weight per Gas.
+/// u64 works for approximations because Weight is a very small unit compared to gas.
+pub const WEIGHT_PER_GAS: u64 = WEIGHT_PER_SECOND / GAS_PER_SECOND;
+
+pub struct MoonbeamGasWeightMapping;
+
+impl pallet_evm::GasWeightMapping for MoonbeamGasWeightMapping {
+	fn gas_to_weight(gas: u64) -> Weight {
+		gas.saturating_mul(WEIGHT_PER_GAS)
+	}
+	fn weight_to_gas(weight: Weight) -> u64 {
+		u64::try_from(weight.wrapping_div(WEIGHT_PER_GAS)).unwrap_or(u32::MAX as u64)
+	}
+}
+
+/// Implements the configs of `pallet_evm`, `pallet_ethereum`, `pallet_base_fee` and
+/// `pallet_ethereum_chain_id`, along with the `TransactionConverter` used by the node.
+///
+/// The runtime provides `Precompiles`, the `MinGasPrice` of the EVM and the `FeesBurnPercent`
+/// shared with `pallet_transaction_payment`. The block gas limit is derived from
+/// `NORMAL_DISPATCH_RATIO` and `MAXIMUM_BLOCK_WEIGHT`.
+#[macro_export]
+macro_rules! impl_evm_config {
+	() => {
+		frame_support::parameter_types! {
+			pub BlockGasLimit: sp_core::U256 = sp_core::U256::from(
+				NORMAL_DISPATCH_RATIO * MAXIMUM_BLOCK_WEIGHT / $crate::WEIGHT_PER_GAS
+			);
+			/// The EVM gas price changes by at most 12.5% per block, like the EIP-1559 base fee.
+			pub GasPriceElasticity: sp_runtime::Perquintill =
+				sp_runtime::Perquintill::from_rational_approximation(1u64, 8u64);
+		}
+
+		impl pallet_base_fee::Config for Runtime {
+			type MinGasPrice = MinGasPrice;
+			type TargetBlockFullness = $crate::TargetBlockFullness;
+			type Elasticity = GasPriceElasticity;
+		}
+
+		impl pallet_evm::Config for Runtime {
+			type FeeCalculator = BaseFee;
+			type GasWeightMapping = $crate::MoonbeamGasWeightMapping;
+			type CallOrigin = pallet_evm::EnsureAddressRoot<AccountId>;
+			type WithdrawOrigin = pallet_evm::EnsureAddressNever<AccountId>;
+			type AddressMapping = pallet_evm::IdentityAddressMapping;
+			type Currency = Balances;
+			type Event = Event;
+			type Runner = pallet_evm::runner::stack::Runner<Self>;
+			type Precompiles = Precompiles;
+			type ChainId = EthereumChainId;
+			type OnChargeTransaction = pallet_evm::EVMCurrencyAdapter<
+				Balances,
+				$crate::DealWithFees<Runtime, FeesBurnPercent>,
+			>;
+			type BlockGasLimit = BlockGasLimit;
+		}
+
+		impl pallet_ethereum::Config for Runtime {
+			type Event = Event;
+			type FindAuthor = AuthorInherent;
+			type StateRoot = pallet_ethereum::IntermediateStateRoot;
+		}
+
+		impl pallet_ethereum_chain_id::Config for Runtime {}
+
+		pub struct TransactionConverter;
+
+		impl fp_rpc::ConvertTransaction<UncheckedExtrinsic> for TransactionConverter {
+			fn convert_transaction(
+				&self,
+				transaction: pallet_ethereum::Transaction,
+			) -> UncheckedExtrinsic {
+				UncheckedExtrinsic::new_unsigned(
+					pallet_ethereum::Call::<Runtime>::transact(transaction).into(),
+				)
+			}
+		}
+
+		impl fp_rpc::ConvertTransaction<opaque::UncheckedExtrinsic> for TransactionConverter {
+			fn convert_transaction(
+				&self,
+				transaction: pallet_ethereum::Transaction,
+			) -> opaque::UncheckedExtrinsic {
+				let extrinsic = UncheckedExtrinsic::new_unsigned(
+					pallet_ethereum::Call::<Runtime>::transact(transaction).into(),
+				);
+				let encoded = parity_scale_codec::Encode::encode(&extrinsic);
+				<opaque::UncheckedExtrinsic as parity_scale_codec::Decode>::decode(
+					&mut &encoded[..],
+				)
+				.expect("Encoded extrinsic is always valid")
+			}
+		}
+	};
+}
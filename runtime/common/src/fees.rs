@@ -0,0 +1,99 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+use frame_support::{
+	parameter_types,
+	traits::{Get, Imbalance, OnUnbalanced},
+};
+use pallet_balances::NegativeImbalance;
+use pallet_transaction_payment::{Multiplier, TargetedFeeAdjustment};
+use sp_runtime::{FixedPointNumber, Percent, Perquintill};
+use sp_std::marker::PhantomData;
+
+/// Burns `BurnPercent` of the fees and sends the rest to the treasury.
+///
+/// It is used for both Substrate fees, through `pallet_transaction_payment::CurrencyAdapter`, and
+/// EVM fees, through `pallet_evm::EVMCurrencyAdapter`. Tips are burned.
+pub struct DealWithFees<R, BurnPercent>(PhantomData<(R, BurnPercent)>);
+
+impl<R, BurnPercent> OnUnbalanced<NegativeImbalance<R>> for DealWithFees<R, BurnPercent>
+where
+	R: pallet_balances::Config + pallet_treasury::Config,
+	pallet_treasury::Pallet<R>: OnUnbalanced<NegativeImbalance<R>>,
+	BurnPercent: Get<Percent>,
+{
+	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalance<R>>) {
+		if let Some(fees) = fees_then_tips.next() {
+			let burned = BurnPercent::get().deconstruct() as u32;
+			let (_, to_treasury) = fees.ration(burned, 100 - burned);
+			// Balances pallet automatically burns dropped Negative Imbalances by decreasing
+			// total_supply accordingly
+			<pallet_treasury::Pallet<R> as OnUnbalanced<_>>::on_unbalanced(to_treasury);
+		}
+	}
+}
+
+parameter_types! {
+	/// The portion of the `NORMAL_DISPATCH_RATIO` that we adjust the fees with. Blocks filled less
+	/// than this will decrease the weight and more will increase.
+	pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
+	/// The adjustment variable of the runtime. Higher values will cause `TargetBlockFullness` to
+	/// change the fees more rapidly. This low value causes changes to occur slowly over time.
+	pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(3, 100_000);
+	/// Minimum amount of the multiplier. This value cannot be too low. A test case should ensure
+	/// that combined with `AdjustmentVariable`, we can recover from the minimum.
+	/// See `multiplier_can_grow_from_zero` in integration_tests.rs.
+	/// This value is currently only used by pallet-transaction-payment as an assertion that the
+	/// next multiplier is always > min value.
+	pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 1_000_000u128);
+}
+
+/// Parameterized slow adjusting fee updated based on
+/// https://w3f-research.readthedocs.io/en/latest/polkadot/overview/2-token-economics.html#-2.-slow-adjusting-mechanism // editorconfig-checker-disable-line
+///
+/// The adjustment algorithm boils down to:
+///
+/// diff = (previous_block_weight - target) / maximum_block_weight
+/// next_multiplier = prev_multiplier * (1 + (v * diff) + ((v * diff)^2 / 2))
+/// assert(next_multiplier > min)
+///     where: v is AdjustmentVariable
+///            target is TargetBlockFullness
+///            min is MinimumMultiplier
+pub type SlowAdjustingFeeUpdate<R> =
+	TargetedFeeAdjustment<R, TargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
+
+/// Implements the config of `pallet_transaction_payment`.
+///
+/// The runtime provides `FeesBurnPercent`, the share of the fees that is burned, and
+/// `currency::BYTE_FEE`.
+#[macro_export]
+macro_rules! impl_transaction_payment_config {
+	() => {
+		frame_support::parameter_types! {
+			pub const TransactionByteFee: Balance = currency::BYTE_FEE;
+		}
+
+		impl pallet_transaction_payment::Config for Runtime {
+			type OnChargeTransaction = pallet_transaction_payment::CurrencyAdapter<
+				Balances,
+				$crate::DealWithFees<Runtime, FeesBurnPercent>,
+			>;
+			type TransactionByteFee = TransactionByteFee;
+			type WeightToFee = frame_support::weights::IdentityFee<Balance>;
+			type FeeMultiplierUpdate = $crate::SlowAdjustingFeeUpdate<Runtime>;
+		}
+	};
+}
@@ -0,0 +1,173 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+/// Implements the configs of `pallet_scheduler`, of the council and technical committee
+/// instances of `pallet_collective`, and of `pallet_democracy`.
+///
+/// It declares the `CouncilInstance` and `TechCommitteeInstance` aliases used by the origins of
+/// the other pallets. The runtime provides the durations `CouncilMotionDuration`,
+/// `TechComitteeMotionDuration`, `LaunchPeriod`, `VotingPeriod`, `FastTrackVotingPeriod`,
+/// `EnactmentPeriod` and `CooloffPeriod`, along with `MinimumDeposit` and `InstantAllowed`.
+#[macro_export]
+macro_rules! impl_governance_config {
+	() => {
+		frame_support::parameter_types! {
+			pub MaximumSchedulerWeight: Weight =
+				NORMAL_DISPATCH_RATIO * BlockWeights::get().max_block;
+			pub const MaxScheduledPerBlock: u32 = 50;
+		}
+
+		impl pallet_scheduler::Config for Runtime {
+			type Event = Event;
+			type Origin = Origin;
+			type PalletsOrigin = OriginCaller;
+			type Call = Call;
+			type MaximumWeight = MaximumSchedulerWeight;
+			type ScheduleOrigin = frame_system::EnsureRoot<AccountId>;
+			type MaxScheduledPerBlock = MaxScheduledPerBlock;
+			type WeightInfo = pallet_scheduler::weights::SubstrateWeight<Runtime>;
+		}
+
+		frame_support::parameter_types! {
+			/// The maximum number of Proposlas that can be open in the council at once.
+			pub const CouncilMaxProposals: u32 = 100;
+			/// The maximum number of council members.
+			pub const CouncilMaxMembers: u32 = 100;
+			/// The maximum number of Proposlas that can be open in the technical committee at once.
+			pub const TechComitteeMaxProposals: u32 = 100;
+			/// The maximum number of technical committee members.
+			pub const TechComitteeMaxMembers: u32 = 100;
+		}
+
+		type CouncilInstance = pallet_collective::Instance1;
+		type TechCommitteeInstance = pallet_collective::Instance2;
+
+		impl pallet_collective::Config<CouncilInstance> for Runtime {
+			type Origin = Origin;
+			type Event = Event;
+			type Proposal = Call;
+			type MotionDuration = CouncilMotionDuration;
+			type MaxProposals = CouncilMaxProposals;
+			type MaxMembers = CouncilMaxMembers;
+			type DefaultVote = pallet_collective::MoreThanMajorityThenPrimeDefaultVote;
+			type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+		}
+
+		impl pallet_collective::Config<TechCommitteeInstance> for Runtime {
+			type Origin = Origin;
+			type Event = Event;
+			type Proposal = Call;
+			type MotionDuration = TechComitteeMotionDuration;
+			type MaxProposals = TechComitteeMaxProposals;
+			type MaxMembers = TechComitteeMaxMembers;
+			type DefaultVote = pallet_collective::MoreThanMajorityThenPrimeDefaultVote;
+			type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+		}
+
+		frame_support::parameter_types! {
+			pub const MaxVotes: u32 = 100;
+			pub const MaxProposals: u32 = 100;
+			pub const PreimageByteDeposit: Balance = currency::BYTE_FEE;
+		}
+
+		impl pallet_democracy::Config for Runtime {
+			type Proposal = Call;
+			type Event = Event;
+			type Currency = Balances;
+			type EnactmentPeriod = EnactmentPeriod;
+			type LaunchPeriod = LaunchPeriod;
+			type VotingPeriod = VotingPeriod;
+			type FastTrackVotingPeriod = FastTrackVotingPeriod;
+			type MinimumDeposit = MinimumDeposit;
+			/// A straight majority of the council can decide what their next motion is.
+			type ExternalOrigin = pallet_collective::EnsureProportionAtLeast<
+				sp_core::u32_trait::_1,
+				sp_core::u32_trait::_2,
+				AccountId,
+				CouncilInstance,
+			>;
+			/// A majority can have the next scheduled referendum be a straight majority-carries vote.
+			type ExternalMajorityOrigin = pallet_collective::EnsureProportionAtLeast<
+				sp_core::u32_trait::_1,
+				sp_core::u32_trait::_2,
+				AccountId,
+				CouncilInstance,
+			>;
+			/// A unanimous council can have the next scheduled referendum be a straight
+			/// default-carries (NTB) vote.
+			type ExternalDefaultOrigin = pallet_collective::EnsureProportionAtLeast<
+				sp_core::u32_trait::_1,
+				sp_core::u32_trait::_1,
+				AccountId,
+				CouncilInstance,
+			>;
+			/// Two thirds of the technical committee can have an ExternalMajority/ExternalDefault
+			/// vote be tabled immediately and with a shorter voting/enactment period.
+			type FastTrackOrigin = pallet_collective::EnsureProportionAtLeast<
+				sp_core::u32_trait::_2,
+				sp_core::u32_trait::_3,
+				AccountId,
+				TechCommitteeInstance,
+			>;
+			/// Instant is only allowed when `InstantAllowed` is set, and requires a unanimous
+			/// technical committee.
+			type InstantOrigin = pallet_collective::EnsureProportionAtLeast<
+				sp_core::u32_trait::_1,
+				sp_core::u32_trait::_1,
+				AccountId,
+				TechCommitteeInstance,
+			>;
+			// To cancel a proposal which has been passed, 2/3 of the council must agree to it.
+			type CancellationOrigin = frame_system::EnsureOneOf<
+				AccountId,
+				frame_system::EnsureRoot<AccountId>,
+				pallet_collective::EnsureProportionAtLeast<
+					sp_core::u32_trait::_2,
+					sp_core::u32_trait::_3,
+					AccountId,
+					CouncilInstance,
+				>,
+			>;
+			// To cancel a proposal before it has been passed, the technical committee must be
+			// unanimous or Root must agree.
+			type CancelProposalOrigin = frame_system::EnsureOneOf<
+				AccountId,
+				frame_system::EnsureRoot<AccountId>,
+				pallet_collective::EnsureProportionAtLeast<
+					sp_core::u32_trait::_1,
+					sp_core::u32_trait::_1,
+					AccountId,
+					TechCommitteeInstance,
+				>,
+			>;
+			type BlacklistOrigin = frame_system::EnsureRoot<AccountId>;
+			// Any single technical committee member may veto a coming council proposal, however
+			// they can only do it once and it lasts only for the cooloff period.
+			type VetoOrigin = pallet_collective::EnsureMember<AccountId, TechCommitteeInstance>;
+			type CooloffPeriod = CooloffPeriod;
+			type PreimageByteDeposit = PreimageByteDeposit;
+			type Slash = ();
+			type InstantAllowed = InstantAllowed;
+			type Scheduler = Scheduler;
+			type MaxVotes = MaxVotes;
+			type OperationalPreimageOrigin =
+				pallet_collective::EnsureMember<AccountId, CouncilInstance>;
+			type PalletsOrigin = OriginCaller;
+			type WeightInfo = pallet_democracy::weights::SubstrateWeight<Runtime>;
+			type MaxProposals = MaxProposals;
+		}
+	};
+}
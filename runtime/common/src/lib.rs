@@ -17,3 +17,22 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod apis;
+mod block;
+mod eip712;
+mod evm;
+mod fees;
+mod governance;
+mod migrations;
+mod precompiles;
+mod proxy;
+mod staking;
+mod transaction_pool;
+
+pub use evm::{MoonbeamGasWeightMapping, GAS_PER_SECOND, WEIGHT_PER_GAS};
+pub use fees::{
+	AdjustmentVariable, DealWithFees, MinimumMultiplier, SlowAdjustingFeeUpdate,
+	TargetBlockFullness,
+};
+pub use migrations::{AddPrecompileDummyCode, Migration, MigrationList, RunMigrations};
+pub use proxy::ProxyType;
+pub use transaction_pool::gas_price_priority;
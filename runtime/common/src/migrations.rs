@@ -0,0 +1,366 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Registry of the storage migrations run when the runtime is upgraded.
+//!
+//! Each migration brings the storage of one pallet to a given `PalletVersion`, read through
+//! `GetPalletVersion` from the key FRAME stores it under. A migration runs on the first runtime
+//! upgrade where the pallet is found at an older version, or with no version at all because it
+//! was added to a live chain. The target version must not be above the crate version of the
+//! pallet: FRAME stores the crate version once the migrations of the upgrade have run, so a
+//! higher target would run the migration again on the next upgrade.
+
+use frame_support::{
+	storage::unhashed,
+	traits::{
+		Get, GetPalletVersion, OnRuntimeUpgrade, PalletInfoAccess, PalletVersion,
+		PALLET_VERSION_STORAGE_KEY_POSTFIX,
+	},
+	weights::{RuntimeDbWeight, Weight},
+	StorageHasher, Twox128,
+};
+use sp_core::H160;
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// Suffix of the keys marking the migrations found pending by `pre_upgrade`.
+#[cfg(feature = "try-runtime")]
+const PENDING_MIGRATION_KEY_POSTFIX: &[u8] = b":__PENDING_MIGRATION__:";
+
+/// A storage migration of a single pallet.
+pub trait Migration {
+	/// The pallet whose storage is migrated.
+	type Pallet: GetPalletVersion + PalletInfoAccess;
+
+	/// The version of the pallet once migrated. The migration only runs while the pallet is at
+	/// an older version.
+	const PALLET_VERSION: PalletVersion;
+
+	/// A human readable name for the migration, used in the logs.
+	fn friendly_name() -> &'static str;
+
+	/// The weight of `migrate`, accounted for in the block of the runtime upgrade.
+	fn weight() -> Weight;
+
+	/// Migrates the storage of the pallet.
+	fn migrate();
+
+	/// Checks the storage before the migration. Only called if the migration is pending.
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> {
+		Ok(())
+	}
+
+	/// Checks the storage after the migration. Only called if the migration ran.
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		Ok(())
+	}
+}
+
+/// An ordered list of migrations, implemented for tuples of `Migration`.
+pub trait MigrationList {
+	/// Runs the pending migrations and returns the consumed weight.
+	fn migrate(db_weight: RuntimeDbWeight) -> Weight;
+
+	/// Runs `pre_upgrade` on the pending migrations.
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str>;
+
+	/// Runs `post_upgrade` on the migrations that were pending in `pre_upgrade`.
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl MigrationList for Tuple {
+	for_tuples!( where #( Tuple: Migration )* );
+
+	fn migrate(db_weight: RuntimeDbWeight) -> Weight {
+		let mut weight: Weight = 0;
+		for_tuples!( #( weight = weight.saturating_add(migrate::<Tuple>(db_weight)); )* );
+		weight
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> {
+		for_tuples!( #( pre_upgrade::<Tuple>()?; )* );
+		Ok(())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		for_tuples!( #( post_upgrade::<Tuple>()?; )* );
+		Ok(())
+	}
+}
+
+/// Runs the `Migrations` of the runtime, to be given to `frame_executive::Executive`.
+pub struct RunMigrations<Runtime, Migrations>(PhantomData<(Runtime, Migrations)>);
+
+impl<Runtime, Migrations> OnRuntimeUpgrade for RunMigrations<Runtime, Migrations>
+where
+	Runtime: frame_system::Config,
+	Migrations: MigrationList,
+{
+	fn on_runtime_upgrade() -> Weight {
+		Migrations::migrate(Runtime::DbWeight::get())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> {
+		Migrations::pre_upgrade()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		Migrations::post_upgrade()
+	}
+}
+
+/// The simplest bytecode to revert without returning any data, stored under the precompile
+/// addresses so that the EVM actually calls them from within contracts.
+/// (PUSH1 0x00 PUSH1 0x00 REVERT)
+const PRECOMPILE_DUMMY_CODE: [u8; 5] = [0x60, 0x00, 0x60, 0x00, 0xFD];
+
+/// Addresses of the precompiles added after the launch of the live chains, whose dummy code is
+/// only set by the genesis of newer chains: Blake2F, ERC-20, democracy, author mapping and batch.
+const PRECOMPILES_ADDED_AFTER_LAUNCH: [u64; 5] = [9, 2050, 2051, 2052, 2053];
+
+/// Stores the dummy code under the precompiles added after the launch of the live chains.
+///
+/// It is keyed to the pallet pausing precompiles, which the live chains don't have yet. A chain
+/// built from a newer genesis already has the code and a version for that pallet, so the
+/// migration is skipped there.
+pub struct AddPrecompileDummyCode<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> Migration for AddPrecompileDummyCode<Runtime>
+where
+	Runtime: pallet_evm::Config + pallet_precompile_pause::Config,
+{
+	type Pallet = pallet_precompile_pause::Pallet<Runtime>;
+	const PALLET_VERSION: PalletVersion = PalletVersion {
+		major: 0,
+		minor: 1,
+		patch: 0,
+	};
+
+	fn friendly_name() -> &'static str {
+		"AddPrecompileDummyCode"
+	}
+
+	fn weight() -> Weight {
+		Runtime::DbWeight::get().writes(PRECOMPILES_ADDED_AFTER_LAUNCH.len() as Weight)
+	}
+
+	fn migrate() {
+		for address in PRECOMPILES_ADDED_AFTER_LAUNCH.iter() {
+			pallet_evm::AccountCodes::<Runtime>::insert(
+				H160::from_low_u64_be(*address),
+				PRECOMPILE_DUMMY_CODE.to_vec(),
+			);
+		}
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		for address in PRECOMPILES_ADDED_AFTER_LAUNCH.iter() {
+			frame_support::ensure!(
+				pallet_evm::AccountCodes::<Runtime>::get(H160::from_low_u64_be(*address))
+					== PRECOMPILE_DUMMY_CODE.to_vec(),
+				"Precompile dummy code is missing"
+			);
+		}
+		Ok(())
+	}
+}
+
+fn pallet_key<Pallet: PalletInfoAccess>(postfix: &[u8]) -> Vec<u8> {
+	let mut key = Twox128::hash(Pallet::name().as_bytes()).to_vec();
+	key.extend_from_slice(&Twox128::hash(postfix));
+	key
+}
+
+fn is_pending<M: Migration>() -> bool {
+	M::Pallet::storage_version().map_or(true, |version| version < M::PALLET_VERSION)
+}
+
+fn migrate<M: Migration>(db_weight: RuntimeDbWeight) -> Weight {
+	if !is_pending::<M>() {
+		log::debug!(
+			target: "migrations",
+			"Skipping migration {}, {} is already at version {:?}",
+			M::friendly_name(),
+			M::Pallet::name(),
+			M::Pallet::storage_version(),
+		);
+		return db_weight.reads(1);
+	}
+
+	log::info!(target: "migrations", "Running migration {}", M::friendly_name());
+	M::migrate();
+	unhashed::put(
+		&pallet_key::<M::Pallet>(PALLET_VERSION_STORAGE_KEY_POSTFIX),
+		&M::PALLET_VERSION,
+	);
+
+	M::weight().saturating_add(db_weight.reads_writes(1, 1))
+}
+
+#[cfg(feature = "try-runtime")]
+fn pending_key<M: Migration>() -> Vec<u8> {
+	use parity_scale_codec::Encode;
+
+	let mut key = pallet_key::<M::Pallet>(PENDING_MIGRATION_KEY_POSTFIX);
+	key.extend_from_slice(&M::PALLET_VERSION.encode());
+	key
+}
+
+#[cfg(feature = "try-runtime")]
+fn pre_upgrade<M: Migration>() -> Result<(), &'static str> {
+	if is_pending::<M>() {
+		M::pre_upgrade()?;
+		unhashed::put(&pending_key::<M>(), &true);
+	}
+	Ok(())
+}
+
+#[cfg(feature = "try-runtime")]
+fn post_upgrade<M: Migration>() -> Result<(), &'static str> {
+	if unhashed::take::<bool>(&pending_key::<M>()).unwrap_or(false) {
+		frame_support::ensure!(
+			!is_pending::<M>(),
+			"Migration did not update the pallet version"
+		);
+		M::post_upgrade()?;
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const DB_WEIGHT: RuntimeDbWeight = RuntimeDbWeight { read: 1, write: 10 };
+
+	struct Mock;
+
+	impl PalletInfoAccess for Mock {
+		fn index() -> usize {
+			0
+		}
+		fn name() -> &'static str {
+			"Mock"
+		}
+	}
+
+	impl GetPalletVersion for Mock {
+		fn current_version() -> PalletVersion {
+			PalletVersion::new(2, 0, 0)
+		}
+		fn storage_version() -> Option<PalletVersion> {
+			unhashed::get(&pallet_key::<Mock>(PALLET_VERSION_STORAGE_KEY_POSTFIX))
+		}
+	}
+
+	/// Counts its runs under a storage key named after the migration.
+	macro_rules! counting_migration {
+		($name:ident, $version:expr) => {
+			struct $name;
+
+			impl Migration for $name {
+				type Pallet = Mock;
+				const PALLET_VERSION: PalletVersion = PalletVersion {
+					major: $version,
+					minor: 0,
+					patch: 0,
+				};
+
+				fn friendly_name() -> &'static str {
+					stringify!($name)
+				}
+				fn weight() -> Weight {
+					100
+				}
+				fn migrate() {
+					let key = stringify!($name).as_bytes();
+					unhashed::put(key, &(unhashed::get_or_default::<u32>(key) + 1));
+				}
+			}
+
+			impl $name {
+				fn runs() -> u32 {
+					unhashed::get_or_default(stringify!($name).as_bytes())
+				}
+			}
+		};
+	}
+
+	counting_migration!(MigrateToV1, 1);
+	counting_migration!(MigrateToV2, 2);
+
+	#[test]
+	fn migrations_run_in_order_and_set_the_pallet_version() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			assert_eq!(Mock::storage_version(), None);
+
+			let weight = <(MigrateToV1, MigrateToV2)>::migrate(DB_WEIGHT);
+
+			assert_eq!(MigrateToV1::runs(), 1);
+			assert_eq!(MigrateToV2::runs(), 1);
+			assert_eq!(Mock::storage_version(), Some(PalletVersion::new(2, 0, 0)));
+			assert_eq!(weight, 2 * (100 + 1 + 10));
+		});
+	}
+
+	#[test]
+	fn migrations_run_only_once() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			<(MigrateToV1,)>::migrate(DB_WEIGHT);
+			let weight = <(MigrateToV1, MigrateToV2)>::migrate(DB_WEIGHT);
+
+			assert_eq!(MigrateToV1::runs(), 1);
+			assert_eq!(MigrateToV2::runs(), 1);
+			assert_eq!(weight, 1 + (100 + 1 + 10));
+
+			assert_eq!(<(MigrateToV1, MigrateToV2)>::migrate(DB_WEIGHT), 2);
+			assert_eq!(MigrateToV2::runs(), 1);
+		});
+	}
+
+	#[test]
+	fn migrations_below_the_pallet_version_are_skipped() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			unhashed::put(
+				&pallet_key::<Mock>(PALLET_VERSION_STORAGE_KEY_POSTFIX),
+				&PalletVersion::new(1, 0, 0),
+			);
+
+			let weight = <(MigrateToV1, MigrateToV2)>::migrate(DB_WEIGHT);
+
+			assert_eq!(MigrateToV1::runs(), 0);
+			assert_eq!(MigrateToV2::runs(), 1);
+			assert_eq!(weight, 1 + (100 + 1 + 10));
+		});
+	}
+
+	#[test]
+	fn empty_list_does_nothing() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			assert_eq!(<()>::migrate(DB_WEIGHT), 0);
+			assert_eq!(Mock::storage_version(), None);
+		});
+	}
+}
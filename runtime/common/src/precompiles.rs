@@ -0,0 +1,221 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+/// Declares a PrecompileSet from a table of `address => precompile` entries.
+///
+/// Both `used_addresses` and `PrecompileSet::execute` are generated from the same table, so a
+/// precompile can't be routed without also being listed. Addresses in `disabled` are neither
+/// listed nor routed. `paused` is a `fn(&H160) -> bool` consulted on every call to the precompiles
+/// from `pausable_from`, so that they can be switched off without a runtime upgrade. The check
+/// reads the storage, so `pause_check_gas` is added to the cost of these calls. The precompiles
+/// below `pausable_from` can't be paused and keep their own cost.
+///
+/// Only the precompiles below `delegatable_below` can be reached through DELEGATECALL or CALLCODE.
+/// The others act on behalf of `context.caller`, which a contract delegating to them would be able
+/// to impersonate, so they must be called directly.
+#[macro_export]
+macro_rules! precompile_set {
+	{
+		$(#[$attr:meta])*
+		$name:ident<$runtime:ident>
+		where { $($bounds:tt)* }
+		disabled: [$($disabled:literal),* $(,)?],
+		paused: $paused:path,
+		pause_check_gas: $pause_check_gas:expr,
+		pausable_from: $pausable_from:literal,
+		delegatable_below: $delegatable_below:literal,
+		precompiles: {
+			$($address:literal => $precompile:ty),* $(,)?
+		}
+	} => {
+		$(#[$attr])*
+		#[derive(Debug, Clone, Copy)]
+		pub struct $name<$runtime>(sp_std::marker::PhantomData<$runtime>);
+
+		impl<$runtime> $name<$runtime> {
+			/// Addresses of the precompiles this runtime doesn't expose.
+			const DISABLED: &'static [u64] = &[$($disabled),*];
+
+			fn is_enabled(address: u64) -> bool {
+				!Self::DISABLED.contains(&address)
+			}
+		}
+
+		impl<$runtime: frame_system::Config> $name<$runtime>
+		where
+			$runtime::AccountId: From<sp_core::H160>,
+		{
+			/// Return all addresses that contain precompiles. This can be used to populate dummy
+			/// code under the precompile.
+			pub fn used_addresses() -> impl Iterator<Item = $runtime::AccountId> {
+				sp_std::vec![$($address),*]
+					.into_iter()
+					.filter(|address| Self::is_enabled(*address))
+					.map(|address| sp_core::H160::from_low_u64_be(address).into())
+			}
+		}
+
+		impl<$runtime> pallet_evm::PrecompileSet for $name<$runtime>
+		where
+			$($bounds)*
+		{
+			fn execute(
+				address: sp_core::H160,
+				input: &[u8],
+				target_gas: Option<u64>,
+				context: &evm::Context,
+			) -> Option<Result<evm::executor::PrecompileOutput, evm::ExitError>> {
+				$(
+					if address == sp_core::H160::from_low_u64_be($address)
+						&& Self::is_enabled($address)
+					{
+						let pause_check_gas: u64 = if $address >= $pausable_from {
+							$pause_check_gas
+						} else {
+							0
+						};
+						if target_gas.map_or(false, |gas_limit| gas_limit < pause_check_gas) {
+							return Some(Err(evm::ExitError::OutOfGas));
+						}
+						if $address >= $pausable_from && $paused(&address) {
+							return Some(Err(evm::ExitError::Other(
+								"Precompile is paused by governance".into(),
+							)));
+						}
+						if $address >= $delegatable_below && context.address != address {
+							return Some(Err(evm::ExitError::Other(
+								"Cannot be called with DELEGATECALL or CALLCODE".into(),
+							)));
+						}
+						let target_gas = target_gas.map(|gas_limit| gas_limit - pause_check_gas);
+						return Some(
+							<$precompile as pallet_evm::Precompile>::execute(
+								input, target_gas, context,
+							)
+							.map(|mut output| {
+								output.cost = output.cost.saturating_add(pause_check_gas);
+								output
+							}),
+						);
+					}
+				)*
+				None
+			}
+		}
+	};
+}
+
+/// Declares the PrecompileSet shared by the Moonbeam runtimes.
+///
+/// The following distribution has been decided for the precompiles
+/// 0-1023: Ethereum Mainnet Precompiles
+/// 1024-2047 Precompiles that are not in Ethereum Mainnet but are neither Moonbeam specific
+/// 2048-4095 Moonbeam specific precompiles
+///
+/// Only the Ethereum Mainnet precompiles can be called through DELEGATECALL or CALLCODE, and they
+/// can't be paused so that they cost the same as on Ethereum.
+///
+/// Each runtime provides the ERC20 metadata of its native token and may disable entries by
+/// address. Governance can additionally pause entries at runtime through
+/// `pallet_precompile_pause`.
+#[macro_export]
+macro_rules! moonbeam_precompile_set {
+	{
+		$(#[$attr:meta])*
+		$name:ident,
+		erc20_metadata: $metadata:ty,
+		disabled: [$($disabled:literal),* $(,)?] $(,)?
+	} => {
+		$crate::precompile_set! {
+			$(#[$attr])*
+			$name<R>
+			where {
+				R::Call: frame_support::dispatch::Dispatchable<
+						PostInfo = frame_support::dispatch::PostDispatchInfo,
+					> + frame_support::dispatch::GetDispatchInfo
+					+ parity_scale_codec::Decode,
+				<R::Call as frame_support::dispatch::Dispatchable>::Origin:
+					From<Option<R::AccountId>>,
+				R: parachain_staking::Config
+					+ pallet_balances::Config
+					+ pallet_democracy::Config
+					+ pallet_crowdloan_rewards::Config
+					+ pallet_author_mapping::Config
+					+ pallet_precompile_pause::Config
+					+ pallet_evm::Config,
+				R::AccountId: From<sp_core::H160> + Into<sp_core::H160>,
+				R::Hash: From<sp_core::H256> + Into<sp_core::H256>,
+				R::BlockNumber: Into<sp_core::U256>,
+				<<R as parachain_staking::Config>::Currency as frame_support::traits::Currency<
+					R::AccountId,
+				>>::Balance: sp_std::convert::TryFrom<sp_core::U256>
+					+ Into<sp_core::U256>
+					+ sp_std::fmt::Debug,
+				<R as pallet_balances::Config>::Balance: sp_std::convert::TryFrom<sp_core::U256>
+					+ Into<sp_core::U256>
+					+ sp_std::fmt::Debug,
+				<<R as pallet_democracy::Config>::Currency as frame_support::traits::Currency<
+					R::AccountId,
+				>>::Balance: sp_std::convert::TryFrom<sp_core::U256>
+					+ Into<sp_core::U256>
+					+ sp_std::fmt::Debug,
+				<<R as pallet_crowdloan_rewards::Config>::RewardCurrency as
+					frame_support::traits::Currency<R::AccountId>>::Balance:
+					Into<sp_core::U256> + sp_std::fmt::Debug,
+				R::Call: From<parachain_staking::Call<R>>
+					+ From<pallet_balances::Call<R>>
+					+ From<pallet_democracy::Call<R>>
+					+ From<pallet_crowdloan_rewards::Call<R>>
+					+ From<pallet_author_mapping::Call<R>>,
+			}
+			disabled: [$($disabled),*],
+			paused: pallet_precompile_pause::Pallet::<R>::is_paused,
+			pause_check_gas: <
+				<R as pallet_evm::Config>::GasWeightMapping as pallet_evm::GasWeightMapping
+			>::weight_to_gas(
+				<<R as frame_system::Config>::DbWeight as frame_support::traits::Get<
+					frame_support::weights::RuntimeDbWeight,
+				>>::get()
+				.read,
+			),
+			pausable_from: 1024,
+			delegatable_below: 1024,
+			precompiles: {
+				// Ethereum precompiles :
+				1 => pallet_evm_precompile_simple::ECRecover,
+				2 => pallet_evm_precompile_simple::Sha256,
+				3 => pallet_evm_precompile_simple::Ripemd160,
+				4 => pallet_evm_precompile_simple::Identity,
+				5 => pallet_evm_precompile_modexp::Modexp,
+				6 => pallet_evm_precompile_bn128::Bn128Add,
+				7 => pallet_evm_precompile_bn128::Bn128Mul,
+				8 => pallet_evm_precompile_bn128::Bn128Pairing,
+				9 => pallet_evm_precompile_blake2::Blake2F,
+				// Non-Moonbeam specific nor Ethereum precompiles :
+				1024 => pallet_evm_precompile_sha3fips::Sha3FIPS256,
+				1025 => pallet_evm_precompile_dispatch::Dispatch<R>,
+				1026 => pallet_evm_precompile_simple::ECRecoverPublicKey,
+				// Moonbeam specific precompiles :
+				2048 => parachain_staking_precompiles::ParachainStakingWrapper<R>,
+				2049 => crowdloan_rewards_precompiles::CrowdloanRewardsWrapper<R>,
+				2050 => balances_erc20_precompiles::BalancesErc20Wrapper<R, $metadata>,
+				2051 => democracy_precompiles::DemocracyWrapper<R>,
+				2052 => author_mapping_precompiles::AuthorMappingWrapper<R>,
+				2053 => batch_precompiles::BatchPrecompile<R>,
+			}
+		}
+	};
+}
@@ -0,0 +1,279 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+use parity_scale_codec::{Decode, Encode};
+
+/// The type used to represent the kinds of proxying allowed.
+///
+/// New variants must be appended, as the index of a variant is its encoding.
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	Encode,
+	Decode,
+	Debug,
+	max_encoded_len::MaxEncodedLen,
+)]
+pub enum ProxyType {
+	/// All calls can be proxied. This is the trivial/most permissive filter.
+	Any,
+	/// Only extrinsics that do not transfer funds.
+	NonTransfer,
+	/// Only extrinsics related to governance (democracy and collectives).
+	Governance,
+	/// Only extrinsics related to staking.
+	Staking,
+	/// Allow to veto an announced proxy call.
+	CancelProxy,
+	/// Only extrinsics related to balances, such as transfers and vested transfers.
+	Balances,
+	/// Only extrinsics related to the mapping of author keys, so that collators can rotate
+	/// their keys with a hot proxy.
+	AuthorMapping,
+	/// Only the claim of crowdloan rewards.
+	CrowdloanClaim,
+}
+
+impl Default for ProxyType {
+	fn default() -> Self {
+		Self::Any
+	}
+}
+
+impl ProxyType {
+	/// Whether every call allowed by `o` is also allowed by `self`.
+	///
+	/// `Any` is above all the other types, and `NonTransfer` is above all the types that don't
+	/// transfer funds. The other types are unrelated.
+	pub fn is_superset(&self, o: &Self) -> bool {
+		match (self, o) {
+			(x, y) if x == y => true,
+			(ProxyType::Any, _) => true,
+			(_, ProxyType::Any) => false,
+			(
+				ProxyType::NonTransfer,
+				ProxyType::Governance
+				| ProxyType::Staking
+				| ProxyType::CancelProxy
+				| ProxyType::AuthorMapping
+				| ProxyType::CrowdloanClaim,
+			) => true,
+			_ => false,
+		}
+	}
+}
+
+/// Implements `InstanceFilter` for `ProxyType` over the `Call` of the runtime.
+///
+/// The filters are consistent with `ProxyType::is_superset`: a call allowed by a type is allowed
+/// by all the types above it.
+#[macro_export]
+macro_rules! impl_proxy_type_filter {
+	() => {
+		impl frame_support::traits::InstanceFilter<Call> for $crate::ProxyType {
+			fn filter(&self, c: &Call) -> bool {
+				match self {
+					$crate::ProxyType::Any => true,
+					$crate::ProxyType::NonTransfer => {
+						matches!(
+							c,
+							Call::System(..)
+								| Call::Timestamp(..) | Call::ParachainStaking(..)
+								| Call::Democracy(..) | Call::CouncilCollective(..)
+								| Call::TechComitteeCollective(..)
+								| Call::Utility(..) | Call::Proxy(..)
+								| Call::AuthorMapping(..)
+								| Call::CrowdloanRewards(pallet_crowdloan_rewards::Call::claim(..))
+								| Call::Identity(..) | Call::Multisig(..)
+								| Call::Vesting(pallet_vesting::Call::vest(..))
+								| Call::Vesting(pallet_vesting::Call::vest_other(..))
+						)
+					}
+					$crate::ProxyType::Governance => matches!(
+						c,
+						Call::Democracy(..)
+							| Call::CouncilCollective(..)
+							| Call::TechComitteeCollective(..)
+							| Call::Utility(..)
+					),
+					$crate::ProxyType::Staking => {
+						matches!(c, Call::ParachainStaking(..) | Call::Utility(..))
+					}
+					$crate::ProxyType::CancelProxy => {
+						matches!(c, Call::Proxy(pallet_proxy::Call::reject_announcement(..)))
+					}
+					$crate::ProxyType::Balances => {
+						matches!(
+							c,
+							Call::Balances(..)
+								| Call::Vesting(pallet_vesting::Call::vested_transfer(..))
+								| Call::Utility(..)
+						)
+					}
+					$crate::ProxyType::AuthorMapping => {
+						matches!(c, Call::AuthorMapping(..) | Call::Utility(..))
+					}
+					$crate::ProxyType::CrowdloanClaim => matches!(
+						c,
+						Call::CrowdloanRewards(pallet_crowdloan_rewards::Call::claim(..))
+							| Call::Utility(..)
+					),
+				}
+			}
+
+			fn is_superset(&self, o: &Self) -> bool {
+				$crate::ProxyType::is_superset(self, o)
+			}
+		}
+	};
+}
+
+/// Implements the config of `pallet_proxy`, with `ProxyType` and its filters.
+///
+/// The deposits are sized with the `currency::deposit` function of the runtime.
+#[macro_export]
+macro_rules! impl_proxy_config {
+	() => {
+		frame_support::parameter_types! {
+			// One storage item; key size 32, value size 8; .
+			pub const ProxyDepositBase: Balance = currency::deposit(1, 8);
+			// Additional storage item size of 21 bytes (20 bytes AccountId + 1 byte sizeof(ProxyType)).
+			pub const ProxyDepositFactor: Balance = currency::deposit(0, 21);
+			pub const MaxProxies: u16 = 32;
+			pub const AnnouncementDepositBase: Balance = currency::deposit(1, 8);
+			// Additional storage item size of 56 bytes:
+			// - 20 bytes AccountId
+			// - 32 bytes Hasher (Blake2256)
+			// - 4 bytes BlockNumber (u32)
+			pub const AnnouncementDepositFactor: Balance = currency::deposit(0, 56);
+			pub const MaxPending: u16 = 32;
+		}
+
+		pub use $crate::ProxyType;
+		$crate::impl_proxy_type_filter!();
+
+		impl pallet_proxy::Config for Runtime {
+			type Event = Event;
+			type Call = Call;
+			type Currency = Balances;
+			type ProxyType = ProxyType;
+			type ProxyDepositBase = ProxyDepositBase;
+			type ProxyDepositFactor = ProxyDepositFactor;
+			type MaxProxies = MaxProxies;
+			type WeightInfo = pallet_proxy::weights::SubstrateWeight<Runtime>;
+			type MaxPending = MaxPending;
+			type CallHasher = sp_runtime::traits::BlakeTwo256;
+			type AnnouncementDepositBase = AnnouncementDepositBase;
+			type AnnouncementDepositFactor = AnnouncementDepositFactor;
+		}
+	};
+}
+
+/// Implements the config of `pallet_identity`.
+///
+/// The deposits are sized with the `currency::deposit` function of the runtime. Slashed deposits
+/// go to the treasury, and more than half of the council, or root, can kill an identity or add a
+/// registrar.
+#[macro_export]
+macro_rules! impl_identity_config {
+	() => {
+		frame_support::parameter_types! {
+			// One storage item; the identity info is at most 258 bytes.
+			pub const BasicDeposit: Balance = currency::deposit(1, 258);
+			// Additional field of 66 bytes: a pair of 33 bytes `Data`.
+			pub const FieldDeposit: Balance = currency::deposit(0, 66);
+			// One storage item; value size of 53 bytes (20 bytes AccountId + 33 bytes `Data`).
+			pub const SubAccountDeposit: Balance = currency::deposit(1, 53);
+			pub const MaxSubAccounts: u32 = 100;
+			pub const MaxAdditionalFields: u32 = 100;
+			pub const MaxRegistrars: u32 = 20;
+		}
+
+		type IdentityForceOrigin = frame_system::EnsureOneOf<
+			AccountId,
+			frame_system::EnsureRoot<AccountId>,
+			pallet_collective::EnsureProportionMoreThan<
+				sp_core::u32_trait::_1,
+				sp_core::u32_trait::_2,
+				AccountId,
+				CouncilInstance,
+			>,
+		>;
+
+		impl pallet_identity::Config for Runtime {
+			type Event = Event;
+			type Currency = Balances;
+			type BasicDeposit = BasicDeposit;
+			type FieldDeposit = FieldDeposit;
+			type SubAccountDeposit = SubAccountDeposit;
+			type MaxSubAccounts = MaxSubAccounts;
+			type MaxAdditionalFields = MaxAdditionalFields;
+			type MaxRegistrars = MaxRegistrars;
+			type Slashed = Treasury;
+			type ForceOrigin = IdentityForceOrigin;
+			type RegistrarOrigin = IdentityForceOrigin;
+			type WeightInfo = pallet_identity::weights::SubstrateWeight<Runtime>;
+		}
+	};
+}
+
+/// Implements the config of `pallet_multisig`.
+///
+/// The deposits are sized with the `currency::deposit` function of the runtime.
+#[macro_export]
+macro_rules! impl_multisig_config {
+	() => {
+		frame_support::parameter_types! {
+			// One storage item; key size of 52 bytes (20 bytes AccountId + 32 bytes call hash)
+			// and value size of 44 bytes (8 bytes Timepoint + 16 bytes Balance + 20 bytes
+			// AccountId).
+			pub const MultisigDepositBase: Balance = currency::deposit(1, 96);
+			// Additional storage item size of 20 bytes AccountId per signatory.
+			pub const MultisigDepositFactor: Balance = currency::deposit(0, 20);
+			pub const MaxSignatories: u16 = 100;
+		}
+
+		impl pallet_multisig::Config for Runtime {
+			type Event = Event;
+			type Call = Call;
+			type Currency = Balances;
+			type DepositBase = MultisigDepositBase;
+			type DepositFactor = MultisigDepositFactor;
+			type MaxSignatories = MaxSignatories;
+			type WeightInfo = pallet_multisig::weights::SubstrateWeight<Runtime>;
+		}
+	};
+}
+
+/// Implements the config of `pallet_vesting`.
+///
+/// The runtime provides `MinVestedTransfer`, in its own currency unit.
+#[macro_export]
+macro_rules! impl_vesting_config {
+	() => {
+		impl pallet_vesting::Config for Runtime {
+			type Event = Event;
+			type Currency = Balances;
+			type BlockNumberToBalance = sp_runtime::traits::ConvertInto;
+			type MinVestedTransfer = MinVestedTransfer;
+			type WeightInfo = pallet_vesting::weights::SubstrateWeight<Runtime>;
+		}
+	};
+}
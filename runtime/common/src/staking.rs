@@ -0,0 +1,65 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+/// Implements the config of `parachain_staking`.
+///
+/// Rounds, commissions and the number of collators and nominators are the same for all the
+/// runtimes. The runtime provides the minimum stakes `MinCollatorStk`, `MinCollatorCandidateStk`
+/// and `MinNominatorStk`, which depend on the value of its token.
+#[macro_export]
+macro_rules! impl_staking_config {
+	() => {
+		frame_support::parameter_types! {
+			/// Minimum round length is 2 minutes (10 * 12 second block times)
+			pub const MinBlocksPerRound: u32 = 10;
+			/// Default BlocksPerRound is every hour (300 * 12 second block times)
+			pub const DefaultBlocksPerRound: u32 = 300;
+			/// Reward payments and collator exit requests are delayed by 2 hours (2 * 300 * block_time)
+			pub const BondDuration: u32 = 2;
+			/// Minimum 8 collators selected per round, default at genesis and minimum forever after
+			pub const MinSelectedCandidates: u32 = 8;
+			/// Maximum 10 nominators per collator
+			pub const MaxNominatorsPerCollator: u32 = 10;
+			/// Maximum 25 collators per nominator
+			pub const MaxCollatorsPerNominator: u32 = 25;
+			/// Default fixed percent a collator takes off the top of due rewards is 20%
+			pub const DefaultCollatorCommission: sp_runtime::Perbill =
+				sp_runtime::Perbill::from_percent(20);
+			/// Default percent of inflation set aside for parachain bond every round
+			pub const DefaultParachainBondReservePercent: sp_runtime::Percent =
+				sp_runtime::Percent::from_percent(30);
+		}
+
+		impl parachain_staking::Config for Runtime {
+			type Event = Event;
+			type Currency = Balances;
+			type MonetaryGovernanceOrigin = frame_system::EnsureRoot<AccountId>;
+			type MinBlocksPerRound = MinBlocksPerRound;
+			type DefaultBlocksPerRound = DefaultBlocksPerRound;
+			type BondDuration = BondDuration;
+			type MinSelectedCandidates = MinSelectedCandidates;
+			type MaxNominatorsPerCollator = MaxNominatorsPerCollator;
+			type MaxCollatorsPerNominator = MaxCollatorsPerNominator;
+			type DefaultCollatorCommission = DefaultCollatorCommission;
+			type DefaultParachainBondReservePercent = DefaultParachainBondReservePercent;
+			type MinCollatorStk = MinCollatorStk;
+			type MinCollatorCandidateStk = MinCollatorCandidateStk;
+			type MinNomination = MinNominatorStk;
+			type MinNominatorStk = MinNominatorStk;
+			type WeightInfo = parachain_staking::weights::SubstrateWeight<Runtime>;
+		}
+	};
+}
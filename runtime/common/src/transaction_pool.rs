@@ -0,0 +1,107 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Ordering of the Ethereum transactions in the transaction pool.
+//!
+//! `pallet_ethereum` tags an Ethereum transaction by its sender and nonce, so a transaction is
+//! pending when all the previous nonces of its sender are either executed or pending, and queued
+//! otherwise, like in geth. Its priority is its gas price, so that the pool prefers the best
+//! paying transactions. The price bump required to replace a pending transaction is enforced by
+//! the transaction pool of the node.
+
+use sp_core::U256;
+use sp_runtime::transaction_validity::TransactionPriority;
+
+/// Declares `validate_pool_transaction`, backing `validate_transaction` of the
+/// `TaggedTransactionQueue` runtime API.
+///
+/// Calls rejected by the `BaseCallFilter` of the runtime fail with `filtered_call`. Ethereum
+/// transactions paying less than the current gas price are rejected, and the valid ones are
+/// prioritized by gas price.
+#[macro_export]
+macro_rules! impl_validate_pool_transaction {
+	{ filtered_call: $filtered_call:expr $(,)? } => {
+		/// Validates a transaction before it enters the transaction pool.
+		fn validate_pool_transaction(
+			source: sp_runtime::transaction_validity::TransactionSource,
+			tx: <Block as sp_runtime::traits::Block>::Extrinsic,
+			block_hash: <Block as sp_runtime::traits::Block>::Hash,
+		) -> sp_runtime::transaction_validity::TransactionValidity {
+			use frame_support::traits::Filter;
+			use pallet_evm::FeeCalculator;
+
+			// Ethereum transactions paying less than the current gas price would fail if inserted.
+			let gas_price = match tx.function {
+				Call::Ethereum(pallet_ethereum::Call::transact(ref transaction)) => {
+					let min_gas_price =
+						<Runtime as pallet_evm::Config>::FeeCalculator::min_gas_price();
+					if transaction.gas_price < min_gas_price {
+						return sp_runtime::transaction_validity::InvalidTransaction::Payment
+							.into();
+					}
+					Some(transaction.gas_price)
+				}
+				_ => None,
+			};
+
+			// Filtered calls should not enter the tx pool as they'll fail if inserted.
+			if !<Runtime as frame_system::Config>::BaseCallFilter::filter(&tx.function) {
+				return $filtered_call.into();
+			}
+
+			let validity = Executive::validate_transaction(source, tx, block_hash);
+			// Ethereum transactions are ordered by gas price, like in Ethereum
+			match gas_price {
+				Some(gas_price) => validity.map(|mut valid| {
+					valid.priority = $crate::gas_price_priority(gas_price);
+					valid
+				}),
+				None => validity,
+			}
+		}
+	};
+}
+
+/// The priority of an Ethereum transaction paying `gas_price`: the gas price itself, saturated
+/// to the priority range.
+pub fn gas_price_priority(gas_price: U256) -> TransactionPriority {
+	gas_price
+		.min(U256::from(TransactionPriority::MAX))
+		.low_u64()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const GWEI: u64 = 1_000_000_000;
+
+	#[test]
+	fn priority_is_the_gas_price() {
+		assert_eq!(gas_price_priority(U256::zero()), 0);
+		assert_eq!(gas_price_priority(U256::from(GWEI)), GWEI);
+		assert_eq!(gas_price_priority(U256::from(GWEI + 1)), GWEI + 1);
+	}
+
+	#[test]
+	fn priority_saturates() {
+		assert_eq!(
+			gas_price_priority(U256::from(TransactionPriority::MAX) + 1),
+			TransactionPriority::MAX
+		);
+		assert_eq!(gas_price_priority(U256::MAX), TransactionPriority::MAX);
+	}
+}
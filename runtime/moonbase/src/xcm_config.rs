@@ -0,0 +1,250 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! XCM configuration of the Moonbase runtime.
+//!
+//! Moonbase is the reserve of its native token: it can be reserve transferred to sibling
+//! parachains and withdrawn back from them. The relay chain token is held in `RelayBalances`, the
+//! relay chain being its reserve. Remote accounts are mapped to H160 accounts, so that XCM
+//! messages can act on behalf of Ethereum accounts.
+//!
+//! The asset transactor, the origin converter and the trader are declared over the pallets they
+//! use, so that the XCM simulator tests run them on a mock runtime.
+
+use crate::{
+	AccountId, Balance, Balances, Call, DealWithFees, DmpQueue, Event, FeesBurnPercent, MaxLocks,
+	MaxReserves, Origin, ParachainInfo, ParachainSystem, RelayBalances, Runtime, XcmpQueue,
+	MAXIMUM_BLOCK_WEIGHT,
+};
+use frame_support::{
+	match_type, parameter_types,
+	traits::{All, Get, OriginTrait},
+	weights::{IdentityFee, Weight},
+};
+use frame_system::EnsureRoot;
+use polkadot_parachain::primitives::Sibling;
+use sp_core::H160;
+use sp_std::{convert::TryInto, marker::PhantomData, prelude::*};
+use xcm::v0::{Junction, MultiLocation, NetworkId};
+use xcm_builder::{
+	AccountKey20Aliases, AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom, CurrencyAdapter,
+	EnsureXcmOrigin, FixedWeightBounds, IsConcrete, LocationInverter, NativeAsset,
+	ParentAsSuperuser, ParentIsDefault, RelayChainAsNative, SiblingParachainAsNative,
+	SiblingParachainConvertsVia, SignedAccountKey20AsNative, SovereignSignedViaLocation,
+	TakeWeightCredit, UsingComponents,
+};
+use xcm_executor::{traits::Convert, XcmExecutor};
+
+parameter_types! {
+	pub const RelayNetwork: NetworkId = NetworkId::Any;
+	pub RelayChainOrigin: Origin = cumulus_pallet_xcm::Origin::Relay.into();
+	/// Location of this parachain, as seen from the relay chain.
+	pub Ancestry: MultiLocation = Junction::Parachain(ParachainInfo::parachain_id().into()).into();
+	/// Location of the native token. It is expressed from the relay chain so that it reads the
+	/// same from this parachain and from its siblings.
+	pub SelfReserve: MultiLocation = MultiLocation::X2(
+		Junction::Parent,
+		Junction::Parachain(ParachainInfo::parachain_id().into()),
+	);
+	/// Location of the relay chain token.
+	pub const RelayLocation: MultiLocation = MultiLocation::X1(Junction::Parent);
+}
+
+/// Converts a location into the H160 account that acts on its behalf.
+pub type LocationToAccountId = (
+	// The relay chain has the default account.
+	ParentIsDefault<AccountId>,
+	// Sibling parachains have their sovereign account.
+	SiblingParachainConvertsVia<Sibling, AccountId>,
+	// Ethereum accounts are used as is.
+	AccountKey20Aliases<RelayNetwork, AccountId>,
+);
+
+/// Deposits and withdraws the native token, whose location is `SelfReserve`, in `Currency` and
+/// the relay chain token in `RelayCurrency`.
+pub type AssetTransactorOf<Currency, RelayCurrency, SelfReserve> = (
+	// The native token can't be teleported, so its issuance isn't checked.
+	CurrencyAdapter<Currency, IsConcrete<SelfReserve>, LocationToAccountId, AccountId, ()>,
+	// The relay chain token is only reserve transferred, so its issuance isn't checked either.
+	CurrencyAdapter<RelayCurrency, IsConcrete<RelayLocation>, LocationToAccountId, AccountId, ()>,
+);
+
+/// Deposits and withdraws the native token, of which this parachain is the reserve, and the relay
+/// chain token.
+pub type LocalAssetTransactor = AssetTransactorOf<Balances, RelayBalances, SelfReserve>;
+
+/// Converts the origin of a `Transact` into a dispatch origin of `Origin`.
+pub type XcmOriginToTransactDispatchOriginOf<Origin, RelayChainOrigin> = (
+	// Sovereign accounts dispatch as signed origins.
+	SovereignSignedViaLocation<LocationToAccountId, Origin>,
+	// The relay chain dispatches as the `Relay` origin of `cumulus_pallet_xcm`.
+	RelayChainAsNative<RelayChainOrigin, Origin>,
+	// Siblings dispatch as the `SiblingParachain` origin of `cumulus_pallet_xcm`.
+	SiblingParachainAsNative<cumulus_pallet_xcm::Origin, Origin>,
+	// The relay chain governance is root.
+	ParentAsSuperuser<Origin>,
+	// Ethereum accounts dispatch as their signed origin.
+	SignedAccountKey20AsNative<RelayNetwork, Origin>,
+);
+
+/// Converts the origin of a `Transact` into a dispatch origin.
+pub type XcmOriginToTransactDispatchOrigin =
+	XcmOriginToTransactDispatchOriginOf<Origin, RelayChainOrigin>;
+
+parameter_types! {
+	/// Weight of an XCM instruction.
+	pub const UnitWeightCost: Weight = 200_000_000;
+}
+
+// The relay chain and its governance bodies.
+match_type! {
+	pub type ParentOrParentsPlurality: impl Contains<MultiLocation> = {
+		MultiLocation::X1(Junction::Parent) |
+		MultiLocation::X2(Junction::Parent, Junction::Plurality { .. })
+	};
+}
+
+/// Messages are executed when they pay for it, or when they come from the relay chain.
+pub type Barrier = (
+	TakeWeightCredit,
+	AllowTopLevelPaidExecutionFrom<All<MultiLocation>>,
+	AllowUnpaidExecutionFrom<ParentOrParentsPlurality>,
+);
+
+/// Buys execution with the native token held in `Currency`, at the price of transaction fees,
+/// and hands the fees to `OnUnbalanced`.
+pub type TraderOf<Currency, SelfReserve, OnUnbalanced> =
+	UsingComponents<IdentityFee<Balance>, SelfReserve, AccountId, Currency, OnUnbalanced>;
+
+/// Buys execution with the native token, whose fees are handled as transaction fees are.
+pub type Trader = TraderOf<Balances, SelfReserve, DealWithFees<Runtime, FeesBurnPercent>>;
+
+/// Sends messages to the relay chain through UMP and to siblings through XCMP.
+pub type XcmRouter = (
+	cumulus_primitives_utility::ParentAsUmp<ParachainSystem>,
+	XcmpQueue,
+);
+
+pub struct XcmConfig;
+impl xcm_executor::Config for XcmConfig {
+	type Call = Call;
+	type XcmSender = XcmRouter;
+	type AssetTransactor = LocalAssetTransactor;
+	type OriginConverter = XcmOriginToTransactDispatchOrigin;
+	// Assets are only accepted from their own reserve.
+	type IsReserve = NativeAsset;
+	type IsTeleporter = ();
+	type LocationInverter = LocationInverter<Ancestry>;
+	type Barrier = Barrier;
+	type Weigher = FixedWeightBounds<UnitWeightCost, Call>;
+	// Execution is paid in the native token, as transaction fees are.
+	type Trader = Trader;
+	type ResponseHandler = ();
+}
+
+/// Converts a signed origin into the `AccountKey20` location of its account.
+pub struct SignedToAccountId20<Origin, AccountId, Network>(
+	PhantomData<(Origin, AccountId, Network)>,
+);
+impl<Origin: OriginTrait + Clone, AccountId: Into<H160>, Network: Get<NetworkId>>
+	Convert<Origin, MultiLocation> for SignedToAccountId20<Origin, AccountId, Network>
+where
+	Origin::PalletsOrigin: From<frame_system::RawOrigin<AccountId>>
+		+ TryInto<frame_system::RawOrigin<AccountId>, Error = Origin::PalletsOrigin>,
+{
+	fn convert(o: Origin) -> Result<MultiLocation, Origin> {
+		o.try_with_caller(|caller| match caller.try_into() {
+			Ok(frame_system::RawOrigin::Signed(who)) => Ok(Junction::AccountKey20 {
+				key: who.into().0,
+				network: Network::get(),
+			}
+			.into()),
+			Ok(other) => Err(other.into()),
+			Err(other) => Err(other),
+		})
+	}
+}
+
+/// Converts a local origin into the location sending a message.
+pub type LocalOriginToLocation = SignedToAccountId20<Origin, AccountId, RelayNetwork>;
+
+impl pallet_xcm::Config for Runtime {
+	type Event = Event;
+	type SendXcmOrigin = EnsureXcmOrigin<Origin, LocalOriginToLocation>;
+	type XcmRouter = XcmRouter;
+	type ExecuteXcmOrigin = EnsureXcmOrigin<Origin, LocalOriginToLocation>;
+	type XcmExecuteFilter = All<(MultiLocation, xcm::v0::Xcm<Call>)>;
+	type XcmExecutor = XcmExecutor<XcmConfig>;
+	// The executor doesn't trust any teleporter, so teleports are rejected anyway.
+	type XcmTeleportFilter = All<(MultiLocation, Vec<xcm::v0::MultiAsset>)>;
+	type XcmReserveTransferFilter = All<(MultiLocation, Vec<xcm::v0::MultiAsset>)>;
+	type Weigher = FixedWeightBounds<UnitWeightCost, Call>;
+}
+
+parameter_types! {
+	pub const RelayExistentialDeposit: Balance = 1;
+}
+
+/// The relay chain token, of which the relay chain is the reserve.
+impl pallet_balances::Config<pallet_balances::Instance1> for Runtime {
+	type MaxReserves = MaxReserves;
+	type ReserveIdentifier = [u8; 4];
+	type MaxLocks = MaxLocks;
+	type Balance = Balance;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = RelayExistentialDeposit;
+	type AccountStore = frame_system::StorageMapShim<
+		pallet_balances::Account<Runtime, pallet_balances::Instance1>,
+		frame_system::Provider<Runtime>,
+		AccountId,
+		pallet_balances::AccountData<Balance>,
+	>;
+	type WeightInfo = pallet_balances::weights::SubstrateWeight<Runtime>;
+}
+
+impl cumulus_pallet_xcm::Config for Runtime {
+	type Event = Event;
+	type XcmExecutor = XcmExecutor<XcmConfig>;
+}
+
+impl cumulus_pallet_xcmp_queue::Config for Runtime {
+	type Event = Event;
+	type XcmExecutor = XcmExecutor<XcmConfig>;
+	type ChannelInfo = ParachainSystem;
+}
+
+impl cumulus_pallet_dmp_queue::Config for Runtime {
+	type Event = Event;
+	type XcmExecutor = XcmExecutor<XcmConfig>;
+	type ExecuteOverweightOrigin = EnsureRoot<AccountId>;
+}
+
+parameter_types! {
+	pub const ReservedXcmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT / 4;
+	pub const ReservedDmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT / 4;
+}
+
+impl cumulus_pallet_parachain_system::Config for Runtime {
+	type Event = Event;
+	type OnValidationData = ();
+	type SelfParaId = ParachainInfo;
+	type DmpMessageHandler = DmpQueue;
+	type ReservedDmpWeight = ReservedDmpWeight;
+	type OutboundXcmpMessageSource = XcmpQueue;
+	type XcmpMessageHandler = XcmpQueue;
+	type ReservedXcmpWeight = ReservedXcmpWeight;
+}
@@ -23,20 +23,25 @@ use evm::{executor::PrecompileOutput, ExitError, ExitSucceed};
 use frame_support::{
 	assert_noop, assert_ok,
 	dispatch::Dispatchable,
-	traits::{fungible::Inspect, PalletInfo, StorageInfo, StorageInfoTrait},
+	storage::unhashed,
+	traits::{
+		fungible::Inspect, GetPalletVersion, OnRuntimeUpgrade, PalletInfo, PalletVersion,
+		StorageInfo, StorageInfoTrait,
+	},
 	weights::{DispatchClass, Weight},
 	StorageHasher, Twox128,
 };
 use moonbase_runtime::{
-	currency::UNIT, AccountId, Balances, BlockWeights, Call, CrowdloanRewards, Event,
-	ParachainStaking, Precompiles, Runtime, System,
+	currency::UNIT, AccountId, AuthorMapping, Balances, BlockWeights, Call, CrowdloanRewards,
+	Event, ParachainStaking, PrecompilePause, Precompiles, Runtime, System,
 };
 use nimbus_primitives::NimbusId;
 use pallet_evm::PrecompileSet;
 use pallet_transaction_payment::Multiplier;
 use parachain_staking::{Bond, NominatorAdded};
+use parachain_staking_precompiles::weights::WeightInfo;
 use sha3::{Digest, Keccak256};
-use sp_core::{Public, H160, U256};
+use sp_core::{Public, H160, H256, U256};
 use sp_runtime::{
 	traits::{Convert, One},
 	DispatchError,
@@ -81,6 +86,15 @@ fn verify_pallet_prefixes() {
 	is_pallet_prefix::<moonbase_runtime::AuthorFilter>("AuthorFilter");
 	is_pallet_prefix::<moonbase_runtime::CrowdloanRewards>("CrowdloanRewards");
 	is_pallet_prefix::<moonbase_runtime::AuthorMapping>("AuthorMapping");
+	is_pallet_prefix::<moonbase_runtime::PrecompilePause>("PrecompilePause");
+	is_pallet_prefix::<moonbase_runtime::BaseFee>("BaseFee");
+	is_pallet_prefix::<moonbase_runtime::XcmpQueue>("XcmpQueue");
+	is_pallet_prefix::<moonbase_runtime::DmpQueue>("DmpQueue");
+	is_pallet_prefix::<moonbase_runtime::PolkadotXcm>("PolkadotXcm");
+	is_pallet_prefix::<moonbase_runtime::Identity>("Identity");
+	is_pallet_prefix::<moonbase_runtime::Multisig>("Multisig");
+	is_pallet_prefix::<moonbase_runtime::Vesting>("Vesting");
+	is_pallet_prefix::<moonbase_runtime::RelayBalances>("RelayBalances");
 	let prefix = |pallet_name, storage_name| {
 		let mut res = [0u8; 32];
 		res[0..16].copy_from_slice(&Twox128::hash(pallet_name));
@@ -327,6 +341,257 @@ fn transfer_through_evm_to_stake() {
 		});
 }
 
+#[test]
+fn evm_fees_are_burned_and_sent_to_treasury() {
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 2_000 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let treasury = moonbase_runtime::Treasury::account_id();
+			let issuance = Balances::total_issuance();
+
+			let gas_price: U256 = 1_000_000_000.into();
+			assert_ok!(Call::EVM(pallet_evm::Call::<Runtime>::call(
+				AccountId::from(ALICE),
+				AccountId::from(BOB),
+				Vec::new(),
+				UNIT.into(),
+				100_000u64,
+				gas_price,
+				None
+			))
+			.dispatch(<Runtime as frame_system::Config>::Origin::root()));
+
+			// A transfer uses 21_000 gas, 80% of the fee is burned and 20% goes to the treasury
+			let fee = 21_000 * 1_000_000_000;
+			assert_eq!(
+				Balances::free_balance(AccountId::from(ALICE)),
+				2_000 * UNIT - UNIT - fee
+			);
+			assert_eq!(Balances::free_balance(AccountId::from(BOB)), UNIT);
+			assert_eq!(Balances::free_balance(&treasury), fee / 5);
+			assert_eq!(Balances::total_issuance(), issuance - fee * 4 / 5);
+		});
+}
+
+#[test]
+fn proxy_type_lattice() {
+	use moonbase_runtime::ProxyType;
+
+	for proxy_type in &[
+		ProxyType::NonTransfer,
+		ProxyType::Governance,
+		ProxyType::Staking,
+		ProxyType::CancelProxy,
+		ProxyType::Balances,
+		ProxyType::AuthorMapping,
+		ProxyType::CrowdloanClaim,
+	] {
+		assert!(ProxyType::Any.is_superset(proxy_type));
+		assert!(!proxy_type.is_superset(&ProxyType::Any));
+		assert!(proxy_type.is_superset(proxy_type));
+	}
+	assert!(ProxyType::NonTransfer.is_superset(&ProxyType::Governance));
+	assert!(ProxyType::NonTransfer.is_superset(&ProxyType::AuthorMapping));
+	assert!(ProxyType::NonTransfer.is_superset(&ProxyType::CrowdloanClaim));
+	assert!(!ProxyType::NonTransfer.is_superset(&ProxyType::Balances));
+	assert!(!ProxyType::Balances.is_superset(&ProxyType::AuthorMapping));
+	assert!(!ProxyType::Staking.is_superset(&ProxyType::Governance));
+}
+
+#[test]
+fn proxy_types_filter_calls() {
+	use frame_support::traits::InstanceFilter;
+	use moonbase_runtime::ProxyType;
+
+	let transfer = Call::Balances(pallet_balances::Call::transfer(AccountId::from(BOB), UNIT));
+	let clear_association = Call::AuthorMapping(pallet_author_mapping::Call::clear_association(
+		NimbusId::from_slice(&ALICE_NIMBUS),
+	));
+	let claim = Call::CrowdloanRewards(pallet_crowdloan_rewards::Call::claim());
+	let update_reward_address = Call::CrowdloanRewards(
+		pallet_crowdloan_rewards::Call::update_reward_address(AccountId::from(BOB)),
+	);
+
+	assert!(ProxyType::Balances.filter(&transfer));
+	assert!(!ProxyType::Balances.filter(&clear_association));
+
+	assert!(ProxyType::AuthorMapping.filter(&clear_association));
+	assert!(!ProxyType::AuthorMapping.filter(&transfer));
+	assert!(!ProxyType::AuthorMapping.filter(&claim));
+
+	assert!(ProxyType::CrowdloanClaim.filter(&claim));
+	assert!(!ProxyType::CrowdloanClaim.filter(&update_reward_address));
+
+	assert!(ProxyType::NonTransfer.filter(&clear_association));
+	assert!(ProxyType::NonTransfer.filter(&claim));
+	assert!(!ProxyType::NonTransfer.filter(&transfer));
+	assert!(!ProxyType::NonTransfer.filter(&update_reward_address));
+
+	let vest = Call::Vesting(pallet_vesting::Call::vest());
+	let vested_transfer = Call::Vesting(pallet_vesting::Call::vested_transfer(
+		AccountId::from(BOB),
+		pallet_vesting::VestingInfo {
+			locked: UNIT,
+			per_block: UNIT,
+			starting_block: 0,
+		},
+	));
+	let clear_identity = Call::Identity(pallet_identity::Call::clear_identity());
+
+	assert!(ProxyType::NonTransfer.filter(&vest));
+	assert!(ProxyType::NonTransfer.filter(&clear_identity));
+	assert!(!ProxyType::NonTransfer.filter(&vested_transfer));
+	assert!(ProxyType::Balances.filter(&vested_transfer));
+	assert!(!ProxyType::Balances.filter(&clear_identity));
+}
+
+#[test]
+fn set_and_clear_identity() {
+	use moonbase_runtime::{BasicDeposit, Identity};
+
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 1_000 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let info = pallet_identity::IdentityInfo {
+				display: pallet_identity::Data::Keccak256([1u8; 32]),
+				..Default::default()
+			};
+			assert_ok!(
+				Call::Identity(pallet_identity::Call::set_identity(Box::new(info)))
+					.dispatch(origin_of(AccountId::from(ALICE)))
+			);
+			assert!(Identity::identity(AccountId::from(ALICE)).is_some());
+			assert_eq!(
+				Balances::reserved_balance(AccountId::from(ALICE)),
+				BasicDeposit::get()
+			);
+
+			assert_ok!(Call::Identity(pallet_identity::Call::clear_identity())
+				.dispatch(origin_of(AccountId::from(ALICE))));
+			assert!(Identity::identity(AccountId::from(ALICE)).is_none());
+			assert_eq!(Balances::reserved_balance(AccountId::from(ALICE)), 0);
+		});
+}
+
+#[test]
+fn multisig_executes_call_once_threshold_is_reached() {
+	use frame_support::weights::GetDispatchInfo;
+	use moonbase_runtime::{Multisig, MultisigDepositBase, MultisigDepositFactor};
+	use parity_scale_codec::Encode;
+
+	let signatories = vec![AccountId::from(ALICE), AccountId::from(BOB)];
+	let multisig = Multisig::multi_account_id(&signatories, 2);
+	ExtBuilder::default()
+		.with_balances(vec![
+			(AccountId::from(ALICE), 1_000 * UNIT),
+			(AccountId::from(BOB), 1_000 * UNIT),
+			(multisig, 1_000 * UNIT),
+		])
+		.build()
+		.execute_with(|| {
+			let call = Call::Balances(pallet_balances::Call::transfer(
+				AccountId::from(CHARLIE),
+				100 * UNIT,
+			));
+			let call_weight = call.get_dispatch_info().weight;
+			let call_hash = sp_io::hashing::blake2_256(&call.encode());
+
+			assert_ok!(Call::Multisig(pallet_multisig::Call::approve_as_multi(
+				2,
+				vec![AccountId::from(BOB)],
+				None,
+				call_hash,
+				0,
+			))
+			.dispatch(origin_of(AccountId::from(ALICE))));
+			assert_eq!(
+				Balances::reserved_balance(AccountId::from(ALICE)),
+				MultisigDepositBase::get() + 2 * MultisigDepositFactor::get()
+			);
+			assert_eq!(Balances::free_balance(AccountId::from(CHARLIE)), 0);
+
+			assert_ok!(Call::Multisig(pallet_multisig::Call::as_multi(
+				2,
+				vec![AccountId::from(ALICE)],
+				Some(Multisig::timepoint()),
+				call.encode(),
+				false,
+				call_weight,
+			))
+			.dispatch(origin_of(AccountId::from(BOB))));
+			assert_eq!(Balances::free_balance(AccountId::from(CHARLIE)), 100 * UNIT);
+			assert_eq!(Balances::free_balance(&multisig), 900 * UNIT);
+			assert_eq!(Balances::reserved_balance(AccountId::from(ALICE)), 0);
+		});
+}
+
+#[test]
+fn genesis_vesting_schedule_unlocks_over_time() {
+	use frame_support::traits::VestingSchedule;
+	use moonbase_runtime::Vesting;
+
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 100 * UNIT)])
+		// Half of the balance vests linearly from block 10 to block 20
+		.with_vesting(vec![(AccountId::from(ALICE), 10, 10, 50 * UNIT)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(
+				Vesting::vesting_balance(&AccountId::from(ALICE)),
+				Some(50 * UNIT)
+			);
+			assert_eq!(Balances::usable_balance(&AccountId::from(ALICE)), 50 * UNIT);
+
+			System::set_block_number(15);
+			assert_ok!(Call::Vesting(pallet_vesting::Call::vest())
+				.dispatch(origin_of(AccountId::from(ALICE))));
+			assert_eq!(
+				Vesting::vesting_balance(&AccountId::from(ALICE)),
+				Some(25 * UNIT)
+			);
+			assert_eq!(Balances::usable_balance(&AccountId::from(ALICE)), 75 * UNIT);
+
+			System::set_block_number(20);
+			assert_ok!(Call::Vesting(pallet_vesting::Call::vest())
+				.dispatch(origin_of(AccountId::from(ALICE))));
+			assert_eq!(Vesting::vesting_balance(&AccountId::from(ALICE)), None);
+			assert_eq!(
+				Balances::usable_balance(&AccountId::from(ALICE)),
+				100 * UNIT
+			);
+		});
+}
+
+#[test]
+fn vested_transfer_locks_transferred_funds() {
+	use frame_support::traits::VestingSchedule;
+	use moonbase_runtime::Vesting;
+
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(BOB), 1_000 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let vested_transfer = Call::Vesting(pallet_vesting::Call::vested_transfer(
+				AccountId::from(CHARLIE),
+				pallet_vesting::VestingInfo {
+					locked: 10 * UNIT,
+					per_block: UNIT,
+					starting_block: 10,
+				},
+			));
+
+			assert_ok!(vested_transfer.dispatch(origin_of(AccountId::from(BOB))));
+			assert_eq!(Balances::free_balance(AccountId::from(CHARLIE)), 10 * UNIT);
+			assert_eq!(
+				Vesting::vesting_balance(&AccountId::from(CHARLIE)),
+				Some(10 * UNIT)
+			);
+			assert_eq!(Balances::usable_balance(&AccountId::from(CHARLIE)), 0);
+		});
+}
+
 #[test]
 fn reward_block_authors() {
 	ExtBuilder::default()
@@ -578,6 +843,11 @@ fn join_candidates_via_precompile() {
 					1000 * UNIT,
 					1000 * UNIT,
 				)),
+				staking_precompile_log_event(
+					b"CandidateJoined(address,uint256)",
+					vec![ALICE],
+					Some(1_000 * UNIT),
+				),
 				Event::EVM(pallet_evm::Event::<Runtime>::Executed(
 					staking_precompile_address,
 				)),
@@ -630,6 +900,7 @@ fn leave_candidates_via_precompile() {
 					AccountId::from(ALICE),
 					3,
 				)),
+				staking_precompile_log_event(b"CandidateLeft(address)", vec![ALICE], None),
 				Event::EVM(pallet_evm::Event::<Runtime>::Executed(
 					staking_precompile_address,
 				)),
@@ -681,6 +952,7 @@ fn go_online_offline_via_precompile() {
 					1,
 					AccountId::from(ALICE),
 				)),
+				staking_precompile_log_event(b"CandidateWentOffline(address)", vec![ALICE], None),
 				Event::EVM(pallet_evm::Event::<Runtime>::Executed(
 					staking_precompile_address,
 				)),
@@ -715,6 +987,7 @@ fn go_online_offline_via_precompile() {
 					1,
 					AccountId::from(ALICE),
 				)),
+				staking_precompile_log_event(b"CandidateBackOnline(address)", vec![ALICE], None),
 				Event::EVM(pallet_evm::Event::<Runtime>::Executed(
 					staking_precompile_address,
 				)),
@@ -775,6 +1048,11 @@ fn candidate_bond_more_less_via_precompile() {
 					1_000 * UNIT,
 					2_000 * UNIT,
 				)),
+				staking_precompile_log_event(
+					b"CandidateBondedMore(address,uint256)",
+					vec![ALICE],
+					Some(1_000 * UNIT),
+				),
 				Event::EVM(pallet_evm::Event::<Runtime>::Executed(
 					staking_precompile_address,
 				)),
@@ -817,6 +1095,11 @@ fn candidate_bond_more_less_via_precompile() {
 					2_000 * UNIT,
 					1_500 * UNIT,
 				)),
+				staking_precompile_log_event(
+					b"CandidateBondedLess(address,uint256)",
+					vec![ALICE],
+					Some(500 * UNIT),
+				),
 				Event::EVM(pallet_evm::Event::<Runtime>::Executed(
 					staking_precompile_address,
 				)),
@@ -890,6 +1173,11 @@ fn nominate_via_precompile() {
 						new_total: 2000 * UNIT,
 					},
 				)),
+				staking_precompile_log_event(
+					b"Nominated(address,address,uint256)",
+					vec![BOB, ALICE],
+					Some(1_000 * UNIT),
+				),
 				Event::EVM(pallet_evm::Event::<Runtime>::Executed(
 					staking_precompile_address,
 				)),
@@ -905,6 +1193,47 @@ fn nominate_via_precompile() {
 		});
 }
 
+#[test]
+fn nominate_below_min_via_precompile_reports_pallet_error() {
+	ExtBuilder::default()
+		.with_balances(vec![
+			(AccountId::from(ALICE), 3_000 * UNIT),
+			(AccountId::from(BOB), 3_000 * UNIT),
+		])
+		.with_collators(vec![(AccountId::from(ALICE), 1_000 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let staking_precompile_address = H160::from_low_u64_be(2048);
+
+			// Bob tries to nominate Alice with less than the minimum nominator stake
+			let nomination_amount: U256 = UNIT.into();
+
+			// Construct the call data (selector, collator, nomination amount, counts)
+			let mut call_data = Vec::<u8>::from([0u8; 132]);
+			call_data[0..4].copy_from_slice(
+				&Keccak256::digest(b"nominate(address,uint256,uint256,uint256)")[0..4],
+			);
+			call_data[16..36].copy_from_slice(&ALICE);
+			nomination_amount.to_big_endian(&mut call_data[36..68]);
+
+			let context = evm::Context {
+				address: staking_precompile_address,
+				caller: H160::from(BOB),
+				apparent_value: From::from(0),
+			};
+
+			// The error names the pallet and the error that made the dispatch fail
+			assert_eq!(
+				Precompiles::execute(staking_precompile_address, &call_data, None, &context),
+				Some(Err(ExitError::Other(
+					"Parachain staking call via EVM failed: ParachainStaking::NomBondBelowMin"
+						.into()
+				)))
+			);
+			assert!(!ParachainStaking::is_nominator(&AccountId::from(BOB)));
+		});
+}
+
 #[test]
 fn leave_nominators_via_precompile() {
 	ExtBuilder::default()
@@ -977,6 +1306,7 @@ fn leave_nominators_via_precompile() {
 					AccountId::from(CHARLIE),
 					1_000 * UNIT,
 				)),
+				staking_precompile_log_event(b"NominatorLeft(address)", vec![CHARLIE], None),
 				Event::EVM(pallet_evm::Event::<Runtime>::Executed(
 					staking_precompile_address,
 				)),
@@ -1050,6 +1380,11 @@ fn revoke_nomination_via_precompile() {
 					500 * UNIT,
 					1_000 * UNIT,
 				)),
+				staking_precompile_log_event(
+					b"NominationRevoked(address,address)",
+					vec![CHARLIE, ALICE],
+					None,
+				),
 				Event::EVM(pallet_evm::Event::<Runtime>::Executed(
 					staking_precompile_address,
 				)),
@@ -1120,6 +1455,11 @@ fn nominator_bond_more_less_via_precompile() {
 					true,
 					2_000 * UNIT,
 				)),
+				staking_precompile_log_event(
+					b"NominationIncreased(address,address,uint256)",
+					vec![BOB, ALICE],
+					Some(500 * UNIT),
+				),
 				Event::EVM(pallet_evm::Event::<Runtime>::Executed(
 					staking_precompile_address,
 				)),
@@ -1165,6 +1505,11 @@ fn nominator_bond_more_less_via_precompile() {
 					true,
 					1_500 * UNIT,
 				)),
+				staking_precompile_log_event(
+					b"NominationDecreased(address,address,uint256)",
+					vec![BOB, ALICE],
+					Some(500 * UNIT),
+				),
 				Event::EVM(pallet_evm::Event::<Runtime>::Executed(
 					staking_precompile_address,
 				)),
@@ -1213,7 +1558,7 @@ fn is_nominator_via_precompile() {
 			let expected_true_result = Some(Ok(PrecompileOutput {
 				exit_status: ExitSucceed::Returned,
 				output: expected_bytes,
-				cost: 1000,
+				cost: staking_view_cost(StakingViewWeights::is_nominator(1)),
 				logs: Default::default(),
 			}));
 
@@ -1223,7 +1568,7 @@ fn is_nominator_via_precompile() {
 					staking_precompile_address,
 					&bob_input_data,
 					None, // target_gas is not necessary right now because consumed none now
-					&evm_test_context(),
+					&evm_test_context(staking_precompile_address),
 				),
 				expected_true_result
 			);
@@ -1239,7 +1584,7 @@ fn is_nominator_via_precompile() {
 			let expected_false_result = Some(Ok(PrecompileOutput {
 				exit_status: ExitSucceed::Returned,
 				output: expected_bytes,
-				cost: 1000,
+				cost: staking_view_cost(StakingViewWeights::is_nominator(0)),
 				logs: Default::default(),
 			}));
 
@@ -1249,7 +1594,7 @@ fn is_nominator_via_precompile() {
 					staking_precompile_address,
 					&charlie_input_data,
 					None,
-					&evm_test_context(),
+					&evm_test_context(staking_precompile_address),
 				),
 				expected_false_result
 			);
@@ -1280,7 +1625,7 @@ fn is_candidate_via_precompile() {
 			let expected_true_result = Some(Ok(PrecompileOutput {
 				exit_status: ExitSucceed::Returned,
 				output: expected_bytes,
-				cost: 1000,
+				cost: staking_view_cost(StakingViewWeights::is_candidate(0)),
 				logs: Default::default(),
 			}));
 
@@ -1290,7 +1635,7 @@ fn is_candidate_via_precompile() {
 					staking_precompile_address,
 					&alice_input_data,
 					None, // target_gas is not necessary right now because consumed none now
-					&evm_test_context(),
+					&evm_test_context(staking_precompile_address),
 				),
 				expected_true_result
 			);
@@ -1306,7 +1651,7 @@ fn is_candidate_via_precompile() {
 			let expected_false_result = Some(Ok(PrecompileOutput {
 				exit_status: ExitSucceed::Returned,
 				output: expected_bytes,
-				cost: 1000,
+				cost: staking_view_cost(StakingViewWeights::is_candidate(0)),
 				logs: Default::default(),
 			}));
 
@@ -1316,7 +1661,7 @@ fn is_candidate_via_precompile() {
 					staking_precompile_address,
 					&bob_input_data,
 					None,
-					&evm_test_context(),
+					&evm_test_context(staking_precompile_address),
 				),
 				expected_false_result
 			);
@@ -1349,7 +1694,7 @@ fn is_selected_candidate_via_precompile() {
 			let expected_true_result = Some(Ok(PrecompileOutput {
 				exit_status: ExitSucceed::Returned,
 				output: expected_bytes,
-				cost: 1000,
+				cost: staking_view_cost(StakingViewWeights::is_selected_candidate(1)),
 				logs: Default::default(),
 			}));
 
@@ -1359,7 +1704,7 @@ fn is_selected_candidate_via_precompile() {
 					staking_precompile_address,
 					&alice_input_data,
 					None, // target_gas is not necessary right now because consumed none now
-					&evm_test_context(),
+					&evm_test_context(staking_precompile_address),
 				),
 				expected_true_result
 			);
@@ -1375,7 +1720,7 @@ fn is_selected_candidate_via_precompile() {
 			let expected_false_result = Some(Ok(PrecompileOutput {
 				exit_status: ExitSucceed::Returned,
 				output: expected_bytes,
-				cost: 1000,
+				cost: staking_view_cost(StakingViewWeights::is_selected_candidate(1)),
 				logs: Default::default(),
 			}));
 
@@ -1385,7 +1730,7 @@ fn is_selected_candidate_via_precompile() {
 					staking_precompile_address,
 					&bob_input_data,
 					None,
-					&evm_test_context(),
+					&evm_test_context(staking_precompile_address),
 				),
 				expected_false_result
 			);
@@ -1407,7 +1752,7 @@ fn min_nomination_via_precompile() {
 		let expected_result = Some(Ok(PrecompileOutput {
 			exit_status: ExitSucceed::Returned,
 			output: buffer.to_vec(),
-			cost: 1000,
+			cost: staking_view_cost(StakingViewWeights::min_nomination()),
 			logs: Default::default(),
 		}));
 
@@ -1416,7 +1761,7 @@ fn min_nomination_via_precompile() {
 				staking_precompile_address,
 				&get_min_nom,
 				None,
-				&evm_test_context(),
+				&evm_test_context(staking_precompile_address),
 			),
 			expected_result
 		);
@@ -1439,7 +1784,7 @@ fn points_precompile_zero() {
 		let expected_zero_result = Some(Ok(PrecompileOutput {
 			exit_status: ExitSucceed::Returned,
 			output: expected_bytes,
-			cost: 1000,
+			cost: staking_view_cost(StakingViewWeights::points()),
 			logs: Default::default(),
 		}));
 
@@ -1449,7 +1794,7 @@ fn points_precompile_zero() {
 				staking_precompile_address,
 				&input_data,
 				None,
-				&evm_test_context(),
+				&evm_test_context(staking_precompile_address),
 			),
 			expected_zero_result
 		);
@@ -1487,7 +1832,7 @@ fn points_precompile_non_zero() {
 			let expected_result = Some(Ok(PrecompileOutput {
 				exit_status: ExitSucceed::Returned,
 				output: expected_bytes,
-				cost: 1000,
+				cost: staking_view_cost(StakingViewWeights::points()),
 				logs: Default::default(),
 			}));
 
@@ -1497,7 +1842,7 @@ fn points_precompile_non_zero() {
 					staking_precompile_address,
 					&input_data,
 					None,
-					&evm_test_context(),
+					&evm_test_context(staking_precompile_address),
 				),
 				expected_result
 			);
@@ -1523,71 +1868,1527 @@ fn points_precompile_round_too_big_error() {
 				staking_precompile_address,
 				&input_data,
 				None,
-				&evm_test_context(),
+				&evm_test_context(staking_precompile_address),
 			),
-			Some(Err(ExitError::Other(
-				"Round is too large. 32 bit maximum".into()
-			)))
+			Some(Err(ExitError::Other("Value is too large for u32".into())))
 		);
 	})
 }
 
-fn run_with_system_weight<F>(w: Weight, mut assertions: F)
-where
-	F: FnMut() -> (),
-{
-	let mut t: sp_io::TestExternalities = frame_system::GenesisConfig::default()
-		.build_storage::<Runtime>()
-		.unwrap()
-		.into();
-	t.execute_with(|| {
-		System::set_block_consumed_resources(w, 0);
-		assertions()
-	});
+#[test]
+fn candidate_and_nominator_views_via_precompile() {
+	ExtBuilder::default()
+		.with_balances(vec![
+			(AccountId::from(ALICE), 2_000 * UNIT),
+			(AccountId::from(BOB), 1_000 * UNIT),
+		])
+		.with_collators(vec![(AccountId::from(ALICE), 1_000 * UNIT)])
+		.with_nominations(vec![(
+			AccountId::from(BOB),
+			AccountId::from(ALICE),
+			500 * UNIT,
+		)])
+		.build()
+		.execute_with(|| {
+			let staking_precompile_address = H160::from_low_u64_be(2048);
+
+			// Builds the input data of a view from its signature and its address arguments
+			let input_data = |signature: &[u8], accounts: &[[u8; 20]]| {
+				let mut input = Keccak256::digest(signature)[0..4].to_vec();
+				for account in accounts {
+					input.extend_from_slice(&[0u8; 12]);
+					input.extend_from_slice(account);
+				}
+				input
+			};
+
+			// Asserts that a view returns the expected uint256 and charges the gas of its weight
+			let assert_view =
+				|signature: &[u8], accounts: &[[u8; 20]], expected: U256, weight: Weight| {
+					let mut expected_bytes = vec![0u8; 32];
+					expected.to_big_endian(&mut expected_bytes);
+					assert_eq!(
+						Precompiles::execute(
+							staking_precompile_address,
+							&input_data(signature, accounts),
+							None,
+							&evm_test_context(staking_precompile_address),
+						),
+						Some(Ok(PrecompileOutput {
+							exit_status: ExitSucceed::Returned,
+							output: expected_bytes,
+							cost: staking_view_cost(weight),
+							logs: Default::default(),
+						}))
+					);
+				};
+
+			assert_view(
+				b"candidate_count()",
+				&[],
+				1.into(),
+				StakingViewWeights::candidate_count(1),
+			);
+			assert_view(
+				b"candidate_bond(address)",
+				&[ALICE],
+				(1_000 * UNIT).into(),
+				StakingViewWeights::candidate_bond(1),
+			);
+			assert_view(
+				b"candidate_total_backing(address)",
+				&[ALICE],
+				(1_500 * UNIT).into(),
+				StakingViewWeights::candidate_total_backing(1),
+			);
+			assert_view(
+				b"collator_nomination_count(address)",
+				&[ALICE],
+				1.into(),
+				StakingViewWeights::collator_nomination_count(1),
+			);
+			assert_view(
+				b"nominator_total(address)",
+				&[BOB],
+				(500 * UNIT).into(),
+				StakingViewWeights::nominator_total(1),
+			);
+			assert_view(
+				b"nominator_nomination_count(address)",
+				&[BOB],
+				1.into(),
+				StakingViewWeights::nominator_nomination_count(1),
+			);
+			assert_view(
+				b"nomination_amount(address,address)",
+				&[BOB, ALICE],
+				(500 * UNIT).into(),
+				StakingViewWeights::nomination_amount(1),
+			);
+
+			// Unknown accounts are reported with zero values
+			assert_view(
+				b"candidate_bond(address)",
+				&[BOB],
+				U256::zero(),
+				StakingViewWeights::candidate_bond(0),
+			);
+			assert_view(
+				b"nominator_total(address)",
+				&[ALICE],
+				U256::zero(),
+				StakingViewWeights::nominator_total(0),
+			);
+			assert_view(
+				b"nomination_amount(address,address)",
+				&[ALICE, BOB],
+				U256::zero(),
+				StakingViewWeights::nomination_amount(0),
+			);
+
+			// The exit round is only set once the candidate requested to leave
+			assert_view(
+				b"candidate_exit_round(address)",
+				&[ALICE],
+				U256::zero(),
+				StakingViewWeights::candidate_exit_round(1),
+			);
+			assert_ok!(ParachainStaking::leave_candidates(
+				<Runtime as frame_system::Config>::Origin::signed(AccountId::from(ALICE)),
+				1
+			));
+			assert_view(
+				b"candidate_exit_round(address)",
+				&[ALICE],
+				3.into(),
+				StakingViewWeights::candidate_exit_round(1),
+			);
+		});
 }
 
 #[test]
-fn multiplier_can_grow_from_zero() {
-	let minimum_multiplier = moonbase_runtime::MinimumMultiplier::get();
-	let target = moonbase_runtime::TargetBlockFullness::get()
-		* BlockWeights::get()
-			.get(DispatchClass::Normal)
-			.max_total
-			.unwrap();
-	// if the min is too small, then this will not change, and we are doomed forever.
-	// the weight is 1/100th bigger than target.
-	run_with_system_weight(target * 101 / 100, || {
-		let next = moonbase_runtime::SlowAdjustingFeeUpdate::<Runtime>::convert(minimum_multiplier);
-		assert!(
-			next > minimum_multiplier,
-			"{:?} !>= {:?}",
-			next,
-			minimum_multiplier
-		);
-	})
+fn round_views_via_precompile() {
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 1_100 * UNIT)])
+		.with_collators(vec![(AccountId::from(ALICE), 1_000 * UNIT)])
+		.with_mappings(vec![(
+			NimbusId::from_slice(&ALICE_NIMBUS),
+			AccountId::from(ALICE),
+		)])
+		.build()
+		.execute_with(|| {
+			let staking_precompile_address = H160::from_low_u64_be(2048);
+
+			// Alice authors a block
+			set_parachain_inherent_data();
+			set_author(NimbusId::from_slice(&ALICE_NIMBUS));
+
+			// We start in round one
+			let mut expected_round = vec![0u8; 32];
+			expected_round[31] = 1;
+			assert_eq!(
+				Precompiles::execute(
+					staking_precompile_address,
+					&Keccak256::digest(b"round()")[0..4],
+					None,
+					&evm_test_context(staking_precompile_address),
+				),
+				Some(Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					output: expected_round,
+					cost: staking_view_cost(StakingViewWeights::round()),
+					logs: Default::default(),
+				}))
+			);
+
+			// Alice is the only selected candidate. The address[] is encoded as its offset, its
+			// length and its elements.
+			let mut expected_candidates = vec![0u8; 96];
+			expected_candidates[31] = 32;
+			expected_candidates[63] = 1;
+			expected_candidates[76..96].copy_from_slice(&ALICE);
+			assert_eq!(
+				Precompiles::execute(
+					staking_precompile_address,
+					&Keccak256::digest(b"selected_candidates()")[0..4],
+					None,
+					&evm_test_context(staking_precompile_address),
+				),
+				Some(Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					output: expected_candidates,
+					cost: staking_view_cost(StakingViewWeights::selected_candidates(1)),
+					logs: Default::default(),
+				}))
+			);
+
+			// Alice was awarded 20 points in round one, one per block
+			let mut input_data = Vec::<u8>::from([0u8; 68]);
+			input_data[0..4]
+				.copy_from_slice(&Keccak256::digest(b"awarded_pts(uint256,address)")[0..4]);
+			U256::one().to_big_endian(&mut input_data[4..36]);
+			input_data[48..68].copy_from_slice(&ALICE);
+
+			let mut expected_points = vec![0u8; 32];
+			expected_points[31] = 20;
+			assert_eq!(
+				Precompiles::execute(
+					staking_precompile_address,
+					&input_data,
+					None,
+					&evm_test_context(staking_precompile_address),
+				),
+				Some(Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					output: expected_points,
+					cost: staking_view_cost(StakingViewWeights::awarded_pts()),
+					logs: Default::default(),
+				}))
+			);
+		})
 }
 
 #[test]
-#[ignore] // test runs for a very long time
-fn multiplier_growth_simulator() {
-	// assume the multiplier is initially set to its minimum. We update it with values twice the
-	//target (target is 25%, thus 50%) and we see at which point it reaches 1.
-	let mut multiplier = moonbase_runtime::MinimumMultiplier::get();
-	let block_weight = moonbase_runtime::TargetBlockFullness::get()
-		* BlockWeights::get()
-			.get(DispatchClass::Normal)
-			.max_total
-			.unwrap()
-		* 2;
-	let mut blocks = 0;
-	while multiplier <= Multiplier::one() {
-		run_with_system_weight(block_weight, || {
-			let next = moonbase_runtime::SlowAdjustingFeeUpdate::<Runtime>::convert(multiplier);
-			// ensure that it is growing as well.
-			assert!(next > multiplier, "{:?} !>= {:?}", next, multiplier);
-			multiplier = next;
-		});
-		blocks += 1;
-		println!("block = {} multiplier {:?}", blocks, multiplier);
-	}
+fn erc20_balance_of_and_total_supply_via_precompile() {
+	ExtBuilder::default()
+		.with_balances(vec![
+			(AccountId::from(ALICE), 1_000 * UNIT),
+			(AccountId::from(BOB), 500 * UNIT),
+		])
+		.build()
+		.execute_with(|| {
+			let erc20_precompile_address = H160::from_low_u64_be(2050);
+
+			// Construct the input data to check the balance of Alice
+			let mut balance_input = Vec::<u8>::from([0u8; 36]);
+			balance_input[0..4].copy_from_slice(&Keccak256::digest(b"balanceOf(address)")[0..4]);
+			balance_input[16..36].copy_from_slice(&ALICE);
+
+			let mut expected_bytes = Vec::from([0u8; 32]);
+			U256::from(1_000 * UNIT).to_big_endian(&mut expected_bytes);
+
+			assert_eq!(
+				Precompiles::execute(
+					erc20_precompile_address,
+					&balance_input,
+					None,
+					&evm_test_context(erc20_precompile_address),
+				),
+				Some(Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					output: expected_bytes,
+					cost: 1000 + pause_check_cost(),
+					logs: Default::default(),
+				}))
+			);
+
+			// Total supply is the sum of all balances
+			let supply_input = Keccak256::digest(b"totalSupply()")[0..4].to_vec();
+
+			let mut expected_bytes = Vec::from([0u8; 32]);
+			U256::from(1_500 * UNIT).to_big_endian(&mut expected_bytes);
+
+			assert_eq!(
+				Precompiles::execute(
+					erc20_precompile_address,
+					&supply_input,
+					None,
+					&evm_test_context(erc20_precompile_address),
+				),
+				Some(Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					output: expected_bytes,
+					cost: 1000 + pause_check_cost(),
+					logs: Default::default(),
+				}))
+			);
+		})
+}
+
+#[test]
+fn erc20_metadata_via_precompile() {
+	ExtBuilder::default().build().execute_with(|| {
+		let erc20_precompile_address = H160::from_low_u64_be(2050);
+
+		let decimals_input = Keccak256::digest(b"decimals()")[0..4].to_vec();
+
+		let mut expected_bytes = Vec::from([0u8; 32]);
+		U256::from(18).to_big_endian(&mut expected_bytes);
+
+		// The metadata is constant, so only the output is charged and no storage read.
+		assert_eq!(
+			Precompiles::execute(
+				erc20_precompile_address,
+				&decimals_input,
+				None,
+				&evm_test_context(erc20_precompile_address),
+			),
+			Some(Ok(PrecompileOutput {
+				exit_status: ExitSucceed::Returned,
+				output: expected_bytes,
+				cost: 18 + pause_check_cost(),
+				logs: Default::default(),
+			}))
+		);
+	})
+}
+
+#[test]
+fn erc20_transfer_via_precompile() {
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 1_000 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let erc20_precompile_address = H160::from_low_u64_be(2050);
+			let amount: U256 = (400 * UNIT).into();
+
+			// Construct the call data (selector, to, amount)
+			let mut call_data = Vec::<u8>::from([0u8; 68]);
+			call_data[0..4].copy_from_slice(&Keccak256::digest(b"transfer(address,uint256)")[0..4]);
+			call_data[16..36].copy_from_slice(&BOB);
+			amount.to_big_endian(&mut call_data[36..68]);
+
+			let context = evm::Context {
+				address: erc20_precompile_address,
+				caller: H160::from(ALICE),
+				apparent_value: From::from(0),
+			};
+
+			let output = Precompiles::execute(erc20_precompile_address, &call_data, None, &context)
+				.expect("precompile exists at this address")
+				.expect("transfer succeeds");
+
+			let mut expected_bytes = Vec::from([0u8; 32]);
+			expected_bytes[31] = 1;
+			assert_eq!(output.exit_status, ExitSucceed::Returned);
+			assert_eq!(output.output, expected_bytes);
+
+			// A Transfer log is emitted
+			let mut log_data = Vec::from([0u8; 32]);
+			amount.to_big_endian(&mut log_data);
+			assert_eq!(
+				output.logs,
+				vec![evm::backend::Log {
+					address: erc20_precompile_address,
+					topics: vec![
+						H256::from_slice(&Keccak256::digest(b"Transfer(address,address,uint256)")),
+						H160::from(ALICE).into(),
+						H160::from(BOB).into(),
+					],
+					data: log_data,
+				}]
+			);
+
+			assert_eq!(Balances::free_balance(AccountId::from(ALICE)), 600 * UNIT);
+			assert_eq!(Balances::free_balance(AccountId::from(BOB)), 400 * UNIT);
+		})
+}
+
+#[test]
+fn erc20_approve_and_transfer_from_via_precompile() {
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 1_000 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let erc20_precompile_address = H160::from_low_u64_be(2050);
+			let approved: U256 = (500 * UNIT).into();
+			let amount: U256 = (400 * UNIT).into();
+
+			// Alice approves Bob to spend 500 UNIT
+			let mut approve_data = Vec::<u8>::from([0u8; 68]);
+			approve_data[0..4]
+				.copy_from_slice(&Keccak256::digest(b"approve(address,uint256)")[0..4]);
+			approve_data[16..36].copy_from_slice(&BOB);
+			approved.to_big_endian(&mut approve_data[36..68]);
+
+			let alice_context = evm::Context {
+				address: erc20_precompile_address,
+				caller: H160::from(ALICE),
+				apparent_value: From::from(0),
+			};
+
+			let output = Precompiles::execute(
+				erc20_precompile_address,
+				&approve_data,
+				None,
+				&alice_context,
+			)
+			.expect("precompile exists at this address")
+			.expect("approve succeeds");
+			assert_eq!(output.logs.len(), 1);
+			assert_eq!(
+				output.logs[0].topics[0],
+				H256::from_slice(&Keccak256::digest(b"Approval(address,address,uint256)"))
+			);
+
+			// Bob transfers 400 UNIT from Alice to Charlie
+			let mut transfer_data = Vec::<u8>::from([0u8; 100]);
+			transfer_data[0..4].copy_from_slice(
+				&Keccak256::digest(b"transferFrom(address,address,uint256)")[0..4],
+			);
+			transfer_data[16..36].copy_from_slice(&ALICE);
+			transfer_data[48..68].copy_from_slice(&CHARLIE);
+			amount.to_big_endian(&mut transfer_data[68..100]);
+
+			let bob_context = evm::Context {
+				address: erc20_precompile_address,
+				caller: H160::from(BOB),
+				apparent_value: From::from(0),
+			};
+
+			assert!(matches!(
+				Precompiles::execute(erc20_precompile_address, &transfer_data, None, &bob_context),
+				Some(Ok(_))
+			));
+			assert_eq!(Balances::free_balance(AccountId::from(ALICE)), 600 * UNIT);
+			assert_eq!(Balances::free_balance(AccountId::from(CHARLIE)), 400 * UNIT);
+
+			// The allowance has been decreased
+			let mut allowance_data = Vec::<u8>::from([0u8; 68]);
+			allowance_data[0..4]
+				.copy_from_slice(&Keccak256::digest(b"allowance(address,address)")[0..4]);
+			allowance_data[16..36].copy_from_slice(&ALICE);
+			allowance_data[48..68].copy_from_slice(&BOB);
+
+			let mut expected_bytes = Vec::from([0u8; 32]);
+			U256::from(100 * UNIT).to_big_endian(&mut expected_bytes);
+			assert_eq!(
+				Precompiles::execute(
+					erc20_precompile_address,
+					&allowance_data,
+					None,
+					&evm_test_context(erc20_precompile_address),
+				),
+				Some(Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					output: expected_bytes,
+					cost: 1000 + pause_check_cost(),
+					logs: Default::default(),
+				}))
+			);
+
+			// Bob can't spend more than what remains of the allowance
+			assert_eq!(
+				Precompiles::execute(erc20_precompile_address, &transfer_data, None, &bob_context),
+				Some(Err(ExitError::Other(
+					"Trying to spend more than allowed".into()
+				)))
+			);
+		})
+}
+
+#[test]
+fn erc20_precompile_rejects_delegatecall() {
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 1_000 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let erc20_precompile_address = H160::from_low_u64_be(2050);
+			let amount: U256 = (400 * UNIT).into();
+
+			// A contract delegating to the precompile runs it in its own context, with the caller
+			// of the contract as caller.
+			let delegating_context = evm::Context {
+				address: H160::repeat_byte(0xaa),
+				caller: H160::from(ALICE),
+				apparent_value: From::from(0),
+			};
+
+			let mut transfer_data = Vec::<u8>::from([0u8; 68]);
+			transfer_data[0..4]
+				.copy_from_slice(&Keccak256::digest(b"transfer(address,uint256)")[0..4]);
+			transfer_data[16..36].copy_from_slice(&BOB);
+			amount.to_big_endian(&mut transfer_data[36..68]);
+
+			let mut approve_data = Vec::<u8>::from([0u8; 68]);
+			approve_data[0..4]
+				.copy_from_slice(&Keccak256::digest(b"approve(address,uint256)")[0..4]);
+			approve_data[16..36].copy_from_slice(&BOB);
+			amount.to_big_endian(&mut approve_data[36..68]);
+
+			for call_data in [transfer_data, approve_data].iter() {
+				assert_eq!(
+					Precompiles::execute(
+						erc20_precompile_address,
+						call_data,
+						None,
+						&delegating_context
+					),
+					Some(Err(ExitError::Other(
+						"Cannot be called with DELEGATECALL or CALLCODE".into()
+					)))
+				);
+			}
+
+			assert_eq!(Balances::free_balance(AccountId::from(ALICE)), 1_000 * UNIT);
+			assert_eq!(Balances::free_balance(AccountId::from(BOB)), 0);
+		})
+}
+
+#[test]
+fn propose_via_democracy_precompile() {
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 1_000 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let democracy_precompile_address = H160::from_low_u64_be(2051);
+			let proposal_hash = H256::repeat_byte(0x11);
+			let deposit: U256 = (10 * UNIT).into();
+
+			// Construct the call data (selector, proposal_hash, value)
+			let mut call_data = Vec::<u8>::from([0u8; 68]);
+			call_data[0..4].copy_from_slice(&Keccak256::digest(b"propose(bytes32,uint256)")[0..4]);
+			call_data[4..36].copy_from_slice(proposal_hash.as_bytes());
+			deposit.to_big_endian(&mut call_data[36..68]);
+
+			let context = evm::Context {
+				address: democracy_precompile_address,
+				caller: H160::from(ALICE),
+				apparent_value: From::from(0),
+			};
+
+			assert!(matches!(
+				Precompiles::execute(democracy_precompile_address, &call_data, None, &context),
+				Some(Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Stopped,
+					..
+				}))
+			));
+
+			// The deposit is reserved from Alice
+			assert_eq!(
+				Balances::reserved_balance(AccountId::from(ALICE)),
+				10 * UNIT
+			);
+
+			// The precompile reports one public proposal
+			let count_input = Keccak256::digest(b"public_prop_count()")[0..4].to_vec();
+			let mut expected_bytes = Vec::from([0u8; 32]);
+			expected_bytes[31] = 1;
+			assert_eq!(
+				Precompiles::execute(
+					democracy_precompile_address,
+					&count_input,
+					None,
+					&evm_test_context(democracy_precompile_address),
+				),
+				Some(Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					output: expected_bytes,
+					cost: 1000 + pause_check_cost(),
+					logs: Default::default(),
+				}))
+			);
+
+			// The precompile reports the deposit of the proposal
+			let mut deposit_input = Vec::<u8>::from([0u8; 36]);
+			deposit_input[0..4].copy_from_slice(&Keccak256::digest(b"deposit_of(uint256)")[0..4]);
+			let mut expected_bytes = Vec::from([0u8; 32]);
+			deposit.to_big_endian(&mut expected_bytes);
+			assert_eq!(
+				Precompiles::execute(
+					democracy_precompile_address,
+					&deposit_input,
+					None,
+					&evm_test_context(democracy_precompile_address),
+				),
+				Some(Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					output: expected_bytes,
+					cost: 1000 + pause_check_cost(),
+					logs: Default::default(),
+				}))
+			);
+		})
+}
+
+#[test]
+fn democracy_precompile_rejects_invalid_conviction() {
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 1_000 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let democracy_precompile_address = H160::from_low_u64_be(2051);
+
+			// Construct the call data (selector, ref_index, aye, vote_amount, conviction)
+			let mut call_data = Vec::<u8>::from([0u8; 132]);
+			call_data[0..4].copy_from_slice(
+				&Keccak256::digest(b"standard_vote(uint256,bool,uint256,uint256)")[0..4],
+			);
+			call_data[67] = 1;
+			U256::from(UNIT).to_big_endian(&mut call_data[68..100]);
+			U256::from(7).to_big_endian(&mut call_data[100..132]);
+
+			assert_eq!(
+				Precompiles::execute(
+					democracy_precompile_address,
+					&call_data,
+					None,
+					&evm_test_context(democracy_precompile_address),
+				),
+				Some(Err(ExitError::Other(
+					"Conviction must be an integer in the range 0-6".into()
+				)))
+			);
+		})
+}
+
+#[test]
+fn democracy_precompile_rejects_delegatecall() {
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 1_000 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let democracy_precompile_address = H160::from_low_u64_be(2051);
+
+			// Construct the call data (selector, proposal_hash, value)
+			let mut call_data = Vec::<u8>::from([0u8; 68]);
+			call_data[0..4].copy_from_slice(&Keccak256::digest(b"propose(bytes32,uint256)")[0..4]);
+			call_data[4..36].copy_from_slice(H256::repeat_byte(0x11).as_bytes());
+			U256::from(10 * UNIT).to_big_endian(&mut call_data[36..68]);
+
+			let delegating_context = evm::Context {
+				address: H160::repeat_byte(0xaa),
+				caller: H160::from(ALICE),
+				apparent_value: From::from(0),
+			};
+
+			assert_eq!(
+				Precompiles::execute(
+					democracy_precompile_address,
+					&call_data,
+					None,
+					&delegating_context
+				),
+				Some(Err(ExitError::Other(
+					"Cannot be called with DELEGATECALL or CALLCODE".into()
+				)))
+			);
+			assert_eq!(Balances::reserved_balance(AccountId::from(ALICE)), 0);
+		})
+}
+
+#[test]
+fn claim_via_crowdloan_rewards_precompile() {
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 2_000 * UNIT)])
+		.with_collators(vec![(AccountId::from(ALICE), 1_000 * UNIT)])
+		.with_mappings(vec![(
+			NimbusId::from_slice(&ALICE_NIMBUS),
+			AccountId::from(ALICE),
+		)])
+		.with_crowdloan_fund(3_000_000 * UNIT)
+		.build()
+		.execute_with(|| {
+			let crowdloan_precompile_address = H160::from_low_u64_be(2049);
+
+			// set parachain inherent data
+			set_parachain_inherent_data();
+			set_author(NimbusId::from_slice(&ALICE_NIMBUS));
+			for x in 1..3 {
+				run_to_block(x);
+			}
+			let init_block = CrowdloanRewards::init_relay_block();
+			let end_block = init_block + 4 * WEEKS;
+			assert_ok!(
+				Call::Utility(pallet_utility::Call::<Runtime>::batch_all(vec![
+					Call::CrowdloanRewards(
+						pallet_crowdloan_rewards::Call::<Runtime>::initialize_reward_vec(vec![(
+							[4u8; 32].into(),
+							Some(AccountId::from(CHARLIE)),
+							3_000_000 * UNIT
+						)])
+					),
+					Call::CrowdloanRewards(
+						pallet_crowdloan_rewards::Call::<Runtime>::complete_initialization(
+							end_block
+						)
+					)
+				]))
+				.dispatch(root_origin())
+			);
+
+			// Charlie is reported as a contributor, Bob is not
+			let mut charlie_input = Vec::<u8>::from([0u8; 36]);
+			charlie_input[0..4]
+				.copy_from_slice(&Keccak256::digest(b"is_contributor(address)")[0..4]);
+			charlie_input[16..36].copy_from_slice(&CHARLIE);
+
+			let mut expected_bytes = Vec::from([0u8; 32]);
+			expected_bytes[31] = 1;
+			assert_eq!(
+				Precompiles::execute(
+					crowdloan_precompile_address,
+					&charlie_input,
+					None,
+					&evm_test_context(crowdloan_precompile_address),
+				),
+				Some(Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					output: expected_bytes,
+					cost: 1000 + pause_check_cost(),
+					logs: Default::default(),
+				}))
+			);
+
+			let mut bob_input = Vec::<u8>::from([0u8; 36]);
+			bob_input[0..4].copy_from_slice(&Keccak256::digest(b"is_contributor(address)")[0..4]);
+			bob_input[16..36].copy_from_slice(&BOB);
+
+			assert_eq!(
+				Precompiles::execute(
+					crowdloan_precompile_address,
+					&bob_input,
+					None,
+					&evm_test_context(crowdloan_precompile_address),
+				),
+				Some(Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					output: Vec::from([0u8; 32]),
+					cost: 1000 + pause_check_cost(),
+					logs: Default::default(),
+				}))
+			);
+
+			// Charlie claims through the precompile
+			let claim_input = Keccak256::digest(b"claim()")[0..4].to_vec();
+			let charlie_context = evm::Context {
+				address: crowdloan_precompile_address,
+				caller: H160::from(CHARLIE),
+				apparent_value: From::from(0),
+			};
+
+			assert!(matches!(
+				Precompiles::execute(
+					crowdloan_precompile_address,
+					&claim_input,
+					None,
+					&charlie_context,
+				),
+				Some(Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Stopped,
+					..
+				}))
+			));
+
+			let vesting_period = 4 * WEEKS as u128;
+			let per_block = (2_100_000 * UNIT) / vesting_period;
+			let claimed = (900_000 * UNIT) + per_block;
+
+			// The reward info reports the total and the claimed rewards
+			let mut info_input = Vec::<u8>::from([0u8; 36]);
+			info_input[0..4].copy_from_slice(&Keccak256::digest(b"reward_info(address)")[0..4]);
+			info_input[16..36].copy_from_slice(&CHARLIE);
+
+			let mut expected_bytes = Vec::from([0u8; 64]);
+			U256::from(3_000_000 * UNIT).to_big_endian(&mut expected_bytes[..32]);
+			U256::from(claimed).to_big_endian(&mut expected_bytes[32..]);
+			assert_eq!(
+				Precompiles::execute(
+					crowdloan_precompile_address,
+					&info_input,
+					None,
+					&evm_test_context(crowdloan_precompile_address),
+				),
+				Some(Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					output: expected_bytes,
+					cost: 1000 + pause_check_cost(),
+					logs: Default::default(),
+				}))
+			);
+			assert_eq!(Balances::balance(&AccountId::from(CHARLIE)), claimed);
+		})
+}
+
+#[test]
+fn crowdloan_rewards_precompile_rejects_delegatecall() {
+	ExtBuilder::default().build().execute_with(|| {
+		let crowdloan_precompile_address = H160::from_low_u64_be(2049);
+
+		let claim_input = Keccak256::digest(b"claim()")[0..4].to_vec();
+		let mut update_input = Vec::<u8>::from([0u8; 36]);
+		update_input[0..4]
+			.copy_from_slice(&Keccak256::digest(b"update_reward_address(address)")[0..4]);
+		update_input[16..36].copy_from_slice(&BOB);
+
+		let delegating_context = evm::Context {
+			address: H160::repeat_byte(0xaa),
+			caller: H160::from(CHARLIE),
+			apparent_value: From::from(0),
+		};
+
+		for input in [claim_input, update_input].iter() {
+			assert_eq!(
+				Precompiles::execute(
+					crowdloan_precompile_address,
+					input,
+					None,
+					&delegating_context
+				),
+				Some(Err(ExitError::Other(
+					"Cannot be called with DELEGATECALL or CALLCODE".into()
+				)))
+			);
+		}
+	})
+}
+
+#[test]
+fn add_association_via_author_mapping_precompile() {
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 1_000 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let author_mapping_precompile_address = H160::from_low_u64_be(2052);
+
+			// Construct the call data (selector, nimbus_id)
+			let mut call_data = Vec::<u8>::from([0u8; 36]);
+			call_data[0..4].copy_from_slice(&Keccak256::digest(b"add_association(bytes32)")[0..4]);
+			call_data[4..36].copy_from_slice(&ALICE_NIMBUS);
+
+			let context = evm::Context {
+				address: author_mapping_precompile_address,
+				caller: H160::from(ALICE),
+				apparent_value: From::from(0),
+			};
+
+			assert!(matches!(
+				Precompiles::execute(
+					author_mapping_precompile_address,
+					&call_data,
+					None,
+					&context
+				),
+				Some(Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Stopped,
+					..
+				}))
+			));
+
+			// The deposit is reserved and the mapping registered
+			assert_eq!(
+				Balances::reserved_balance(AccountId::from(ALICE)),
+				100 * UNIT
+			);
+			assert_eq!(
+				AuthorMapping::account_id_of(&NimbusId::from_slice(&ALICE_NIMBUS)),
+				Some(AccountId::from(ALICE))
+			);
+
+			// The precompile resolves the NimbusId to Alice
+			let mut lookup_input = Vec::<u8>::from([0u8; 36]);
+			lookup_input[0..4].copy_from_slice(&Keccak256::digest(b"account_id_of(bytes32)")[0..4]);
+			lookup_input[4..36].copy_from_slice(&ALICE_NIMBUS);
+
+			let mut expected_bytes = Vec::from([0u8; 32]);
+			expected_bytes[12..32].copy_from_slice(&ALICE);
+			assert_eq!(
+				Precompiles::execute(
+					author_mapping_precompile_address,
+					&lookup_input,
+					None,
+					&evm_test_context(author_mapping_precompile_address),
+				),
+				Some(Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					output: expected_bytes,
+					cost: 1000 + pause_check_cost(),
+					logs: Default::default(),
+				}))
+			);
+		})
+}
+
+#[test]
+fn author_mapping_precompile_rejects_delegatecall() {
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 1_000 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let author_mapping_precompile_address = H160::from_low_u64_be(2052);
+
+			let mut add_input = Vec::<u8>::from([0u8; 36]);
+			add_input[0..4].copy_from_slice(&Keccak256::digest(b"add_association(bytes32)")[0..4]);
+			add_input[4..36].copy_from_slice(&ALICE_NIMBUS);
+
+			let mut clear_input = Vec::<u8>::from([0u8; 36]);
+			clear_input[0..4]
+				.copy_from_slice(&Keccak256::digest(b"clear_association(bytes32)")[0..4]);
+			clear_input[4..36].copy_from_slice(&ALICE_NIMBUS);
+
+			let mut update_input = Vec::<u8>::from([0u8; 68]);
+			update_input[0..4]
+				.copy_from_slice(&Keccak256::digest(b"update_association(bytes32,bytes32)")[0..4]);
+			update_input[4..36].copy_from_slice(&ALICE_NIMBUS);
+			update_input[36..68].copy_from_slice(&[2u8; 32]);
+
+			let delegating_context = evm::Context {
+				address: H160::repeat_byte(0xaa),
+				caller: H160::from(ALICE),
+				apparent_value: From::from(0),
+			};
+
+			for input in [add_input, clear_input, update_input].iter() {
+				assert_eq!(
+					Precompiles::execute(
+						author_mapping_precompile_address,
+						input,
+						None,
+						&delegating_context
+					),
+					Some(Err(ExitError::Other(
+						"Cannot be called with DELEGATECALL or CALLCODE".into()
+					)))
+				);
+			}
+
+			assert_eq!(Balances::reserved_balance(AccountId::from(ALICE)), 0);
+			assert_eq!(
+				AuthorMapping::account_id_of(&NimbusId::from_slice(&ALICE_NIMBUS)),
+				None
+			);
+		})
+}
+
+/// Builds the input of a batch precompile method making plain transfers, with empty call data
+/// and gas limit arrays.
+fn batch_transfers_input(signature: &[u8], to: &[[u8; 20]], values: &[U256]) -> Vec<u8> {
+	let word = |value: usize| {
+		let mut word = [0u8; 32];
+		U256::from(value).to_big_endian(&mut word);
+		word
+	};
+
+	let to_offset = 4 * 32;
+	let value_offset = to_offset + 32 * (1 + to.len());
+	let call_data_offset = value_offset + 32 * (1 + values.len());
+	let gas_limit_offset = call_data_offset + 32;
+
+	let mut input = Keccak256::digest(signature)[0..4].to_vec();
+	for offset in &[to_offset, value_offset, call_data_offset, gas_limit_offset] {
+		input.extend_from_slice(&word(*offset));
+	}
+	input.extend_from_slice(&word(to.len()));
+	for account in to {
+		input.extend_from_slice(&[0u8; 12]);
+		input.extend_from_slice(account);
+	}
+	input.extend_from_slice(&word(values.len()));
+	for value in values {
+		let mut word = [0u8; 32];
+		value.to_big_endian(&mut word);
+		input.extend_from_slice(&word);
+	}
+	// Empty call data and gas limit arrays
+	input.extend_from_slice(&word(0));
+	input.extend_from_slice(&word(0));
+
+	input
+}
+
+/// Builds a log emitted by the batch precompile for the subcall at `index`.
+fn batch_subcall_log(signature: &[u8], index: u8) -> evm::backend::Log {
+	let mut data = vec![0u8; 32];
+	data[31] = index;
+
+	evm::backend::Log {
+		address: H160::from_low_u64_be(2053),
+		topics: vec![H256::from_slice(&Keccak256::digest(signature))],
+		data,
+	}
+}
+
+#[test]
+fn batch_all_via_precompile() {
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 1_000 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let batch_precompile_address = H160::from_low_u64_be(2053);
+
+			let input = batch_transfers_input(
+				b"batchAll(address[],uint256[],bytes[],uint64[])",
+				&[BOB, CHARLIE],
+				&[UNIT.into(), (2 * UNIT).into()],
+			);
+
+			let context = evm::Context {
+				address: batch_precompile_address,
+				caller: H160::from(ALICE),
+				apparent_value: From::from(0),
+			};
+
+			// Each transfer is charged like a call transaction
+			assert_eq!(
+				Precompiles::execute(batch_precompile_address, &input, None, &context),
+				Some(Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Stopped,
+					output: Default::default(),
+					cost: 2 * 21_000 + batch_caller_check_cost() + pause_check_cost(),
+					logs: vec![
+						batch_subcall_log(b"SubcallSucceeded(uint256)", 0),
+						batch_subcall_log(b"SubcallSucceeded(uint256)", 1),
+					],
+				}))
+			);
+
+			assert_eq!(Balances::free_balance(AccountId::from(BOB)), UNIT);
+			assert_eq!(Balances::free_balance(AccountId::from(CHARLIE)), 2 * UNIT);
+			assert_eq!(Balances::free_balance(AccountId::from(ALICE)), 997 * UNIT);
+
+			// The subcalls don't consume the nonce of the caller
+			assert_eq!(System::account_nonce(AccountId::from(ALICE)), 0);
+		});
+}
+
+#[test]
+fn batch_all_via_precompile_fails_with_failing_subcall() {
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 1_000 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let batch_precompile_address = H160::from_low_u64_be(2053);
+
+			// Alice can't afford the second transfer
+			let input = batch_transfers_input(
+				b"batchAll(address[],uint256[],bytes[],uint64[])",
+				&[BOB, CHARLIE],
+				&[UNIT.into(), (2_000 * UNIT).into()],
+			);
+
+			let context = evm::Context {
+				address: batch_precompile_address,
+				caller: H160::from(ALICE),
+				apparent_value: From::from(0),
+			};
+
+			assert_eq!(
+				Precompiles::execute(batch_precompile_address, &input, None, &context),
+				Some(Err(ExitError::Other("A subcall of batchAll failed".into())))
+			);
+
+			// The transfer to Bob made by the first subcall was reverted
+			assert_eq!(Balances::free_balance(AccountId::from(BOB)), 0);
+			assert_eq!(Balances::free_balance(AccountId::from(ALICE)), 1_000 * UNIT);
+			assert_eq!(System::account_nonce(AccountId::from(ALICE)), 0);
+		});
+}
+
+#[test]
+fn batch_precompile_rejects_delegatecall() {
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 1_000 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let batch_precompile_address = H160::from_low_u64_be(2053);
+
+			let input = batch_transfers_input(
+				b"batchAll(address[],uint256[],bytes[],uint64[])",
+				&[BOB],
+				&[UNIT.into()],
+			);
+
+			// A contract delegating to the batch precompile would make the subcalls on behalf of
+			// its own caller
+			let delegating_context = evm::Context {
+				address: H160::repeat_byte(0xaa),
+				caller: H160::from(ALICE),
+				apparent_value: From::from(0),
+			};
+
+			assert_eq!(
+				Precompiles::execute(batch_precompile_address, &input, None, &delegating_context),
+				Some(Err(ExitError::Other(
+					"Cannot be called with DELEGATECALL or CALLCODE".into()
+				)))
+			);
+			assert_eq!(Balances::free_balance(AccountId::from(BOB)), 0);
+		});
+}
+
+#[test]
+fn batch_precompile_rejects_contracts() {
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 1_000 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let batch_precompile_address = H160::from_low_u64_be(2053);
+
+			// Alice is a contract
+			pallet_evm::AccountCodes::<Runtime>::insert(H160::from(ALICE), vec![0x00]);
+
+			let input = batch_transfers_input(
+				b"batchAll(address[],uint256[],bytes[],uint64[])",
+				&[BOB],
+				&[UNIT.into()],
+			);
+
+			let context = evm::Context {
+				address: batch_precompile_address,
+				caller: H160::from(ALICE),
+				apparent_value: From::from(0),
+			};
+
+			assert_eq!(
+				Precompiles::execute(batch_precompile_address, &input, None, &context),
+				Some(Err(ExitError::Other(
+					"Batch precompile can only be called by externally owned accounts".into()
+				)))
+			);
+			assert_eq!(Balances::free_balance(AccountId::from(BOB)), 0);
+		});
+}
+
+#[test]
+fn batch_precompile_cannot_be_reentered() {
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 1_000 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let batch_precompile_address = H160::from_low_u64_be(2053);
+
+			let inner_input = batch_transfers_input(
+				b"batchAll(address[],uint256[],bytes[],uint64[])",
+				&[BOB],
+				&[UNIT.into()],
+			);
+
+			// A batch whose only subcall is another batch, making a transfer to Bob
+			let mut input =
+				Keccak256::digest(b"batchAll(address[],uint256[],bytes[],uint64[])")[0..4].to_vec();
+			let word = |value: usize| {
+				let mut word = [0u8; 32];
+				U256::from(value).to_big_endian(&mut word);
+				word
+			};
+			for offset in &[
+				4 * 32,
+				6 * 32,
+				7 * 32,
+				10 * 32 + (inner_input.len() + 31) / 32 * 32,
+			] {
+				input.extend_from_slice(&word(*offset));
+			}
+			// to: [batch precompile]
+			input.extend_from_slice(&word(1));
+			input.extend_from_slice(&[0u8; 12]);
+			input.extend_from_slice(batch_precompile_address.as_bytes());
+			// value: []
+			input.extend_from_slice(&word(0));
+			// callData: [inner_input]
+			input.extend_from_slice(&word(1));
+			input.extend_from_slice(&word(32));
+			input.extend_from_slice(&word(inner_input.len()));
+			let mut padded_inner_input = inner_input.clone();
+			padded_inner_input.resize((inner_input.len() + 31) / 32 * 32, 0);
+			input.extend_from_slice(&padded_inner_input);
+			// gasLimit: []
+			input.extend_from_slice(&word(0));
+
+			let context = evm::Context {
+				address: batch_precompile_address,
+				caller: H160::from(ALICE),
+				apparent_value: From::from(0),
+			};
+
+			assert_eq!(
+				Precompiles::execute(batch_precompile_address, &input, None, &context),
+				Some(Err(ExitError::Other("A subcall of batchAll failed".into())))
+			);
+			assert_eq!(Balances::free_balance(AccountId::from(BOB)), 0);
+		});
+}
+
+#[test]
+fn batch_some_until_failure_via_precompile() {
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 1_000 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let batch_precompile_address = H160::from_low_u64_be(2053);
+
+			// Alice can't afford the second transfer, so the third one is not made
+			let input = batch_transfers_input(
+				b"batchSomeUntilFailure(address[],uint256[],bytes[],uint64[])",
+				&[BOB, CHARLIE, CHARLIE],
+				&[UNIT.into(), (2_000 * UNIT).into(), UNIT.into()],
+			);
+
+			let context = evm::Context {
+				address: batch_precompile_address,
+				caller: H160::from(ALICE),
+				apparent_value: From::from(0),
+			};
+
+			assert_eq!(
+				Precompiles::execute(batch_precompile_address, &input, None, &context),
+				Some(Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Stopped,
+					output: Default::default(),
+					cost: 21_000 + batch_caller_check_cost() + pause_check_cost(),
+					logs: vec![
+						batch_subcall_log(b"SubcallSucceeded(uint256)", 0),
+						batch_subcall_log(b"SubcallFailed(uint256)", 1),
+					],
+				}))
+			);
+
+			assert_eq!(Balances::free_balance(AccountId::from(BOB)), UNIT);
+			assert_eq!(Balances::free_balance(AccountId::from(CHARLIE)), 0);
+		});
+}
+
+#[test]
+fn blake2f_precompile_eip152_test_vectors() {
+	ExtBuilder::default().build().execute_with(|| {
+		let blake2f_precompile_address = H160::from_low_u64_be(9);
+
+		// Test vectors from https://eips.ethereum.org/EIPS/eip-152#test-cases
+		// The input is rounds (4 bytes) | h (64 bytes) | m (128 bytes) | t (16 bytes) | f (1 byte)
+		let input = |rounds: &str, f: &str| {
+			hex::decode(format!(
+				"{}{}{}{}{}",
+				rounds,
+				"48c9bdf267e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5d182e6ad7f520e511f6c\
+				3e2b8c68059b6bbd41fbabd9831f79217e1319cde05b",
+				format!("616263{}", "00".repeat(125)),
+				"03000000000000000000000000000000",
+				f
+			))
+			.unwrap()
+		};
+		let execute = |input: &[u8]| {
+			Precompiles::execute(
+				blake2f_precompile_address,
+				input,
+				None,
+				&evm_test_context(blake2f_precompile_address),
+			)
+		};
+
+		// Vector 0: empty input
+		assert!(matches!(execute(&[]), Some(Err(_))));
+
+		// Vector 1: input one byte too short
+		let mut short_input = input("0000000c", "01");
+		short_input.remove(0);
+		assert!(matches!(execute(&short_input), Some(Err(_))));
+
+		// Vector 2: input one byte too long
+		let mut long_input = input("0000000c", "01");
+		long_input.insert(0, 0);
+		assert!(matches!(execute(&long_input), Some(Err(_))));
+
+		// Vector 3: malformed final block indicator flag
+		assert!(matches!(execute(&input("0000000c", "02")), Some(Err(_))));
+
+		// Vectors 4 to 7. The cost is one gas per round.
+		for (rounds, f, cost, output) in vec![
+			(
+				"00000000",
+				"01",
+				0,
+				"08c9bcf367e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5d282e6ad7f520e511f6c\
+				3e2b8c68059b9442be0454267ce079217e1319cde05b",
+			),
+			(
+				"0000000c",
+				"01",
+				12,
+				"ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252\
+				d5de4533cc9518d38aa8dbf1925ab92386edd4009923",
+			),
+			(
+				"0000000c",
+				"00",
+				12,
+				"75ab69d3190a562c51aef8d88f1c2775876944407270c42c9844252c26d2875298743e7f6d5ea2f2d3e8\
+				d226039cd31b4e426ac4f2d3d666a610c2116fde4735",
+			),
+			(
+				"00000001",
+				"01",
+				1,
+				"b63a380cb2897d521994a85234ee2c181b5f844d2c624c002677e9703449d2fba551b3a8333bcdf5f2f7\
+				e08993d53923de3d64fcc68c034e717b9293fed7a421",
+			),
+		] {
+			assert_eq!(
+				execute(&input(rounds, f)),
+				Some(Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					output: hex::decode(output).unwrap(),
+					cost,
+					logs: Default::default(),
+				}))
+			);
+		}
+	});
+}
+
+#[test]
+fn paused_precompile_is_not_executed() {
+	ExtBuilder::default().build().execute_with(|| {
+		let sha3fips_precompile_address = H160::from_low_u64_be(1024);
+		let execute = || {
+			Precompiles::execute(
+				sha3fips_precompile_address,
+				b"test",
+				None,
+				&evm_test_context(sha3fips_precompile_address),
+			)
+		};
+
+		assert!(matches!(execute(), Some(Ok(_))));
+
+		assert_ok!(moonbase_runtime::PrecompilePause::pause_precompile(
+			root_origin(),
+			sha3fips_precompile_address
+		));
+		assert_eq!(
+			execute(),
+			Some(Err(evm::ExitError::Other(
+				"Precompile is paused by governance".into()
+			)))
+		);
+
+		assert_ok!(moonbase_runtime::PrecompilePause::unpause_precompile(
+			root_origin(),
+			sha3fips_precompile_address
+		));
+		assert!(matches!(execute(), Some(Ok(_))));
+	});
+}
+
+#[test]
+fn ethereum_precompiles_are_not_pausable() {
+	ExtBuilder::default().build().execute_with(|| {
+		let identity_precompile_address = H160::from_low_u64_be(4);
+
+		assert_ok!(moonbase_runtime::PrecompilePause::pause_precompile(
+			root_origin(),
+			identity_precompile_address
+		));
+
+		// The Identity precompile still runs, and costs the same as on Ethereum
+		assert_eq!(
+			Precompiles::execute(
+				identity_precompile_address,
+				b"test",
+				None,
+				&evm_test_context(identity_precompile_address),
+			),
+			Some(Ok(PrecompileOutput {
+				exit_status: ExitSucceed::Returned,
+				output: b"test".to_vec(),
+				cost: 18,
+				logs: Default::default(),
+			}))
+		);
+	});
+}
+
+/// Records its runs in the storage of `PrecompilePause`.
+struct CountingMigration;
+
+impl runtime_common::Migration for CountingMigration {
+	type Pallet = PrecompilePause;
+	const PALLET_VERSION: PalletVersion = PalletVersion {
+		major: 0,
+		minor: 1,
+		patch: 0,
+	};
+
+	fn friendly_name() -> &'static str {
+		"CountingMigration"
+	}
+	fn weight() -> Weight {
+		0
+	}
+	fn migrate() {
+		let key = b"CountingMigration";
+		unhashed::put(key, &(unhashed::get_or_default::<u32>(key) + 1));
+	}
+}
+
+#[test]
+fn run_migrations_migrates_pallets_added_by_an_upgrade() {
+	ExtBuilder::default().build().execute_with(|| {
+		type Migrations = runtime_common::RunMigrations<Runtime, (CountingMigration,)>;
+		let run_migrations = || {
+			Migrations::on_runtime_upgrade();
+			unhashed::get_or_default::<u32>(b"CountingMigration")
+		};
+
+		// The pallet is added to a live chain, so it has no version yet.
+		assert_eq!(PrecompilePause::storage_version(), None);
+		assert_eq!(run_migrations(), 1);
+		assert_eq!(
+			PrecompilePause::storage_version(),
+			Some(PalletVersion::new(0, 1, 0))
+		);
+
+		// The next upgrades find the pallet migrated.
+		assert_eq!(run_migrations(), 1);
+	});
+}
+
+fn run_with_system_weight<F>(w: Weight, mut assertions: F)
+where
+	F: FnMut() -> (),
+{
+	let mut t: sp_io::TestExternalities = frame_system::GenesisConfig::default()
+		.build_storage::<Runtime>()
+		.unwrap()
+		.into();
+	t.execute_with(|| {
+		System::set_block_consumed_resources(w, 0);
+		assertions()
+	});
+}
+
+#[test]
+fn multiplier_can_grow_from_zero() {
+	let minimum_multiplier = moonbase_runtime::MinimumMultiplier::get();
+	let target = moonbase_runtime::TargetBlockFullness::get()
+		* BlockWeights::get()
+			.get(DispatchClass::Normal)
+			.max_total
+			.unwrap();
+	// if the min is too small, then this will not change, and we are doomed forever.
+	// the weight is 1/100th bigger than target.
+	run_with_system_weight(target * 101 / 100, || {
+		let next = moonbase_runtime::SlowAdjustingFeeUpdate::<Runtime>::convert(minimum_multiplier);
+		assert!(
+			next > minimum_multiplier,
+			"{:?} !>= {:?}",
+			next,
+			minimum_multiplier
+		);
+	})
+}
+
+#[test]
+#[ignore] // test runs for a very long time
+fn multiplier_growth_simulator() {
+	// assume the multiplier is initially set to its minimum. We update it with values twice the
+	//target (target is 25%, thus 50%) and we see at which point it reaches 1.
+	let mut multiplier = moonbase_runtime::MinimumMultiplier::get();
+	let block_weight = moonbase_runtime::TargetBlockFullness::get()
+		* BlockWeights::get()
+			.get(DispatchClass::Normal)
+			.max_total
+			.unwrap()
+		* 2;
+	let mut blocks = 0;
+	while multiplier <= Multiplier::one() {
+		run_with_system_weight(block_weight, || {
+			let next = moonbase_runtime::SlowAdjustingFeeUpdate::<Runtime>::convert(multiplier);
+			// ensure that it is growing as well.
+			assert!(next > multiplier, "{:?} !>= {:?}", next, multiplier);
+			multiplier = next;
+		});
+		blocks += 1;
+		println!("block = {} multiplier {:?}", blocks, multiplier);
+	}
+}
+
+#[test]
+fn runtime_upgrade_adds_dummy_code_under_newer_precompiles() {
+	ExtBuilder::default().build().execute_with(|| {
+		let dummy_code = vec![0x60, 0x00, 0x60, 0x00, 0xFD];
+		let code_at =
+			|address| moonbase_runtime::EVM::account_codes(H160::from_low_u64_be(address));
+		assert!(code_at(9).is_empty());
+
+		moonbase_runtime::Executive::execute_on_runtime_upgrade();
+
+		for address in &[9, 2050, 2051, 2052, 2053] {
+			assert_eq!(code_at(*address), dummy_code);
+		}
+	});
+}
+
+#[test]
+fn staking_calls_are_signed_as_typed_data() {
+	use account::eip712::{Eip712Call, SubstrateTransaction};
+	use parity_scale_codec::Encode;
+	use sp_runtime::generic::Era;
+
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(Call::chain_id(), CHAIN_ID);
+
+		// The largest payload of a nomination still fits in the 256 bytes signed as typed data.
+		let call = Call::ParachainStaking(parachain_staking::Call::nominate(
+			AccountId::from(ALICE),
+			u128::MAX,
+			u32::MAX,
+			u32::MAX,
+		));
+		let transaction = SubstrateTransaction {
+			call: call.encode(),
+			typed_call: call.typed_call(),
+			nonce: u32::MAX,
+			era: Era::mortal(u64::MAX, u64::MAX),
+			tip: u128::MAX,
+			genesis_hash: H256::repeat_byte(1),
+			spec_version: u32::MAX,
+			transaction_version: u32::MAX,
+			block_hash: H256::repeat_byte(2),
+		};
+		let payload = transaction.payload();
+		assert!(payload.len() <= 256);
+		assert_eq!(
+			transaction
+				.typed_call
+				.as_ref()
+				.map(|typed_call| typed_call.name),
+			Some("Nominate")
+		);
+		assert_eq!(
+			SubstrateTransaction::decode::<Call>(&payload),
+			Some(transaction)
+		);
+	});
 }
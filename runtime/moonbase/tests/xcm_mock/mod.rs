@@ -0,0 +1,151 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A local network made of a mock relay chain, Moonbase and a sibling parachain.
+//!
+//! The network runs in memory: messages are routed between the chains by `xcm-simulator`.
+
+pub mod moonbase;
+pub mod msg_queue;
+pub mod relay_chain;
+pub mod sibling;
+
+use frame_support::weights::{constants::WEIGHT_PER_SECOND, Weight};
+use moonbase_runtime::AccountId;
+use polkadot_parachain::primitives::Id as ParaId;
+use sp_runtime::traits::AccountIdConversion;
+use xcm_simulator::{decl_test_network, decl_test_parachain, decl_test_relay_chain};
+
+pub const MOONBASE_ID: u32 = 1000;
+pub const SIBLING_ID: u32 = 2000;
+
+pub const ALICE: [u8; 20] = [4u8; 20];
+pub const BOB: [u8; 20] = [5u8; 20];
+/// Bob's account on the relay chain.
+pub const RELAY_BOB: [u8; 32] = [5u8; 32];
+pub const INITIAL_BALANCE: u128 = 1_000_000_000_000_000;
+
+/// Weight bought by the messages sent in the tests.
+pub const DEST_WEIGHT: Weight = WEIGHT_PER_SECOND / 1_000;
+
+decl_test_parachain! {
+	pub struct Moonbase {
+		Runtime = moonbase::Runtime,
+		XcmpMessageHandler = moonbase::MsgQueue,
+		DmpMessageHandler = moonbase::MsgQueue,
+		new_ext = moonbase_ext(),
+	}
+}
+
+decl_test_parachain! {
+	pub struct Sibling {
+		Runtime = sibling::Runtime,
+		XcmpMessageHandler = sibling::MsgQueue,
+		DmpMessageHandler = sibling::MsgQueue,
+		new_ext = sibling_ext(),
+	}
+}
+
+decl_test_relay_chain! {
+	pub struct Relay {
+		Runtime = relay_chain::Runtime,
+		XcmConfig = relay_chain::XcmConfig,
+		new_ext = relay_ext(),
+	}
+}
+
+decl_test_network! {
+	pub struct MockNet {
+		relay_chain = Relay,
+		parachains = vec![
+			(MOONBASE_ID, Moonbase),
+			(SIBLING_ID, Sibling),
+		],
+	}
+}
+
+/// Sovereign account of a parachain on the relay chain.
+pub fn para_account_id(id: u32) -> relay_chain::AccountId {
+	ParaId::from(id).into_account()
+}
+
+/// Sovereign account of a parachain on its siblings.
+pub fn sibling_account_id(id: u32) -> AccountId {
+	polkadot_parachain::primitives::Sibling::from(id).into_account()
+}
+
+pub fn moonbase_ext() -> sp_io::TestExternalities {
+	use moonbase::{MsgQueue, Runtime, System};
+
+	let mut t = frame_system::GenesisConfig::default()
+		.build_storage::<Runtime>()
+		.unwrap();
+
+	pallet_balances::GenesisConfig::<Runtime> {
+		balances: vec![(AccountId::from(ALICE), INITIAL_BALANCE)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		MsgQueue::set_para_id(MOONBASE_ID.into());
+	});
+	ext
+}
+
+pub fn sibling_ext() -> sp_io::TestExternalities {
+	use sibling::{MsgQueue, Runtime, System};
+
+	let mut t = frame_system::GenesisConfig::default()
+		.build_storage::<Runtime>()
+		.unwrap();
+
+	pallet_balances::GenesisConfig::<Runtime> {
+		balances: vec![(AccountId::from(ALICE), INITIAL_BALANCE)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		MsgQueue::set_para_id(SIBLING_ID.into());
+	});
+	ext
+}
+
+pub fn relay_ext() -> sp_io::TestExternalities {
+	use relay_chain::{Runtime, System};
+
+	let mut t = frame_system::GenesisConfig::default()
+		.build_storage::<Runtime>()
+		.unwrap();
+
+	pallet_balances::GenesisConfig::<Runtime> {
+		balances: vec![(para_account_id(MOONBASE_ID), INITIAL_BALANCE)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+pub type MoonbasePalletXcm = pallet_xcm::Pallet<moonbase::Runtime>;
+pub type SiblingPalletXcm = pallet_xcm::Pallet<sibling::Runtime>;
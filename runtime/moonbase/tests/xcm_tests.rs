@@ -0,0 +1,303 @@
+// Copyright 2019-2021 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Moonbase XCM tests, run on a local network made of a relay chain, Moonbase and a sibling
+//! parachain.
+
+mod xcm_mock;
+
+use frame_support::assert_ok;
+use moonbase_runtime::{xcm_config::LocationToAccountId, AccountId};
+use parity_scale_codec::Encode;
+use xcm::v0::{
+	Error as XcmError,
+	Junction::{self, AccountKey20, Parachain, Parent},
+	MultiAsset::{All, ConcreteFungible},
+	MultiLocation::{self, *},
+	NetworkId::Any,
+	Order, OriginKind, SendXcm, Xcm,
+};
+use xcm_executor::traits::Convert;
+use xcm_mock::*;
+use xcm_simulator::TestExt;
+
+fn account_key(account: [u8; 20]) -> MultiLocation {
+	X1(AccountKey20 {
+		network: Any,
+		key: account,
+	})
+}
+
+fn moonbase_token(amount: u128) -> xcm::v0::MultiAsset {
+	ConcreteFungible {
+		id: X2(Parent, Parachain(MOONBASE_ID)),
+		amount,
+	}
+}
+
+/// The relay chain token, as seen from Moonbase.
+fn relay_token(amount: u128) -> xcm::v0::MultiAsset {
+	ConcreteFungible {
+		id: X1(Parent),
+		amount,
+	}
+}
+
+/// Transfer `amount` of the Moonbase token from Alice on Moonbase to Bob on the sibling.
+fn transfer_to_sibling(amount: u128) {
+	Moonbase::execute_with(|| {
+		assert_ok!(MoonbasePalletXcm::reserve_transfer_assets(
+			moonbase::Origin::signed(ALICE.into()),
+			X2(Parent, Parachain(SIBLING_ID)),
+			account_key(BOB),
+			vec![moonbase_token(amount)],
+			DEST_WEIGHT,
+		));
+	});
+}
+
+/// Transact a root call on Moonbase, setting the balance of Bob to `amount`.
+fn set_balance_of_bob(amount: u128) -> Xcm<()> {
+	Xcm::Transact {
+		origin_type: OriginKind::Superuser,
+		require_weight_at_most: DEST_WEIGHT,
+		call: moonbase::Call::Balances(pallet_balances::Call::set_balance(BOB.into(), amount, 0))
+			.encode()
+			.into(),
+	}
+}
+
+#[test]
+fn location_to_account_id_converts_ethereum_accounts() {
+	assert_eq!(
+		LocationToAccountId::convert(account_key(ALICE)),
+		Ok(AccountId::from(ALICE))
+	);
+}
+
+#[test]
+fn location_to_account_id_converts_relay_and_siblings() {
+	assert_eq!(
+		LocationToAccountId::convert(X1(Parent)),
+		Ok(AccountId::default())
+	);
+	assert_eq!(
+		LocationToAccountId::convert(X2(Parent, Parachain(SIBLING_ID))),
+		Ok(sibling_account_id(SIBLING_ID))
+	);
+}
+
+#[test]
+fn location_to_account_id_rejects_other_locations() {
+	let relay_account = X2(
+		Parent,
+		Junction::AccountId32 {
+			network: Any,
+			id: [1u8; 32],
+		},
+	);
+	assert!(LocationToAccountId::convert(relay_account).is_err());
+	assert!(LocationToAccountId::convert(X1(Parachain(SIBLING_ID))).is_err());
+}
+
+#[test]
+fn reserve_transfer_native_token_to_sibling() {
+	MockNet::reset();
+
+	transfer_to_sibling(100);
+
+	Moonbase::execute_with(|| {
+		assert_eq!(
+			moonbase::Balances::free_balance(&AccountId::from(ALICE)),
+			INITIAL_BALANCE - 100
+		);
+		assert_eq!(
+			moonbase::Balances::free_balance(&sibling_account_id(SIBLING_ID)),
+			100
+		);
+	});
+
+	Sibling::execute_with(|| {
+		assert_eq!(
+			sibling::ForeignBalances::free_balance(&AccountId::from(BOB)),
+			100
+		);
+	});
+}
+
+#[test]
+fn withdraw_native_token_from_sibling() {
+	MockNet::reset();
+
+	let amount = 10 * DEST_WEIGHT as u128;
+	transfer_to_sibling(amount);
+
+	Sibling::execute_with(|| {
+		let message = Xcm::WithdrawAsset {
+			assets: vec![moonbase_token(amount)],
+			effects: vec![Order::InitiateReserveWithdraw {
+				assets: vec![All],
+				reserve: X2(Parent, Parachain(MOONBASE_ID)),
+				effects: vec![
+					Order::BuyExecution {
+						fees: All,
+						weight: 0,
+						debt: DEST_WEIGHT,
+						halt_on_error: false,
+						xcm: vec![],
+					},
+					Order::DepositAsset {
+						assets: vec![All],
+						dest: account_key(ALICE),
+					},
+				],
+			}],
+		};
+		assert_ok!(SiblingPalletXcm::execute(
+			sibling::Origin::signed(BOB.into()),
+			Box::new(message),
+			DEST_WEIGHT,
+		));
+		assert_eq!(
+			sibling::ForeignBalances::free_balance(&AccountId::from(BOB)),
+			0
+		);
+	});
+
+	Moonbase::execute_with(|| {
+		// The execution is bought at the price of transaction fees, one unit per unit of weight.
+		assert_eq!(
+			moonbase::Balances::free_balance(&AccountId::from(ALICE)),
+			INITIAL_BALANCE - DEST_WEIGHT as u128
+		);
+		assert_eq!(
+			moonbase::Balances::free_balance(&sibling_account_id(SIBLING_ID)),
+			0
+		);
+	});
+}
+
+#[test]
+fn relay_chain_executes_without_paying() {
+	MockNet::reset();
+
+	Relay::execute_with(|| {
+		assert_ok!(relay_chain::XcmRouter::send_xcm(
+			X1(Parachain(MOONBASE_ID)),
+			set_balance_of_bob(123),
+		));
+	});
+
+	Moonbase::execute_with(|| {
+		assert_eq!(moonbase::Balances::free_balance(&AccountId::from(BOB)), 123);
+	});
+}
+
+#[test]
+fn sibling_must_pay_for_execution() {
+	MockNet::reset();
+
+	Sibling::execute_with(|| {
+		assert_ok!(sibling::XcmRouter::send_xcm(
+			X2(Parent, Parachain(MOONBASE_ID)),
+			set_balance_of_bob(123),
+		));
+	});
+
+	Moonbase::execute_with(|| {
+		assert!(moonbase::System::events().iter().any(|record| matches!(
+			record.event,
+			moonbase::Event::MsgQueue(msg_queue::Event::Fail(_, XcmError::Barrier))
+		)));
+		assert_eq!(moonbase::Balances::free_balance(&AccountId::from(BOB)), 0);
+	});
+}
+
+/// Deposit `amount` of the relay chain token, held in reserve by the relay chain, to Bob on
+/// Moonbase.
+fn deposit_relay_token_to_bob(amount: u128) {
+	Relay::execute_with(|| {
+		assert_ok!(relay_chain::XcmRouter::send_xcm(
+			X1(Parachain(MOONBASE_ID)),
+			Xcm::ReserveAssetDeposit {
+				assets: vec![relay_token(amount)],
+				effects: vec![Order::DepositAsset {
+					assets: vec![All],
+					dest: account_key(BOB),
+				}],
+			},
+		));
+	});
+}
+
+#[test]
+fn receive_relay_token_from_relay_chain() {
+	MockNet::reset();
+
+	deposit_relay_token_to_bob(100);
+
+	Moonbase::execute_with(|| {
+		assert_eq!(
+			moonbase::RelayBalances::free_balance(&AccountId::from(BOB)),
+			100
+		);
+		assert_eq!(moonbase::Balances::free_balance(&AccountId::from(BOB)), 0);
+	});
+}
+
+#[test]
+fn withdraw_relay_token_to_relay_chain() {
+	MockNet::reset();
+
+	deposit_relay_token_to_bob(100);
+
+	Moonbase::execute_with(|| {
+		let message = Xcm::WithdrawAsset {
+			assets: vec![relay_token(100)],
+			effects: vec![Order::InitiateReserveWithdraw {
+				assets: vec![All],
+				reserve: X1(Parent),
+				effects: vec![Order::DepositAsset {
+					assets: vec![All],
+					dest: X1(Junction::AccountId32 {
+						network: Any,
+						id: RELAY_BOB,
+					}),
+				}],
+			}],
+		};
+		assert_ok!(MoonbasePalletXcm::execute(
+			moonbase::Origin::signed(BOB.into()),
+			Box::new(message),
+			DEST_WEIGHT,
+		));
+		assert_eq!(
+			moonbase::RelayBalances::free_balance(&AccountId::from(BOB)),
+			0
+		);
+	});
+
+	Relay::execute_with(|| {
+		assert_eq!(
+			relay_chain::Balances::free_balance(&relay_chain::AccountId::from(RELAY_BOB)),
+			100
+		);
+		assert_eq!(
+			relay_chain::Balances::free_balance(&para_account_id(MOONBASE_ID)),
+			INITIAL_BALANCE - 100
+		);
+	});
+}
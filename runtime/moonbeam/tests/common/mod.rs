@@ -20,15 +20,15 @@ use cumulus_primitives_parachain_inherent::ParachainInherentData;
 use frame_support::{
 	assert_ok,
 	dispatch::Dispatchable,
-	traits::{GenesisBuild, OnFinalize, OnInitialize},
+	traits::{GenesisBuild, Get, OnFinalize, OnInitialize},
 };
 pub use moonbeam_runtime::{
-	currency::GLMR, AccountId, AuthorInherent, Balance, Balances, Call, CrowdloanRewards, Ethereum,
-	Event, Executive, FixedGasPrice, InflationInfo, ParachainStaking, Range, Runtime, System,
+	currency::GLMR, AccountId, AuthorInherent, Balance, Balances, BaseFee, Call, CrowdloanRewards,
+	Ethereum, Event, Executive, InflationInfo, ParachainStaking, Range, Runtime, System,
 	TransactionConverter, UncheckedExtrinsic, WEEKS,
 };
 use nimbus_primitives::NimbusId;
-use pallet_evm::GenesisAccount;
+use pallet_evm::{GasWeightMapping, GenesisAccount};
 use sp_core::H160;
 use sp_runtime::Perbill;
 
@@ -49,17 +49,67 @@ pub fn last_event() -> Event {
 	System::events().pop().expect("Event expected").event
 }
 
-// Helper function to give a simple evm context suitable for tests.
+// Helper function to give a simple evm context, calling the precompile at `address` directly,
+// suitable for tests.
 // We can remove this once https://github.com/rust-blockchain/evm/pull/35
 // is in our dependency graph.
-pub fn evm_test_context() -> evm::Context {
+pub fn evm_test_context(address: H160) -> evm::Context {
 	evm::Context {
-		address: Default::default(),
+		address,
 		caller: Default::default(),
 		apparent_value: From::from(0),
 	}
 }
 
+/// Weights of the staking precompile views
+pub type StakingViewWeights = parachain_staking_precompiles::weights::SubstrateWeight<Runtime>;
+
+// Helper function to get the EVM gas charged by the PrecompileSet to check whether the called
+// precompile is paused, on top of the cost of the precompile itself.
+pub fn pause_check_cost() -> u64 {
+	<Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
+		<Runtime as frame_system::Config>::DbWeight::get().read,
+	)
+}
+
+// Helper function to get the EVM gas charged by a staking precompile view of the given weight
+pub fn staking_view_cost(weight: frame_support::weights::Weight) -> u64 {
+	<Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(weight) + pause_check_cost()
+}
+
+// Helper function to build the EVM log event emitted by the staking precompile when a call
+// succeeds. The topics are the hashed event signature followed by the indexed accounts, and the
+// data is the amount, if any.
+pub fn staking_precompile_log_event(
+	signature: &[u8],
+	accounts: Vec<[u8; 20]>,
+	amount: Option<Balance>,
+) -> Event {
+	use sha3::{Digest, Keccak256};
+	use sp_core::{H256, U256};
+
+	let mut topics = vec![H256::from_slice(&Keccak256::digest(signature))];
+	topics.extend(
+		accounts
+			.into_iter()
+			.map(|account| H256::from(H160::from(account))),
+	);
+
+	let data = amount
+		.map(|amount| {
+			let mut buffer = [0u8; 32];
+			U256::from(amount).to_big_endian(&mut buffer);
+			buffer.to_vec()
+		})
+		.unwrap_or_default();
+
+	Event::EVM(pallet_evm::Event::<Runtime>::Log(evm::backend::Log {
+		address: H160::from_low_u64_be(2048),
+		topics,
+		data,
+	}))
+}
+
 pub struct ExtBuilder {
 	// endowed accounts with balances
 	balances: Vec<(AccountId, Balance)>,
@@ -77,6 +127,8 @@ pub struct ExtBuilder {
 	chain_id: u64,
 	// EVM genesis accounts
 	evm_accounts: BTreeMap<H160, GenesisAccount>,
+	// [account, begin, length, liquid]
+	vesting: Vec<(AccountId, u32, u32, Balance)>,
 }
 
 impl Default for ExtBuilder {
@@ -108,6 +160,7 @@ impl Default for ExtBuilder {
 			crowdloan_fund: 0,
 			chain_id: CHAIN_ID,
 			evm_accounts: BTreeMap::new(),
+			vesting: vec![],
 		}
 	}
 }
@@ -143,6 +196,12 @@ impl ExtBuilder {
 		self
 	}
 
+	#[allow(dead_code)]
+	pub fn with_vesting(mut self, vesting: Vec<(AccountId, u32, u32, Balance)>) -> Self {
+		self.vesting = vesting;
+		self
+	}
+
 	#[allow(dead_code)]
 	pub fn with_inflation(mut self, inflation: InflationInfo<Balance>) -> Self {
 		self.inflation = inflation;
@@ -160,6 +219,12 @@ impl ExtBuilder {
 		.assimilate_storage(&mut t)
 		.unwrap();
 
+		pallet_vesting::GenesisConfig::<Runtime> {
+			vesting: self.vesting,
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
 		parachain_staking::GenesisConfig::<Runtime> {
 			candidates: self.collators,
 			nominations: self.nominations,
@@ -202,6 +267,15 @@ impl ExtBuilder {
 		)
 		.unwrap();
 
+		// Like Moonbeam at launch, the chain starts in maintenance mode.
+		<pallet_maintenance_mode::GenesisConfig as GenesisBuild<Runtime>>::assimilate_storage(
+			&pallet_maintenance_mode::GenesisConfig {
+				start_in_maintenance_mode: true,
+			},
+			&mut t,
+		)
+		.unwrap();
+
 		let mut ext = sp_io::TestExternalities::new(t);
 		ext.execute_with(|| System::set_block_number(1));
 		ext
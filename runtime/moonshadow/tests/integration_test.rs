@@ -34,6 +34,7 @@ use nimbus_primitives::NimbusId;
 use pallet_evm::PrecompileSet;
 use pallet_transaction_payment::Multiplier;
 use parachain_staking::{Bond, NominatorAdded};
+use parachain_staking_precompiles::weights::WeightInfo;
 use sha3::{Digest, Keccak256};
 use sp_core::{Public, H160, U256};
 use sp_runtime::{
@@ -80,6 +81,11 @@ fn verify_pallet_prefixes() {
 	is_pallet_prefix::<moonshadow_runtime::AuthorFilter>("AuthorFilter");
 	is_pallet_prefix::<moonshadow_runtime::CrowdloanRewards>("CrowdloanRewards");
 	is_pallet_prefix::<moonshadow_runtime::AuthorMapping>("AuthorMapping");
+	is_pallet_prefix::<moonshadow_runtime::PrecompilePause>("PrecompilePause");
+	is_pallet_prefix::<moonshadow_runtime::BaseFee>("BaseFee");
+	is_pallet_prefix::<moonshadow_runtime::Identity>("Identity");
+	is_pallet_prefix::<moonshadow_runtime::Multisig>("Multisig");
+	is_pallet_prefix::<moonshadow_runtime::Vesting>("Vesting");
 	let prefix = |pallet_name, storage_name| {
 		let mut res = [0u8; 32];
 		res[0..16].copy_from_slice(&Twox128::hash(pallet_name));
@@ -325,6 +331,252 @@ fn transfer_through_evm_to_stake() {
 		});
 }
 
+#[test]
+fn evm_fees_are_burned_and_sent_to_treasury() {
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 2_000 * MSHD)])
+		.build()
+		.execute_with(|| {
+			let treasury = moonshadow_runtime::Treasury::account_id();
+			let issuance = Balances::total_issuance();
+
+			let gas_price: U256 = 1_000_000_000.into();
+			assert_ok!(Call::EVM(pallet_evm::Call::<Runtime>::call(
+				AccountId::from(ALICE),
+				AccountId::from(BOB),
+				Vec::new(),
+				MSHD.into(),
+				100_000u64,
+				gas_price,
+				None
+			))
+			.dispatch(<Runtime as frame_system::Config>::Origin::root()));
+
+			// A transfer uses 21_000 gas, 80% of the fee is burned and 20% goes to the treasury
+			let fee = 21_000 * 1_000_000_000;
+			assert_eq!(
+				Balances::free_balance(AccountId::from(ALICE)),
+				2_000 * MSHD - MSHD - fee
+			);
+			assert_eq!(Balances::free_balance(AccountId::from(BOB)), MSHD);
+			assert_eq!(Balances::free_balance(&treasury), fee / 5);
+			assert_eq!(Balances::total_issuance(), issuance - fee * 4 / 5);
+		});
+}
+
+#[test]
+fn proxy_type_lattice() {
+	use moonshadow_runtime::ProxyType;
+
+	for proxy_type in &[
+		ProxyType::NonTransfer,
+		ProxyType::Governance,
+		ProxyType::Staking,
+		ProxyType::CancelProxy,
+		ProxyType::Balances,
+		ProxyType::AuthorMapping,
+		ProxyType::CrowdloanClaim,
+	] {
+		assert!(ProxyType::Any.is_superset(proxy_type));
+		assert!(!proxy_type.is_superset(&ProxyType::Any));
+		assert!(proxy_type.is_superset(proxy_type));
+	}
+	assert!(ProxyType::NonTransfer.is_superset(&ProxyType::Governance));
+	assert!(ProxyType::NonTransfer.is_superset(&ProxyType::AuthorMapping));
+	assert!(ProxyType::NonTransfer.is_superset(&ProxyType::CrowdloanClaim));
+	assert!(!ProxyType::NonTransfer.is_superset(&ProxyType::Balances));
+	assert!(!ProxyType::Balances.is_superset(&ProxyType::AuthorMapping));
+	assert!(!ProxyType::Staking.is_superset(&ProxyType::Governance));
+}
+
+#[test]
+fn proxy_types_filter_calls() {
+	use frame_support::traits::InstanceFilter;
+	use moonshadow_runtime::ProxyType;
+
+	let transfer = Call::Balances(pallet_balances::Call::transfer(AccountId::from(BOB), MSHD));
+	let clear_association = Call::AuthorMapping(pallet_author_mapping::Call::clear_association(
+		NimbusId::from_slice(&ALICE_NIMBUS),
+	));
+	let claim = Call::CrowdloanRewards(pallet_crowdloan_rewards::Call::claim());
+	let update_reward_address = Call::CrowdloanRewards(
+		pallet_crowdloan_rewards::Call::update_reward_address(AccountId::from(BOB)),
+	);
+
+	assert!(ProxyType::Balances.filter(&transfer));
+	assert!(!ProxyType::Balances.filter(&clear_association));
+
+	assert!(ProxyType::AuthorMapping.filter(&clear_association));
+	assert!(!ProxyType::AuthorMapping.filter(&transfer));
+	assert!(!ProxyType::AuthorMapping.filter(&claim));
+
+	assert!(ProxyType::CrowdloanClaim.filter(&claim));
+	assert!(!ProxyType::CrowdloanClaim.filter(&update_reward_address));
+
+	assert!(ProxyType::NonTransfer.filter(&clear_association));
+	assert!(ProxyType::NonTransfer.filter(&claim));
+	assert!(!ProxyType::NonTransfer.filter(&transfer));
+	assert!(!ProxyType::NonTransfer.filter(&update_reward_address));
+
+	let vest = Call::Vesting(pallet_vesting::Call::vest());
+	let vested_transfer = Call::Vesting(pallet_vesting::Call::vested_transfer(
+		AccountId::from(BOB),
+		pallet_vesting::VestingInfo {
+			locked: MSHD,
+			per_block: MSHD,
+			starting_block: 0,
+		},
+	));
+	let clear_identity = Call::Identity(pallet_identity::Call::clear_identity());
+
+	assert!(ProxyType::NonTransfer.filter(&vest));
+	assert!(ProxyType::NonTransfer.filter(&clear_identity));
+	assert!(!ProxyType::NonTransfer.filter(&vested_transfer));
+	assert!(ProxyType::Balances.filter(&vested_transfer));
+	assert!(!ProxyType::Balances.filter(&clear_identity));
+}
+
+#[test]
+fn set_and_clear_identity() {
+	use moonshadow_runtime::{BasicDeposit, Identity};
+
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 1_000 * MSHD)])
+		.build()
+		.execute_with(|| {
+			let info = pallet_identity::IdentityInfo {
+				display: pallet_identity::Data::Keccak256([1u8; 32]),
+				..Default::default()
+			};
+			assert_ok!(
+				Call::Identity(pallet_identity::Call::set_identity(Box::new(info)))
+					.dispatch(origin_of(AccountId::from(ALICE)))
+			);
+			assert!(Identity::identity(AccountId::from(ALICE)).is_some());
+			assert_eq!(
+				Balances::reserved_balance(AccountId::from(ALICE)),
+				BasicDeposit::get()
+			);
+
+			assert_ok!(Call::Identity(pallet_identity::Call::clear_identity())
+				.dispatch(origin_of(AccountId::from(ALICE))));
+			assert!(Identity::identity(AccountId::from(ALICE)).is_none());
+			assert_eq!(Balances::reserved_balance(AccountId::from(ALICE)), 0);
+		});
+}
+
+#[test]
+fn multisig_cannot_bypass_base_filter() {
+	use frame_support::weights::GetDispatchInfo;
+	use moonshadow_runtime::{Multisig, MultisigDepositBase, MultisigDepositFactor};
+	use parity_scale_codec::Encode;
+
+	let signatories = vec![AccountId::from(ALICE), AccountId::from(BOB)];
+	let multisig = Multisig::multi_account_id(&signatories, 2);
+	ExtBuilder::default()
+		.with_balances(vec![
+			(AccountId::from(ALICE), 1_000 * MSHD),
+			(AccountId::from(BOB), 1_000 * MSHD),
+			(multisig, 1_000 * MSHD),
+		])
+		.build()
+		.execute_with(|| {
+			let call = Call::Balances(pallet_balances::Call::transfer(
+				AccountId::from(CHARLIE),
+				100 * MSHD,
+			));
+			let call_weight = call.get_dispatch_info().weight;
+			let call_hash = sp_io::hashing::blake2_256(&call.encode());
+
+			assert_ok!(Call::Multisig(pallet_multisig::Call::approve_as_multi(
+				2,
+				vec![AccountId::from(BOB)],
+				None,
+				call_hash,
+				0,
+			))
+			.dispatch(origin_of(AccountId::from(ALICE))));
+			assert_eq!(
+				Balances::reserved_balance(AccountId::from(ALICE)),
+				MultisigDepositBase::get() + 2 * MultisigDepositFactor::get()
+			);
+			assert_eq!(Balances::free_balance(AccountId::from(CHARLIE)), 0);
+
+			assert_ok!(Call::Multisig(pallet_multisig::Call::as_multi(
+				2,
+				vec![AccountId::from(ALICE)],
+				Some(Multisig::timepoint()),
+				call.encode(),
+				false,
+				call_weight,
+			))
+			.dispatch(origin_of(AccountId::from(BOB))));
+			// The transfer is dispatched by the multisig account, so it is still filtered.
+			assert_eq!(Balances::free_balance(AccountId::from(CHARLIE)), 0);
+			assert_eq!(Balances::free_balance(&multisig), 1_000 * MSHD);
+			assert_eq!(Balances::reserved_balance(AccountId::from(ALICE)), 0);
+		});
+}
+
+#[test]
+fn genesis_vesting_schedule_unlocks_over_time() {
+	use frame_support::traits::VestingSchedule;
+	use moonshadow_runtime::Vesting;
+
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 100 * MSHD)])
+		// Half of the balance vests linearly from block 10 to block 20
+		.with_vesting(vec![(AccountId::from(ALICE), 10, 10, 50 * MSHD)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(
+				Vesting::vesting_balance(&AccountId::from(ALICE)),
+				Some(50 * MSHD)
+			);
+			assert_eq!(Balances::usable_balance(&AccountId::from(ALICE)), 50 * MSHD);
+
+			System::set_block_number(15);
+			assert_ok!(Call::Vesting(pallet_vesting::Call::vest())
+				.dispatch(origin_of(AccountId::from(ALICE))));
+			assert_eq!(
+				Vesting::vesting_balance(&AccountId::from(ALICE)),
+				Some(25 * MSHD)
+			);
+			assert_eq!(Balances::usable_balance(&AccountId::from(ALICE)), 75 * MSHD);
+
+			System::set_block_number(20);
+			assert_ok!(Call::Vesting(pallet_vesting::Call::vest())
+				.dispatch(origin_of(AccountId::from(ALICE))));
+			assert_eq!(Vesting::vesting_balance(&AccountId::from(ALICE)), None);
+			assert_eq!(
+				Balances::usable_balance(&AccountId::from(ALICE)),
+				100 * MSHD
+			);
+		});
+}
+
+#[test]
+fn base_filter_filters_vested_transfers() {
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(BOB), 1_000 * MSHD)])
+		.build()
+		.execute_with(|| {
+			let vested_transfer = Call::Vesting(pallet_vesting::Call::vested_transfer(
+				AccountId::from(CHARLIE),
+				pallet_vesting::VestingInfo {
+					locked: 10 * MSHD,
+					per_block: MSHD,
+					starting_block: 10,
+				},
+			));
+
+			assert_noop!(
+				vested_transfer.dispatch(origin_of(AccountId::from(BOB))),
+				DispatchError::BadOrigin
+			);
+		});
+}
+
 #[test]
 fn reward_block_authors() {
 	ExtBuilder::default()
@@ -576,6 +828,11 @@ fn join_candidates_via_precompile() {
 					1000 * MSHD,
 					1000 * MSHD,
 				)),
+				staking_precompile_log_event(
+					b"CandidateJoined(address,uint256)",
+					vec![ALICE],
+					Some(1_000 * MSHD),
+				),
 				Event::EVM(pallet_evm::Event::<Runtime>::Executed(
 					staking_precompile_address,
 				)),
@@ -628,6 +885,7 @@ fn leave_candidates_via_precompile() {
 					AccountId::from(ALICE),
 					3,
 				)),
+				staking_precompile_log_event(b"CandidateLeft(address)", vec![ALICE], None),
 				Event::EVM(pallet_evm::Event::<Runtime>::Executed(
 					staking_precompile_address,
 				)),
@@ -679,6 +937,7 @@ fn go_online_offline_via_precompile() {
 					1,
 					AccountId::from(ALICE),
 				)),
+				staking_precompile_log_event(b"CandidateWentOffline(address)", vec![ALICE], None),
 				Event::EVM(pallet_evm::Event::<Runtime>::Executed(
 					staking_precompile_address,
 				)),
@@ -713,6 +972,7 @@ fn go_online_offline_via_precompile() {
 					1,
 					AccountId::from(ALICE),
 				)),
+				staking_precompile_log_event(b"CandidateBackOnline(address)", vec![ALICE], None),
 				Event::EVM(pallet_evm::Event::<Runtime>::Executed(
 					staking_precompile_address,
 				)),
@@ -773,6 +1033,11 @@ fn candidate_bond_more_less_via_precompile() {
 					1_000 * MSHD,
 					2_000 * MSHD,
 				)),
+				staking_precompile_log_event(
+					b"CandidateBondedMore(address,uint256)",
+					vec![ALICE],
+					Some(1_000 * MSHD),
+				),
 				Event::EVM(pallet_evm::Event::<Runtime>::Executed(
 					staking_precompile_address,
 				)),
@@ -815,6 +1080,11 @@ fn candidate_bond_more_less_via_precompile() {
 					2_000 * MSHD,
 					1_500 * MSHD,
 				)),
+				staking_precompile_log_event(
+					b"CandidateBondedLess(address,uint256)",
+					vec![ALICE],
+					Some(500 * MSHD),
+				),
 				Event::EVM(pallet_evm::Event::<Runtime>::Executed(
 					staking_precompile_address,
 				)),
@@ -888,6 +1158,11 @@ fn nominate_via_precompile() {
 						new_total: 2000 * MSHD,
 					},
 				)),
+				staking_precompile_log_event(
+					b"Nominated(address,address,uint256)",
+					vec![BOB, ALICE],
+					Some(1_000 * MSHD),
+				),
 				Event::EVM(pallet_evm::Event::<Runtime>::Executed(
 					staking_precompile_address,
 				)),
@@ -903,6 +1178,47 @@ fn nominate_via_precompile() {
 		});
 }
 
+#[test]
+fn nominate_below_min_via_precompile_reports_pallet_error() {
+	ExtBuilder::default()
+		.with_balances(vec![
+			(AccountId::from(ALICE), 3_000 * MSHD),
+			(AccountId::from(BOB), 3_000 * MSHD),
+		])
+		.with_collators(vec![(AccountId::from(ALICE), 1_000 * MSHD)])
+		.build()
+		.execute_with(|| {
+			let staking_precompile_address = H160::from_low_u64_be(2048);
+
+			// Bob tries to nominate Alice with less than the minimum nominator stake
+			let nomination_amount: U256 = MSHD.into();
+
+			// Construct the call data (selector, collator, nomination amount, counts)
+			let mut call_data = Vec::<u8>::from([0u8; 132]);
+			call_data[0..4].copy_from_slice(
+				&Keccak256::digest(b"nominate(address,uint256,uint256,uint256)")[0..4],
+			);
+			call_data[16..36].copy_from_slice(&ALICE);
+			nomination_amount.to_big_endian(&mut call_data[36..68]);
+
+			let context = evm::Context {
+				address: staking_precompile_address,
+				caller: H160::from(BOB),
+				apparent_value: From::from(0),
+			};
+
+			// The error names the pallet and the error that made the dispatch fail
+			assert_eq!(
+				Precompiles::execute(staking_precompile_address, &call_data, None, &context),
+				Some(Err(ExitError::Other(
+					"Parachain staking call via EVM failed: ParachainStaking::NomBondBelowMin"
+						.into()
+				)))
+			);
+			assert!(!ParachainStaking::is_nominator(&AccountId::from(BOB)));
+		});
+}
+
 #[test]
 fn leave_nominators_via_precompile() {
 	ExtBuilder::default()
@@ -975,6 +1291,7 @@ fn leave_nominators_via_precompile() {
 					AccountId::from(CHARLIE),
 					1_000 * MSHD,
 				)),
+				staking_precompile_log_event(b"NominatorLeft(address)", vec![CHARLIE], None),
 				Event::EVM(pallet_evm::Event::<Runtime>::Executed(
 					staking_precompile_address,
 				)),
@@ -1048,6 +1365,11 @@ fn revoke_nomination_via_precompile() {
 					500 * MSHD,
 					1_000 * MSHD,
 				)),
+				staking_precompile_log_event(
+					b"NominationRevoked(address,address)",
+					vec![CHARLIE, ALICE],
+					None,
+				),
 				Event::EVM(pallet_evm::Event::<Runtime>::Executed(
 					staking_precompile_address,
 				)),
@@ -1118,6 +1440,11 @@ fn nominator_bond_more_less_via_precompile() {
 					true,
 					2_000 * MSHD,
 				)),
+				staking_precompile_log_event(
+					b"NominationIncreased(address,address,uint256)",
+					vec![BOB, ALICE],
+					Some(500 * MSHD),
+				),
 				Event::EVM(pallet_evm::Event::<Runtime>::Executed(
 					staking_precompile_address,
 				)),
@@ -1163,6 +1490,11 @@ fn nominator_bond_more_less_via_precompile() {
 					true,
 					1_500 * MSHD,
 				)),
+				staking_precompile_log_event(
+					b"NominationDecreased(address,address,uint256)",
+					vec![BOB, ALICE],
+					Some(500 * MSHD),
+				),
 				Event::EVM(pallet_evm::Event::<Runtime>::Executed(
 					staking_precompile_address,
 				)),
@@ -1211,7 +1543,7 @@ fn is_nominator_via_precompile() {
 			let expected_true_result = Some(Ok(PrecompileOutput {
 				exit_status: ExitSucceed::Returned,
 				output: expected_bytes,
-				cost: 1000,
+				cost: staking_view_cost(StakingViewWeights::is_nominator(1)),
 				logs: Default::default(),
 			}));
 
@@ -1242,7 +1574,7 @@ fn is_nominator_via_precompile() {
 			let expected_false_result = Some(Ok(PrecompileOutput {
 				exit_status: ExitSucceed::Returned,
 				output: expected_bytes,
-				cost: 1000,
+				cost: staking_view_cost(StakingViewWeights::is_nominator(0)),
 				logs: Default::default(),
 			}));
 
@@ -1288,7 +1620,7 @@ fn is_candidate_via_precompile() {
 			let expected_true_result = Some(Ok(PrecompileOutput {
 				exit_status: ExitSucceed::Returned,
 				output: expected_bytes,
-				cost: 1000,
+				cost: staking_view_cost(StakingViewWeights::is_candidate(0)),
 				logs: Default::default(),
 			}));
 
@@ -1319,7 +1651,7 @@ fn is_candidate_via_precompile() {
 			let expected_false_result = Some(Ok(PrecompileOutput {
 				exit_status: ExitSucceed::Returned,
 				output: expected_bytes,
-				cost: 1000,
+				cost: staking_view_cost(StakingViewWeights::is_candidate(0)),
 				logs: Default::default(),
 			}));
 
@@ -1367,7 +1699,7 @@ fn is_selected_candidate_via_precompile() {
 			let expected_true_result = Some(Ok(PrecompileOutput {
 				exit_status: ExitSucceed::Returned,
 				output: expected_bytes,
-				cost: 1000,
+				cost: staking_view_cost(StakingViewWeights::is_selected_candidate(1)),
 				logs: Default::default(),
 			}));
 
@@ -1377,7 +1709,7 @@ fn is_selected_candidate_via_precompile() {
 					staking_precompile_address,
 					&alice_input_data,
 					None, // target_gas is not necessary right now because consumed none now
-					&evm_test_context(),
+					&evm_test_context(staking_precompile_address),
 				),
 				expected_true_result
 			);
@@ -1393,7 +1725,7 @@ fn is_selected_candidate_via_precompile() {
 			let expected_false_result = Some(Ok(PrecompileOutput {
 				exit_status: ExitSucceed::Returned,
 				output: expected_bytes,
-				cost: 1000,
+				cost: staking_view_cost(StakingViewWeights::is_selected_candidate(1)),
 				logs: Default::default(),
 			}));
 
@@ -1403,7 +1735,7 @@ fn is_selected_candidate_via_precompile() {
 					staking_precompile_address,
 					&bob_input_data,
 					None,
-					&evm_test_context(),
+					&evm_test_context(staking_precompile_address),
 				),
 				expected_false_result
 			);
@@ -1425,7 +1757,7 @@ fn min_nomination_via_precompile() {
 		let expected_result = Some(Ok(PrecompileOutput {
 			exit_status: ExitSucceed::Returned,
 			output: buffer.to_vec(),
-			cost: 1000,
+			cost: staking_view_cost(StakingViewWeights::min_nomination()),
 			logs: Default::default(),
 		}));
 
@@ -1462,7 +1794,7 @@ fn points_precompile_zero() {
 		let expected_zero_result = Some(Ok(PrecompileOutput {
 			exit_status: ExitSucceed::Returned,
 			output: expected_bytes,
-			cost: 1000,
+			cost: staking_view_cost(StakingViewWeights::points()),
 			logs: Default::default(),
 		}));
 
@@ -1472,7 +1804,7 @@ fn points_precompile_zero() {
 				staking_precompile_address,
 				&input_data,
 				None,
-				&evm_test_context(),
+				&evm_test_context(staking_precompile_address),
 			),
 			expected_zero_result
 		);
@@ -1510,7 +1842,7 @@ fn points_precompile_non_zero() {
 			let expected_result = Some(Ok(PrecompileOutput {
 				exit_status: ExitSucceed::Returned,
 				output: expected_bytes,
-				cost: 1000,
+				cost: staking_view_cost(StakingViewWeights::points()),
 				logs: Default::default(),
 			}));
 
@@ -1520,7 +1852,7 @@ fn points_precompile_non_zero() {
 					staking_precompile_address,
 					&input_data,
 					None,
-					&evm_test_context(),
+					&evm_test_context(staking_precompile_address),
 				),
 				expected_result
 			);
@@ -1546,15 +1878,348 @@ fn points_precompile_round_too_big_error() {
 				staking_precompile_address,
 				&input_data,
 				None,
-				&evm_test_context(),
+				&evm_test_context(staking_precompile_address),
 			),
-			Some(Err(ExitError::Other(
-				"Round is too large. 32 bit maximum".into()
-			)))
+			Some(Err(ExitError::Other("Value is too large for u32".into())))
 		);
 	})
 }
 
+#[test]
+fn candidate_and_nominator_views_via_precompile() {
+	ExtBuilder::default()
+		.with_balances(vec![
+			(AccountId::from(ALICE), 2_000 * MSHD),
+			(AccountId::from(BOB), 1_000 * MSHD),
+		])
+		.with_collators(vec![(AccountId::from(ALICE), 1_000 * MSHD)])
+		.with_nominations(vec![(
+			AccountId::from(BOB),
+			AccountId::from(ALICE),
+			500 * MSHD,
+		)])
+		.build()
+		.execute_with(|| {
+			let staking_precompile_address = H160::from_low_u64_be(2048);
+
+			// Builds the input data of a view from its signature and its address arguments
+			let input_data = |signature: &[u8], accounts: &[[u8; 20]]| {
+				let mut input = Keccak256::digest(signature)[0..4].to_vec();
+				for account in accounts {
+					input.extend_from_slice(&[0u8; 12]);
+					input.extend_from_slice(account);
+				}
+				input
+			};
+
+			// Asserts that a view returns the expected uint256 and charges the gas of its weight
+			let assert_view =
+				|signature: &[u8], accounts: &[[u8; 20]], expected: U256, weight: Weight| {
+					let mut expected_bytes = vec![0u8; 32];
+					expected.to_big_endian(&mut expected_bytes);
+					assert_eq!(
+						Precompiles::execute(
+							staking_precompile_address,
+							&input_data(signature, accounts),
+							None,
+							&evm_test_context(staking_precompile_address),
+						),
+						Some(Ok(PrecompileOutput {
+							exit_status: ExitSucceed::Returned,
+							output: expected_bytes,
+							cost: staking_view_cost(weight),
+							logs: Default::default(),
+						}))
+					);
+				};
+
+			assert_view(
+				b"candidate_count()",
+				&[],
+				1.into(),
+				StakingViewWeights::candidate_count(1),
+			);
+			assert_view(
+				b"candidate_bond(address)",
+				&[ALICE],
+				(1_000 * MSHD).into(),
+				StakingViewWeights::candidate_bond(1),
+			);
+			assert_view(
+				b"candidate_total_backing(address)",
+				&[ALICE],
+				(1_500 * MSHD).into(),
+				StakingViewWeights::candidate_total_backing(1),
+			);
+			assert_view(
+				b"collator_nomination_count(address)",
+				&[ALICE],
+				1.into(),
+				StakingViewWeights::collator_nomination_count(1),
+			);
+			assert_view(
+				b"nominator_total(address)",
+				&[BOB],
+				(500 * MSHD).into(),
+				StakingViewWeights::nominator_total(1),
+			);
+			assert_view(
+				b"nominator_nomination_count(address)",
+				&[BOB],
+				1.into(),
+				StakingViewWeights::nominator_nomination_count(1),
+			);
+			assert_view(
+				b"nomination_amount(address,address)",
+				&[BOB, ALICE],
+				(500 * MSHD).into(),
+				StakingViewWeights::nomination_amount(1),
+			);
+
+			// Unknown accounts are reported with zero values
+			assert_view(
+				b"candidate_bond(address)",
+				&[BOB],
+				U256::zero(),
+				StakingViewWeights::candidate_bond(0),
+			);
+			assert_view(
+				b"nominator_total(address)",
+				&[ALICE],
+				U256::zero(),
+				StakingViewWeights::nominator_total(0),
+			);
+			assert_view(
+				b"nomination_amount(address,address)",
+				&[ALICE, BOB],
+				U256::zero(),
+				StakingViewWeights::nomination_amount(0),
+			);
+
+			// The exit round is only set once the candidate requested to leave
+			assert_view(
+				b"candidate_exit_round(address)",
+				&[ALICE],
+				U256::zero(),
+				StakingViewWeights::candidate_exit_round(1),
+			);
+			assert_ok!(ParachainStaking::leave_candidates(
+				<Runtime as frame_system::Config>::Origin::signed(AccountId::from(ALICE)),
+				1
+			));
+			assert_view(
+				b"candidate_exit_round(address)",
+				&[ALICE],
+				3.into(),
+				StakingViewWeights::candidate_exit_round(1),
+			);
+		});
+}
+
+#[test]
+fn round_views_via_precompile() {
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 1_100 * MSHD)])
+		.with_collators(vec![(AccountId::from(ALICE), 1_000 * MSHD)])
+		.with_mappings(vec![(
+			NimbusId::from_slice(&ALICE_NIMBUS),
+			AccountId::from(ALICE),
+		)])
+		.build()
+		.execute_with(|| {
+			let staking_precompile_address = H160::from_low_u64_be(2048);
+
+			// Alice authors a block
+			set_parachain_inherent_data();
+			set_author(NimbusId::from_slice(&ALICE_NIMBUS));
+
+			// We start in round one
+			let mut expected_round = vec![0u8; 32];
+			expected_round[31] = 1;
+			assert_eq!(
+				Precompiles::execute(
+					staking_precompile_address,
+					&Keccak256::digest(b"round()")[0..4],
+					None,
+					&evm_test_context(staking_precompile_address),
+				),
+				Some(Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					output: expected_round,
+					cost: staking_view_cost(StakingViewWeights::round()),
+					logs: Default::default(),
+				}))
+			);
+
+			// Alice is the only selected candidate. The address[] is encoded as its offset, its
+			// length and its elements.
+			let mut expected_candidates = vec![0u8; 96];
+			expected_candidates[31] = 32;
+			expected_candidates[63] = 1;
+			expected_candidates[76..96].copy_from_slice(&ALICE);
+			assert_eq!(
+				Precompiles::execute(
+					staking_precompile_address,
+					&Keccak256::digest(b"selected_candidates()")[0..4],
+					None,
+					&evm_test_context(staking_precompile_address),
+				),
+				Some(Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					output: expected_candidates,
+					cost: staking_view_cost(StakingViewWeights::selected_candidates(1)),
+					logs: Default::default(),
+				}))
+			);
+
+			// Alice was awarded 20 points in round one, one per block
+			let mut input_data = Vec::<u8>::from([0u8; 68]);
+			input_data[0..4]
+				.copy_from_slice(&Keccak256::digest(b"awarded_pts(uint256,address)")[0..4]);
+			U256::one().to_big_endian(&mut input_data[4..36]);
+			input_data[48..68].copy_from_slice(&ALICE);
+
+			let mut expected_points = vec![0u8; 32];
+			expected_points[31] = 20;
+			assert_eq!(
+				Precompiles::execute(
+					staking_precompile_address,
+					&input_data,
+					None,
+					&evm_test_context(staking_precompile_address),
+				),
+				Some(Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					output: expected_points,
+					cost: staking_view_cost(StakingViewWeights::awarded_pts()),
+					logs: Default::default(),
+				}))
+			);
+		})
+}
+
+#[test]
+fn blake2f_precompile_eip152_test_vectors() {
+	ExtBuilder::default().build().execute_with(|| {
+		let blake2f_precompile_address = H160::from_low_u64_be(9);
+
+		// Test vectors from https://eips.ethereum.org/EIPS/eip-152#test-cases
+		// The input is rounds (4 bytes) | h (64 bytes) | m (128 bytes) | t (16 bytes) | f (1 byte)
+		let input = |rounds: &str, f: &str| {
+			hex::decode(format!(
+				"{}{}{}{}{}",
+				rounds,
+				"48c9bdf267e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5d182e6ad7f520e511f6c\
+				3e2b8c68059b6bbd41fbabd9831f79217e1319cde05b",
+				format!("616263{}", "00".repeat(125)),
+				"03000000000000000000000000000000",
+				f
+			))
+			.unwrap()
+		};
+		let execute = |input: &[u8]| {
+			Precompiles::execute(
+				blake2f_precompile_address,
+				input,
+				None,
+				&evm_test_context(blake2f_precompile_address),
+			)
+		};
+
+		// Vector 0: empty input
+		assert!(matches!(execute(&[]), Some(Err(_))));
+
+		// Vector 1: input one byte too short
+		let mut short_input = input("0000000c", "01");
+		short_input.remove(0);
+		assert!(matches!(execute(&short_input), Some(Err(_))));
+
+		// Vector 2: input one byte too long
+		let mut long_input = input("0000000c", "01");
+		long_input.insert(0, 0);
+		assert!(matches!(execute(&long_input), Some(Err(_))));
+
+		// Vector 3: malformed final block indicator flag
+		assert!(matches!(execute(&input("0000000c", "02")), Some(Err(_))));
+
+		// Vectors 4 to 7. The cost is one gas per round.
+		for (rounds, f, cost, output) in vec![
+			(
+				"00000000",
+				"01",
+				0,
+				"08c9bcf367e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5d282e6ad7f520e511f6c\
+				3e2b8c68059b9442be0454267ce079217e1319cde05b",
+			),
+			(
+				"0000000c",
+				"01",
+				12,
+				"ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252\
+				d5de4533cc9518d38aa8dbf1925ab92386edd4009923",
+			),
+			(
+				"0000000c",
+				"00",
+				12,
+				"75ab69d3190a562c51aef8d88f1c2775876944407270c42c9844252c26d2875298743e7f6d5ea2f2d3e8\
+				d226039cd31b4e426ac4f2d3d666a610c2116fde4735",
+			),
+			(
+				"00000001",
+				"01",
+				1,
+				"b63a380cb2897d521994a85234ee2c181b5f844d2c624c002677e9703449d2fba551b3a8333bcdf5f2f7\
+				e08993d53923de3d64fcc68c034e717b9293fed7a421",
+			),
+		] {
+			assert_eq!(
+				execute(&input(rounds, f)),
+				Some(Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					output: hex::decode(output).unwrap(),
+					cost,
+					logs: Default::default(),
+				}))
+			);
+		}
+	});
+}
+
+#[test]
+fn paused_precompile_is_not_executed() {
+	ExtBuilder::default().build().execute_with(|| {
+		let sha3fips_precompile_address = H160::from_low_u64_be(1024);
+		let execute = || {
+			Precompiles::execute(
+				sha3fips_precompile_address,
+				b"test",
+				None,
+				&evm_test_context(sha3fips_precompile_address),
+			)
+		};
+
+		assert!(matches!(execute(), Some(Ok(_))));
+
+		assert_ok!(moonshadow_runtime::PrecompilePause::pause_precompile(
+			root_origin(),
+			sha3fips_precompile_address
+		));
+		assert_eq!(
+			execute(),
+			Some(Err(evm::ExitError::Other(
+				"Precompile is paused by governance".into()
+			)))
+		);
+
+		assert_ok!(moonshadow_runtime::PrecompilePause::unpause_precompile(
+			root_origin(),
+			sha3fips_precompile_address
+		));
+		assert!(matches!(execute(), Some(Ok(_))));
+	});
+}
+
 fn run_with_system_weight<F>(w: Weight, mut assertions: F)
 where
 	F: FnMut() -> (),
@@ -1615,3 +2280,19 @@ fn multiplier_growth_simulator() {
 		println!("block = {} multiplier {:?}", blocks, multiplier);
 	}
 }
+
+#[test]
+fn runtime_upgrade_adds_dummy_code_under_newer_precompiles() {
+	ExtBuilder::default().build().execute_with(|| {
+		let dummy_code = vec![0x60, 0x00, 0x60, 0x00, 0xFD];
+		let code_at =
+			|address| moonshadow_runtime::EVM::account_codes(H160::from_low_u64_be(address));
+		assert!(code_at(9).is_empty());
+
+		moonshadow_runtime::Executive::execute_on_runtime_upgrade();
+
+		for address in &[9, 2050, 2051, 2052, 2053] {
+			assert_eq!(code_at(*address), dummy_code);
+		}
+	});
+}
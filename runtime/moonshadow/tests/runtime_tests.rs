@@ -19,15 +19,19 @@
 mod common;
 use common::*;
 
+use frame_support::{traits::OnFinalize, weights::DispatchClass};
+use moonshadow_runtime::BlockWeights;
 use nimbus_primitives::NimbusId;
 use pallet_evm::{Account as EVMAccount, AddressMapping, FeeCalculator, GenesisAccount};
 use sp_core::{Public, H160, H256, U256};
+use sp_runtime::transaction_validity::{InvalidTransaction, TransactionSource};
 
 use fp_rpc::runtime_decl_for_EthereumRuntimeRPCApi::EthereumRuntimeRPCApi;
 use fp_rpc::ConvertTransaction;
 use frame_support::assert_noop;
 use moonbeam_rpc_primitives_debug::runtime_decl_for_DebugRuntimeApi::DebugRuntimeApi;
 use moonbeam_rpc_primitives_txpool::runtime_decl_for_TxPoolRuntimeApi::TxPoolRuntimeApi;
+use sp_transaction_pool::runtime_api::runtime_decl_for_TaggedTransactionQueue::TaggedTransactionQueue;
 use std::collections::BTreeMap;
 use std::str::FromStr;
 
@@ -75,7 +79,37 @@ fn ethereum_runtime_rpc_api_account_basic() {
 #[test]
 fn ethereum_runtime_rpc_api_gas_price() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_eq!(Runtime::gas_price(), FixedGasPrice::min_gas_price());
+		assert_eq!(Runtime::gas_price(), BaseFee::min_gas_price());
+	});
+}
+
+#[test]
+fn ethereum_runtime_rpc_api_gas_price_follows_block_fullness() {
+	ExtBuilder::default().build().execute_with(|| {
+		let min_gas_price = Runtime::gas_price();
+
+		let max_normal_weight = BlockWeights::get()
+			.get(DispatchClass::Normal)
+			.max_total
+			.unwrap();
+		System::set_block_consumed_resources(max_normal_weight, 0);
+		BaseFee::on_finalize(System::block_number());
+
+		// A full block raises the gas price by 12.5%
+		assert_eq!(Runtime::gas_price(), min_gas_price * 9 / 8);
+	});
+}
+
+#[test]
+fn underpriced_ethereum_transaction_is_rejected_by_pool() {
+	ExtBuilder::default().build().execute_with(|| {
+		// The transaction pays 1 gwei per gas
+		pallet_base_fee::BaseFeePerGas::<Runtime>::put(U256::from(2_000_000_000u64));
+
+		assert_eq!(
+			Runtime::validate_transaction(TransactionSource::External, uxt(), Default::default()),
+			Err(InvalidTransaction::Payment.into())
+		);
 	});
 }
 
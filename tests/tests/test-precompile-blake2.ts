@@ -0,0 +1,35 @@
+import { expect } from "chai";
+import { GENESIS_ACCOUNT } from "../util/constants";
+import { customWeb3Request } from "../util/providers";
+import { describeDevMoonbeam } from "../util/setup-dev-tests";
+
+// Test vector 5 from https://eips.ethereum.org/EIPS/eip-152#test-cases
+const INPUT =
+  "0x0000000c" +
+  "48c9bdf267e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5" +
+  "d182e6ad7f520e511f6c3e2b8c68059b6bbd41fbabd9831f79217e1319cde05b" +
+  "6162630000000000000000000000000000000000000000000000000000000000" +
+  "0000000000000000000000000000000000000000000000000000000000000000" +
+  "0000000000000000000000000000000000000000000000000000000000000000" +
+  "0000000000000000000000000000000000000000000000000000000000000000" +
+  "0300000000000000000000000000000001";
+
+describeDevMoonbeam("Precompiles - blake2f", (context) => {
+  it("should be valid", async function () {
+    const txCall = await customWeb3Request(context.web3, "eth_call", [
+      {
+        from: GENESIS_ACCOUNT,
+        value: "0x0",
+        gas: "0x10000",
+        gasPrice: context.web3.utils.numberToHex(1_000_000_000),
+        to: "0x0000000000000000000000000000000000000009",
+        data: INPUT,
+      },
+    ]);
+
+    expect(txCall.result).equals(
+      "0xba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1" +
+        "7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+    );
+  });
+});
@@ -28,6 +28,7 @@ describeDevMoonbeam("Precompiles - precompiles dummy bytecode", (context) => {
       "0x0000000000000000000000000000000000000006",
       "0x0000000000000000000000000000000000000007",
       "0x0000000000000000000000000000000000000008",
+      "0x0000000000000000000000000000000000000009",
 
       "0x0000000000000000000000000000000000000400",
       "0x0000000000000000000000000000000000000401",